@@ -3,6 +3,7 @@ use std::sync::Arc;
 use mc_storage::OverrideHandle;
 use mp_digest_log::FindLogError;
 use mp_starknet::block::BlockTransactions;
+use mp_starknet::crypto::commitment::calculate_versioned_transaction_commitment;
 use mp_starknet::traits::hash::HasherT;
 use mp_starknet::traits::ThreadSafeCopy;
 use pallet_starknet::runtime_api::StarknetRuntimeApi;
@@ -45,6 +46,19 @@ where
                              db state ({storage_starknet_block_hash:?})"
                         ))
                     } else {
+                        // The header hash doesn't cover the transactions, check they are the ones committed to
+                        if let BlockTransactions::Full(transactions) = storage_starknet_block.transactions() {
+                            let header = storage_starknet_block.header();
+                            let transaction_commitment =
+                                calculate_versioned_transaction_commitment(transactions, header.commitment_version());
+                            if transaction_commitment != H256::from(header.transaction_commitment) {
+                                return Err(format!(
+                                    "Starknet transaction commitment mismatch: header ({:?}), transactions \
+                                     ({transaction_commitment:?})",
+                                    H256::from(header.transaction_commitment)
+                                ));
+                            }
+                        }
                         // Success, we write the Starknet to Substate hashes mapping to db
                         let mapping_commitment = mc_db::MappingCommitment {
                             block_hash: substrate_block_hash,
@@ -1,4 +1,7 @@
 use jsonrpsee::types::error::{CallError, ErrorObject};
+use mp_starknet::transaction::types::RPCTransactionConversionError;
+use sc_transaction_pool_api::error::{Error as PoolError, IntoPoolError};
+use sp_runtime::transaction_validity::InvalidTransaction;
 
 #[derive(thiserror::Error, Clone, Copy, Debug)]
 pub enum StarknetRpcApiError {
@@ -28,6 +31,10 @@ pub enum StarknetRpcApiError {
     ContractError = 40,
     #[error("Invalid contract class")]
     InvalidContractClass = 50,
+    #[error("Max fee is smaller than the minimal transaction cost (validation plus fee transfer)")]
+    InsufficientMaxFee = 53,
+    #[error("Account validation failed")]
+    ValidationFailure = 55,
     #[error("Too many storage keys requested")]
     ProofLimitExceeded = 10000,
     #[error("Too many keys provided in a filter")]
@@ -38,8 +45,31 @@ pub enum StarknetRpcApiError {
     FailedToFetchPendingTransactions = 38,
 }
 
+impl StarknetRpcApiError {
+    /// Maps the error of a rejected pool submission, `default` unless the pallet rejected the
+    /// transaction for not being able to pay its max fee or for exceeding the size limits of the
+    /// runtime.
+    pub fn from_submission_error<E: IntoPoolError>(error: E, default: Self) -> Self {
+        match error.into_pool_error() {
+            Ok(PoolError::InvalidTransaction(InvalidTransaction::Payment)) => Self::InsufficientMaxFee,
+            Ok(PoolError::InvalidTransaction(InvalidTransaction::ExhaustsResources)) => Self::ValidationFailure,
+            _ => default,
+        }
+    }
+}
+
 impl From<StarknetRpcApiError> for jsonrpsee::core::Error {
     fn from(err: StarknetRpcApiError) -> Self {
         jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(err as i32, err.to_string(), None::<()>)))
     }
 }
+
+/// Reports a transaction that failed its conversion with the JSON-RPC error the conversion error
+/// maps to, see [`RPCTransactionConversionError::rpc_error_code`].
+pub fn conversion_error(err: RPCTransactionConversionError) -> jsonrpsee::core::Error {
+    jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+        err.rpc_error_code() as i32,
+        err.rpc_error_message(),
+        None::<()>,
+    )))
+}
@@ -10,17 +10,19 @@ mod types;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use errors::StarknetRpcApiError;
+use errors::{conversion_error, StarknetRpcApiError};
 use jsonrpsee::core::{async_trait, RpcResult};
 use log::{error, info};
 use mc_rpc_core::utils::{to_declare_tx, to_deploy_account_tx, to_invoke_tx, to_rpc_contract_class, to_tx};
 pub use mc_rpc_core::StarknetRpcApiServer;
 use mc_storage::OverrideHandle;
 use mp_starknet::block::BlockTransactions;
-use mp_starknet::execution::types::Felt252Wrapper;
+use mp_starknet::execution::types::{ChainId, Felt252Wrapper};
 use mp_starknet::traits::hash::HasherT;
 use mp_starknet::traits::ThreadSafeCopy;
-use mp_starknet::transaction::types::{RPCTransactionConversionError, Transaction as MPTransaction, TxType};
+use mp_starknet::transaction::types::{
+    IntoRpcTransactions, RPCTransactionConversionError, Transaction as MPTransaction, TxType,
+};
 use pallet_starknet::runtime_api::{ConvertTransactionRuntimeApi, StarknetRuntimeApi};
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_network_sync::SyncingService;
@@ -132,8 +134,7 @@ where
         .ok_or("Failed to retrieve the substrate block id".to_string())
     }
 
-    /// Helper function to convert the chain_id from a felt to an ascii string. Ex:
-    /// 0x534e5f474f45524c49 => SN_GOERLI
+    /// Helper function to get the chain id of the runtime, used to compute transaction hashes.
     ///
     /// # Argument
     ///
@@ -141,18 +142,13 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if it can't retrieve the chain id or can't convert it to an ascii string.
-    fn chain_id_str(&self, hash: B::Hash) -> RpcResult<String> {
+    /// Returns an error if it can't retrieve the chain id.
+    fn runtime_chain_id(&self, hash: B::Hash) -> RpcResult<ChainId> {
         let chain_id = self.client.runtime_api().chain_id(hash).map_err(|_| {
             error!("fetch runtime chain id failed");
             StarknetRpcApiError::InternalServerError
         })?;
-        Ok(std::str::from_utf8(&chain_id.0.to_bytes_be())
-            .map_err(|_| {
-                error!("Couldn't convert chain id to string");
-                Into::<jsonrpsee::core::Error>::into(StarknetRpcApiError::InternalServerError)
-            })?
-            .to_string())
+        Ok(chain_id.into())
     }
 
     /// Helper function to get the substrate block number from a Starknet block id
@@ -604,7 +600,11 @@ where
         let best_block_hash = self.client.info().best_hash;
         let invoke_tx = to_invoke_tx(invoke_transaction)?;
 
-        let transaction: MPTransaction = invoke_tx.from_invoke(&self.chain_id_str(best_block_hash)?);
+        let transaction: MPTransaction =
+            invoke_tx.from_invoke(self.runtime_chain_id(best_block_hash)?).map_err(|e| {
+                error!("Invalid invoke transaction: {e}");
+                conversion_error(e)
+            })?;
         let extrinsic = self
             .client
             .runtime_api()
@@ -621,7 +621,7 @@ where
         self.pool.submit_one(&SPBlockId::hash(self.client.info().best_hash), TX_SOURCE, extrinsic).await.map_err(
             |e| {
                 error!("Failed to submit extrinsic: {:?}", e);
-                StarknetRpcApiError::ContractError
+                StarknetRpcApiError::from_submission_error(e, StarknetRpcApiError::ContractError)
             },
         )?;
 
@@ -649,7 +649,14 @@ where
             StarknetRpcApiError::InternalServerError
         })?;
 
-        let transaction: MPTransaction = deploy_account_transaction.from_deploy(&self.chain_id_str(best_block_hash)?);
+        // The conversion from the RPC transaction derived the sender address from the salt.
+        let contract_address = deploy_account_transaction.sender_address;
+        // The size limits of the runtime are checked when the transaction enters the pool.
+        let transaction: MPTransaction =
+            deploy_account_transaction.from_deploy(self.runtime_chain_id(best_block_hash)?).map_err(|e| {
+                error!("Invalid deploy account transaction: {e}");
+                conversion_error(e)
+            })?;
         let extrinsic = self
             .client
             .runtime_api()
@@ -665,12 +672,12 @@ where
 
         self.pool.submit_one(&SPBlockId::hash(best_block_hash), TX_SOURCE, extrinsic).await.map_err(|e| {
             error!("Failed to submit extrinsic: {:?}", e);
-            StarknetRpcApiError::InternalServerError
+            StarknetRpcApiError::from_submission_error(e, StarknetRpcApiError::InternalServerError)
         })?;
 
         Ok(DeployAccountTransactionResult {
             transaction_hash: transaction.hash.into(),
-            contract_address: transaction.sender_address.into(),
+            contract_address: contract_address.into(),
         })
     }
 
@@ -696,7 +703,7 @@ where
 
         let best_block_hash = self.client.info().best_hash;
 
-        let tx = to_tx(request, &self.chain_id_str(best_block_hash)?)?;
+        let tx = to_tx(request, self.runtime_chain_id(best_block_hash)?)?;
         let (actual_fee, gas_usage) = self
             .client
             .runtime_api()
@@ -730,7 +737,7 @@ where
         match block_transactions {
             BlockTransactions::Full(transactions) => {
                 let transaction = transactions.get(index).ok_or(StarknetRpcApiError::InvalidTxnIndex)?;
-                Ok(Transaction::try_from(transaction.clone()).map_err(|e| {
+                Ok(transaction.to_rpc().map_err(|e| {
                     error!("{:?}", e);
                     StarknetRpcApiError::InternalServerError
                 })?)
@@ -769,14 +776,12 @@ where
             new_root: block.header().global_state_root.into(),
             timestamp: block.header().block_timestamp,
             sequencer_address: block.header().sequencer_address.into(),
-            transactions: transactions
-                .into_iter()
-                .map(Transaction::try_from)
-                .collect::<Result<Vec<_>, RPCTransactionConversionError>>()
-                .map_err(|e| {
+            transactions: transactions.into_rpc().collect::<Result<Vec<_>, RPCTransactionConversionError>>().map_err(
+                |e| {
                     error!("{:#?}", e);
                     StarknetRpcApiError::InternalServerError
-                })?,
+                },
+            )?,
         };
 
         Ok(MaybePendingBlockWithTxs::Block(block_with_txs))
@@ -885,7 +890,7 @@ where
             StarknetRpcApiError::InternalServerError
         })?;
 
-        let transaction: MPTransaction = declare_tx.from_declare(&self.chain_id_str(best_block_hash)?);
+        let transaction: MPTransaction = declare_tx.from_declare(self.runtime_chain_id(best_block_hash)?);
         let extrinsic = self
             .client
             .runtime_api()
@@ -901,7 +906,7 @@ where
 
         self.pool.submit_one(&SPBlockId::hash(best_block_hash), TX_SOURCE, extrinsic).await.map_err(|e| {
             error!("Failed to submit extrinsic: {:?}", e);
-            StarknetRpcApiError::InternalServerError
+            StarknetRpcApiError::from_submission_error(e, StarknetRpcApiError::InternalServerError)
         })?;
 
         Ok(DeclareTransactionResult { transaction_hash: transaction.hash.into(), class_hash: FieldElement::ZERO })
@@ -939,10 +944,8 @@ where
 
         match block.transactions() {
             BlockTransactions::Full(transactions) => {
-                let find_tx = transactions
-                    .into_iter()
-                    .find(|tx| tx.hash == transaction_hash.into())
-                    .map(|tx| Transaction::try_from(tx.clone()));
+                let find_tx =
+                    transactions.into_iter().find(|tx| tx.hash == transaction_hash.into()).map(|tx| tx.to_rpc());
 
                 match find_tx {
                     Some(res_tx) => match res_tx {
@@ -13,6 +13,7 @@ use std::sync::Arc;
 
 use mp_starknet::storage::{StarknetStorageSchemaVersion, PALLET_STARKNET_SCHEMA};
 pub use overrides::*;
+use pallet_starknet::migrations;
 use pallet_starknet::runtime_api::StarknetRuntimeApi;
 use sc_client_api::backend::{Backend, StorageProvider};
 use scale_codec::Decode;
@@ -31,9 +32,22 @@ where
     BE: Backend<B> + 'static,
 {
     let mut overrides_map = BTreeMap::new();
+    // The older schemas decode their current block with the migration to the next schema.
+    let block_decoders: [(StarknetStorageSchemaVersion, BlockDecoder); 4] = [
+        (StarknetStorageSchemaVersion::V1, migrations::v2::decode_block),
+        (StarknetStorageSchemaVersion::V2, migrations::v3::decode_block),
+        (StarknetStorageSchemaVersion::V3, migrations::v4::decode_block),
+        (StarknetStorageSchemaVersion::V4, migrations::v5::decode_block),
+    ];
+    for (version, decode_block) in block_decoders {
+        overrides_map.insert(
+            version,
+            Box::new(SchemaOverride::with_block_decoder(client.clone(), decode_block)) as Box<dyn StorageOverride<_>>,
+        );
+    }
     overrides_map.insert(
-        StarknetStorageSchemaVersion::V1,
-        Box::new(SchemaV1Override::new(client.clone())) as Box<dyn StorageOverride<_>>,
+        StarknetStorageSchemaVersion::V5,
+        Box::new(SchemaOverride::new(client.clone())) as Box<dyn StorageOverride<_>>,
     );
 
     Arc::new(OverrideHandle {
@@ -14,9 +14,9 @@ use sp_api::ProvideRuntimeApi;
 use sp_io::hashing::twox_128;
 use sp_runtime::traits::Block as BlockT;
 
-mod schema_v1_override;
+mod schema_override;
 
-pub use self::schema_v1_override::SchemaV1Override;
+pub use self::schema_override::{BlockDecoder, SchemaOverride};
 use crate::onchain_storage_schema;
 
 /// A handle containing multiple entities implementing `StorageOverride`
@@ -21,19 +21,33 @@ use sp_storage::StorageKey;
 
 use super::{storage_key_build, storage_prefix_build, StorageOverride};
 
-/// An override for runtimes that use Schema V1
-pub struct SchemaV1Override<B: BlockT, C, BE> {
+/// Decodes the current block as stored with a given schema into the current layout, see the
+/// `decode_block` of [`pallet_starknet::migrations`].
+pub type BlockDecoder = fn(&mut &[u8]) -> Result<StarknetBlock, scale_codec::Error>;
+
+/// An override for runtimes that use a given schema.
+///
+/// Only the layout of the current block changed between the schemas, it's decoded with the
+/// [BlockDecoder] of the schema.
+pub struct SchemaOverride<B: BlockT, C, BE> {
     client: Arc<C>,
+    decode_block: BlockDecoder,
     _marker: PhantomData<(B, BE)>,
 }
 
-impl<B: BlockT, C, BE> SchemaV1Override<B, C, BE> {
+impl<B: BlockT, C, BE> SchemaOverride<B, C, BE> {
+    /// Creates an override for the current schema.
     pub fn new(client: Arc<C>) -> Self {
-        Self { client, _marker: PhantomData }
+        Self::with_block_decoder(client, |input| StarknetBlock::decode(input))
+    }
+
+    /// Creates an override for a schema whose current block is decoded by `decode_block`.
+    pub fn with_block_decoder(client: Arc<C>, decode_block: BlockDecoder) -> Self {
+        Self { client, decode_block, _marker: PhantomData }
     }
 }
 
-impl<B, C, BE> SchemaV1Override<B, C, BE>
+impl<B, C, BE> SchemaOverride<B, C, BE>
 where
     B: BlockT,
     C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
@@ -52,18 +66,16 @@ where
     }
 }
 
-impl<B, C, BE> StorageOverride<B> for SchemaV1Override<B, C, BE>
+impl<B, C, BE> StorageOverride<B> for SchemaOverride<B, C, BE>
 where
     B: BlockT,
     C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
     BE: Backend<B> + 'static,
 {
     fn current_block(&self, block_hash: B::Hash) -> Option<StarknetBlock> {
-        self.query_storage::<StarknetBlock>(
-            block_hash,
-            &StorageKey(storage_prefix_build(PALLET_STARKNET, STARKNET_CURRENT_BLOCK)),
-        )
-        .map(Into::into)
+        let key = StorageKey(storage_prefix_build(PALLET_STARKNET, STARKNET_CURRENT_BLOCK));
+        let data = self.client.storage(block_hash, &key).ok()??;
+        (self.decode_block)(&mut &data.0[..]).ok()
     }
 
     fn contract_class_by_address(
@@ -316,6 +316,8 @@ fn testnet_genesis(
                 ),
             ],
             fee_token_address,
+            // The devnet has a single fee token, for every transaction version.
+            strk_fee_token_address: fee_token_address,
             _phantom: Default::default(),
             chain_id,
         },
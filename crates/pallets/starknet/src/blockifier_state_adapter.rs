@@ -6,7 +6,9 @@ use blockifier::state::cached_state::ContractStorageKey;
 use blockifier::state::errors::StateError;
 use blockifier::state::state_api::{State, StateReader, StateResult};
 use mp_starknet::execution::types::{ClassHashWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper};
-use mp_starknet::state::StateChanges;
+use mp_starknet::state::{StateChanges, StateRollback};
+use mp_starknet::transaction::types::{StateDiffError, StateDiffWrapper};
+use sp_runtime::TransactionOutcome;
 use sp_std::sync::Arc;
 use starknet_api::api_core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::hash::StarkFelt;
@@ -24,6 +26,9 @@ use crate::{Config, Pallet};
 pub struct BlockifierStateAdapter<T: Config> {
     storage_update: BTreeMap<ContractStorageKey, StarkFelt>,
     class_hash_update: usize,
+    nonce_update: BTreeMap<ContractAddress, Nonce>,
+    deployed_contracts: BTreeMap<ContractAddress, ClassHash>,
+    declared_classes: BTreeSet<ClassHash>,
     _phantom: PhantomData<T>,
 }
 
@@ -36,11 +41,60 @@ where
         let n_contract_updated = BTreeSet::from_iter(keys.clone().map(|&(contract_address, _)| contract_address)).len();
         (n_contract_updated, keys.len(), self.class_hash_update)
     }
+
+    fn state_diff(&self) -> Result<StateDiffWrapper, StateDiffError> {
+        let mut state_diff = StateDiffWrapper::default();
+        // The storage updates are sorted by contract, so the updates of a contract are consecutive.
+        for ((contract_address, key), value) in &self.storage_update {
+            state_diff.register_storage_update(contract_address.0.0.into(), key.0.0.into(), (*value).into())?;
+        }
+        for (contract_address, nonce) in &self.nonce_update {
+            state_diff.register_nonce_update(contract_address.0.0.into(), nonce.0.into())?;
+        }
+        for (contract_address, class_hash) in &self.deployed_contracts {
+            state_diff.register_deployed_contract(contract_address.0.0.into(), class_hash.0.into())?;
+        }
+        for class_hash in &self.declared_classes {
+            state_diff.register_declared_class(class_hash.0.into())?;
+        }
+        Ok(state_diff)
+    }
+}
+
+/// The writes are made in a storage transaction layer, rolled back along with the tracked changes
+/// if `f` fails.
+impl<T: Config> StateRollback for BlockifierStateAdapter<T> {
+    fn with_rollback<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E> {
+        let storage_update = self.storage_update.clone();
+        let class_hash_update = self.class_hash_update;
+        let nonce_update = self.nonce_update.clone();
+        let deployed_contracts = self.deployed_contracts.clone();
+        let declared_classes = self.declared_classes.clone();
+
+        frame_support::storage::with_transaction_unchecked(|| match f(self) {
+            Ok(result) => TransactionOutcome::Commit(Ok(result)),
+            Err(error) => {
+                self.storage_update = storage_update;
+                self.class_hash_update = class_hash_update;
+                self.nonce_update = nonce_update;
+                self.deployed_contracts = deployed_contracts;
+                self.declared_classes = declared_classes;
+                TransactionOutcome::Rollback(Err(error))
+            }
+        })
+    }
 }
 
 impl<T: Config> Default for BlockifierStateAdapter<T> {
     fn default() -> Self {
-        Self { storage_update: BTreeMap::default(), class_hash_update: usize::default(), _phantom: PhantomData }
+        Self {
+            storage_update: BTreeMap::default(),
+            class_hash_update: usize::default(),
+            nonce_update: BTreeMap::default(),
+            deployed_contracts: BTreeMap::default(),
+            declared_classes: BTreeSet::default(),
+            _phantom: PhantomData,
+        }
     }
 }
 
@@ -98,16 +152,19 @@ impl<T: Config> State for BlockifierStateAdapter<T> {
     }
 
     fn increment_nonce(&mut self, contract_address: ContractAddress) -> StateResult<()> {
+        let address = contract_address;
         let contract_address: ContractAddressWrapper = contract_address.0.0.into();
-        let current_nonce = Pallet::<T>::nonce(contract_address);
+        let new_nonce = Pallet::<T>::nonce(contract_address) + 1;
 
-        crate::Nonces::<T>::insert(contract_address, current_nonce + 1);
+        crate::Nonces::<T>::insert(contract_address, new_nonce);
+        self.nonce_update.insert(address, Nonce(StarkFelt::new(new_nonce.into())?));
 
         Ok(())
     }
 
     fn set_class_hash_at(&mut self, contract_address: ContractAddress, class_hash: ClassHash) -> StateResult<()> {
         self.class_hash_update += 1;
+        self.deployed_contracts.insert(contract_address, class_hash);
         let contract_address: ContractAddressWrapper = contract_address.0.0.into();
         let class_hash: ClassHashWrapper = class_hash.0.into();
 
@@ -117,6 +174,7 @@ impl<T: Config> State for BlockifierStateAdapter<T> {
     }
 
     fn set_contract_class(&mut self, class_hash: &ClassHash, contract_class: ContractClass) -> StateResult<()> {
+        self.declared_classes.insert(*class_hash);
         let class_hash: ClassHashWrapper = class_hash.0.into();
         let contract_class: ContractClassWrapper = ContractClassWrapper::try_from(contract_class).unwrap();
 
@@ -10,9 +10,9 @@
 //! during the block lifecycle: on_finalize, on_initialize, on_runtime_upgrade, and offchain_worker.
 //!
 //! 3. Storage: Several storage items are defined, including Pending, CurrentBlock, BlockHash,
-//! ContractClassHashes, ContractClasses, Nonces, StorageView, LastKnownEthBlock, and
-//! FeeTokenAddress. These storage items are used to store and manage data related to the Starknet
-//! pallet.
+//! ContractClassHashes, ContractClasses, Nonces, StorageView, LastKnownEthBlock,
+//! FeeTokenAddress and StrkFeeTokenAddress. These storage items are used to store and manage data
+//! related to the Starknet pallet.
 //!
 //! 4. Genesis Configuration: The GenesisConfig struct is defined, which is used to set up the
 //! initial state of the pallet during genesis. The struct includes fields for contracts,
@@ -42,6 +42,8 @@ pub use pallet::*;
 pub mod blockifier_state_adapter;
 /// The implementation of the message type.
 pub mod message;
+/// Storage migrations.
+pub mod migrations;
 /// The Starknet pallet's runtime API
 pub mod runtime_api;
 /// State root logic.
@@ -66,7 +68,7 @@ use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use blockifier::execution::entry_point::CallInfo;
+use blockifier::abi::abi_utils::get_storage_var_address;
 use blockifier_state_adapter::BlockifierStateAdapter;
 use frame_support::pallet_prelude::*;
 use frame_support::traits::Time;
@@ -78,17 +80,20 @@ use mp_starknet::execution::types::{
     CallEntryPointWrapper, ClassHashWrapper, ContractAddressWrapper, ContractClassWrapper, EntryPointTypeWrapper,
     Felt252Wrapper,
 };
+use mp_starknet::fees::FeeTokenConfig;
 use mp_starknet::storage::{StarknetStorageSchemaVersion, PALLET_STARKNET_SCHEMA};
 use mp_starknet::traits::hash::{CryptoHasherT, DefaultHasher, HasherT};
+use mp_starknet::traits::limits::TxLimits;
 use mp_starknet::transaction::types::{
-    DeclareTransaction, DeployAccountTransaction, EventError, EventWrapper as StarknetEventType, InvokeTransaction,
-    Transaction, TransactionExecutionInfoWrapper, TransactionReceiptWrapper, TxType,
+    DeclareTransaction, DeployAccountTransaction, EventError, EventWrapper as StarknetEventType, FeeUnit,
+    InvokeTransaction, RPCTransactionConversionError, Transaction, TransactionExecutionInfoWrapper,
+    TransactionReceiptWrapper, TransactionValidationErrorWrapper, TxType, ValidationContext,
 };
+use mp_starknet::transaction::validate_events;
 use sp_core::U256;
 use sp_runtime::traits::UniqueSaturatedInto;
 use sp_runtime::DigestItem;
-use starknet_api::api_core::{ChainId, ContractAddress};
-use starknet_api::transaction::{EventContent, TransactionHash};
+use starknet_api::api_core::ChainId;
 
 use crate::alloc::string::ToString;
 use crate::types::{ContractStorageKeyWrapper, NonceWrapper, StorageKeyWrapper};
@@ -143,6 +148,14 @@ pub mod pallet {
         /// multiple pallets send unsigned transactions.
         #[pallet::constant]
         type UnsignedPriority: Get<TransactionPriority>;
+        /// The size limits of the transactions.
+        ///
+        /// They can't exceed the bounds of the stored types (`DefaultTxLimits`).
+        type TxLimits: TxLimits;
+        /// The max difference between the nonce of a transaction and the nonce of its sender for
+        /// the transaction to be admitted, see [`Transaction::check_nonce_gap`].
+        #[pallet::constant]
+        type MaxNonceGap: Get<u64>;
     }
 
     /// The Starknet pallet hooks.
@@ -166,7 +179,10 @@ pub mod pallet {
 
         /// Perform a module upgrade.
         fn on_runtime_upgrade() -> Weight {
-            Weight::zero()
+            migrations::v2::migrate::<T>()
+                .saturating_add(migrations::v3::migrate::<T>())
+                .saturating_add(migrations::v4::migrate::<T>())
+                .saturating_add(migrations::v5::migrate::<T>())
         }
 
         /// Run offchain tasks.
@@ -254,6 +270,11 @@ pub mod pallet {
     #[pallet::getter(fn fee_token_address)]
     pub(super) type FeeTokenAddress<T: Config> = StorageValue<_, ContractAddressWrapper, ValueQuery>;
 
+    /// The address of the STRK fee token ERC20 contract, the fees of v3 transactions are paid in.
+    #[pallet::storage]
+    #[pallet::getter(fn strk_fee_token_address)]
+    pub(super) type StrkFeeTokenAddress<T: Config> = StorageValue<_, ContractAddressWrapper, ValueQuery>;
+
     /// The chain id.
     #[pallet::storage]
     #[pallet::getter(fn chain_id)]
@@ -278,6 +299,8 @@ pub mod pallet {
         /// The address of the fee token.
         /// Must be set to the address of the fee token ERC20 contract.
         pub fee_token_address: ContractAddressWrapper,
+        /// The address of the STRK fee token, for the fees of v3 transactions.
+        pub strk_fee_token_address: ContractAddressWrapper,
         pub _phantom: PhantomData<T>,
         /// The chain id.
         pub chain_id: Felt252Wrapper,
@@ -291,6 +314,7 @@ pub mod pallet {
                 contract_classes: vec![],
                 storage: vec![],
                 fee_token_address: ContractAddressWrapper::default(),
+                strk_fee_token_address: ContractAddressWrapper::default(),
                 _phantom: PhantomData,
                 chain_id: Default::default(),
             }
@@ -303,7 +327,7 @@ pub mod pallet {
             <Pallet<T>>::store_block(U256::zero());
             frame_support::storage::unhashed::put::<StarknetStorageSchemaVersion>(
                 PALLET_STARKNET_SCHEMA,
-                &StarknetStorageSchemaVersion::V1,
+                &StarknetStorageSchemaVersion::V5,
             );
 
             for (address, class_hash) in self.contracts.iter() {
@@ -320,6 +344,7 @@ pub mod pallet {
             LastKnownEthBlock::<T>::set(None);
             // Set the fee token address from the genesis config.
             FeeTokenAddress::<T>::set(self.fee_token_address);
+            StrkFeeTokenAddress::<T>::set(self.strk_fee_token_address);
             // Set the chain id from the genesis config.
             ChainId::<T>::put(self.chain_id);
         }
@@ -364,6 +389,14 @@ pub mod pallet {
         StateDiffError,
         ContractNotFound,
         ReachedBoundedVecLimit,
+        TransactionTooLarge,
+        InvalidContractAddressSalt,
+        CalldataTooLong,
+        SignatureTooLong,
+        MissingL1Fee,
+        EmptyContractClass,
+        InvalidEntryPointSelector,
+        InvalidSenderAddress,
     }
 
     /// The Starknet pallet external functions.
@@ -412,10 +445,12 @@ pub mod pallet {
 
             // Get current block
             let block = Self::current_block();
-            // Get fee token address
-            let fee_token_address = Self::fee_token_address();
             let chain_id = Self::chain_id_str();
-            let transaction: Transaction = transaction.from_invoke(&chain_id);
+            let transaction: Transaction =
+                transaction.from_invoke(Self::chain_id().into()).map_err(|_| Error::<T>::InvalidEntryPointSelector)?;
+            Self::ensure_tx_limits(&transaction)?;
+            // Get fee token address
+            let fee_token_address = transaction.fee_token_address(&Self::fee_token_config());
             let call_info = transaction.execute(
                 &mut BlockifierStateAdapter::<T>::default(),
                 block.clone(),
@@ -425,42 +460,21 @@ pub mod pallet {
                 StarknetChainId(chain_id),
             );
             let receipt = match call_info {
-                Ok(TransactionExecutionInfoWrapper {
-                    validate_call_info: _validate_call_info,
-                    execute_call_info,
-                    fee_transfer_call_info,
-                    actual_fee,
-                    actual_resources: _actual_resources,
-                }) => {
-                    log!(debug, "Transaction executed successfully: {:?}", execute_call_info);
-
-                    let tx_hash = TransactionHash(transaction.hash.into());
-                    let events = match (execute_call_info, fee_transfer_call_info) {
-                        (Some(mut exec), Some(mut fee)) => {
-                            let mut events =
-                                Self::emit_events(&mut exec, tx_hash).map_err(|_| Error::<T>::EmitEventError)?;
-                            events.append(
-                                &mut Self::emit_events(&mut fee, tx_hash).map_err(|_| Error::<T>::EmitEventError)?,
-                            );
-                            events
+                Ok(execution_info) => {
+                    match &execution_info.revert_error {
+                        Some(reason) => log!(debug, "Transaction reverted: {}", reason),
+                        None => {
+                            log!(debug, "Transaction executed successfully: {:?}", execution_info.execute_call_info)
                         }
-                        (_, Some(mut fee)) => {
-                            Self::emit_events(&mut fee, tx_hash).map_err(|_| Error::<T>::EmitEventError)?
-                        }
-                        _ => Vec::default(),
-                    };
-
-                    TransactionReceiptWrapper {
-                        events: BoundedVec::try_from(events).map_err(|_| Error::<T>::ReachedBoundedVecLimit)?,
-                        transaction_hash: transaction.hash,
-                        tx_type: TxType::Invoke,
-                        actual_fee: actual_fee.0.into(),
-                        block_hash: block.header().hash(T::SystemHash::hasher()), // unwrap to check.
-                        block_number: block.header().block_number.as_u64(),
                     }
+
+                    let mut receipt = Self::build_receipt(execution_info, transaction.hash, TxType::Invoke)?;
+                    receipt.fee_unit = FeeUnit::from_tx_version(transaction.version);
+                    Self::emit_events(&receipt.events).map_err(|_| Error::<T>::EmitEventError)?;
+                    receipt
                 }
                 Err(e) => {
-                    log!(error, "Transaction execution failed: {:?}", e);
+                    log!(error, "Transaction execution failed: {} {:?}", transaction.to_log_string(), e);
                     return Err(Error::<T>::TransactionExecutionFailed.into());
                 }
             };
@@ -493,7 +507,11 @@ pub mod pallet {
 
             let chain_id = Self::chain_id_str();
 
-            let transaction: Transaction = transaction.from_declare(&chain_id);
+            // Check that the contract class isn't empty before hashing it
+            transaction.check_class_present().map_err(|_| Error::<T>::EmptyContractClass)?;
+
+            let transaction: Transaction = transaction.from_declare(Self::chain_id().into());
+            Self::ensure_tx_limits(&transaction)?;
             // Check that contract class is not None
             let contract_class = transaction.contract_class.clone().ok_or(Error::<T>::ContractClassMustBeSpecified)?;
 
@@ -509,7 +527,7 @@ pub mod pallet {
             // Get current block
             let block = Self::current_block();
             // Get fee token address
-            let fee_token_address = Self::fee_token_address();
+            let fee_token_address = transaction.fee_token_address(&Self::fee_token_config());
 
             // Parse contract class
             let contract_class = contract_class.try_into().or(Err(Error::<T>::InvalidContractClass))?;
@@ -524,42 +542,17 @@ pub mod pallet {
                 StarknetChainId(chain_id),
             );
             let receipt = match call_info {
-                Ok(TransactionExecutionInfoWrapper {
-                    validate_call_info: _validate_call_info,
-                    execute_call_info,
-                    fee_transfer_call_info,
-                    actual_fee,
-                    actual_resources: _actual_resources,
-                }) => {
-                    log!(trace, "Transaction executed successfully: {:?}", execute_call_info);
-
-                    let tx_hash = TransactionHash(transaction.hash.into());
-                    let events = match (execute_call_info, fee_transfer_call_info) {
-                        (Some(mut exec), Some(mut fee)) => {
-                            let mut events =
-                                Self::emit_events(&mut exec, tx_hash).map_err(|_| Error::<T>::EmitEventError)?;
-                            events.append(
-                                &mut Self::emit_events(&mut fee, tx_hash).map_err(|_| Error::<T>::EmitEventError)?,
-                            );
-                            events
-                        }
-                        (_, Some(mut fee)) => {
-                            Self::emit_events(&mut fee, tx_hash).map_err(|_| Error::<T>::EmitEventError)?
-                        }
-                        _ => Vec::default(),
-                    };
-
-                    TransactionReceiptWrapper {
-                        events: BoundedVec::try_from(events).map_err(|_| Error::<T>::ReachedBoundedVecLimit)?,
-                        transaction_hash: transaction.hash,
-                        tx_type: TxType::Declare,
-                        block_hash: block.header().hash(T::SystemHash::hasher()),
-                        block_number: block.header().block_number.as_u64(),
-                        actual_fee: actual_fee.0.into(),
-                    }
+                Ok(execution_info) => {
+                    log!(trace, "Transaction executed successfully: {:?}", execution_info.execute_call_info);
+
+                    let mut receipt = Self::build_receipt(execution_info, transaction.hash, TxType::Declare)?;
+                    receipt.class_hash = Some(class_hash);
+                    receipt.fee_unit = FeeUnit::from_tx_version(transaction.version);
+                    Self::emit_events(&receipt.events).map_err(|_| Error::<T>::EmitEventError)?;
+                    receipt
                 }
                 Err(e) => {
-                    log!(error, "Transaction execution failed: {:?}", e);
+                    log!(error, "Transaction execution failed: {} {:?}", transaction.to_log_string(), e);
                     return Err(Error::<T>::TransactionExecutionFailed.into());
                 }
             };
@@ -593,19 +586,31 @@ pub mod pallet {
             // This ensures that the function can only be called via unsigned transaction.
             ensure_none(origin)?;
 
-            // Check if contract is deployed
-            ensure!(
-                !ContractClassHashes::<T>::contains_key(transaction.sender_address),
-                Error::<T>::AccountAlreadyDeployed
-            );
-
             let chain_id = Self::chain_id_str();
-            let transaction: Transaction = transaction.from_deploy(&chain_id);
+            let deploy_account_transaction = transaction;
+            let transaction: Transaction = deploy_account_transaction
+                .clone()
+                .try_from_deploy::<T::TxLimits>(Self::chain_id().into())
+                .map_err(|e| match e {
+                    RPCTransactionConversionError::CalldataTooLong => Error::<T>::CalldataTooLong,
+                    RPCTransactionConversionError::SignatureTooLong => Error::<T>::SignatureTooLong,
+                    _ => Error::<T>::InvalidContractAddressSalt,
+                })?;
+
+            // The account is deployed at the address derived from the transaction, it must be the
+            // sender address its hash commits to.
+            let contract_address =
+                deploy_account_transaction.contract_address().map_err(|_| Error::<T>::InvalidContractAddressSalt)?;
+            ensure!(deploy_account_transaction.sender_address == contract_address, Error::<T>::InvalidSenderAddress);
+
+            // Check if contract is deployed
+            ensure!(!ContractClassHashes::<T>::contains_key(contract_address), Error::<T>::AccountAlreadyDeployed);
+            Self::ensure_tx_limits(&transaction)?;
 
             // Get current block
             let block = Self::current_block();
             // Get fee token address
-            let fee_token_address = Self::fee_token_address();
+            let fee_token_address = transaction.fee_token_address(&Self::fee_token_config());
             // Execute transaction
             let call_info = transaction.execute(
                 &mut BlockifierStateAdapter::<T>::default(),
@@ -616,42 +621,17 @@ pub mod pallet {
                 StarknetChainId(chain_id),
             );
             let receipt = match call_info {
-                Ok(TransactionExecutionInfoWrapper {
-                    validate_call_info: _validate_call_info,
-                    execute_call_info,
-                    fee_transfer_call_info,
-                    actual_fee,
-                    actual_resources: _actual_resources,
-                }) => {
-                    log!(trace, "Transaction executed successfully: {:?}", execute_call_info);
-
-                    let tx_hash = TransactionHash(transaction.hash.into());
-                    let events = match (execute_call_info, fee_transfer_call_info) {
-                        (Some(mut exec), Some(mut fee)) => {
-                            let mut events =
-                                Self::emit_events(&mut exec, tx_hash).map_err(|_| Error::<T>::EmitEventError)?;
-                            events.append(
-                                &mut Self::emit_events(&mut fee, tx_hash).map_err(|_| Error::<T>::EmitEventError)?,
-                            );
-                            events
-                        }
-                        (_, Some(mut fee)) => {
-                            Self::emit_events(&mut fee, tx_hash).map_err(|_| Error::<T>::EmitEventError)?
-                        }
-                        _ => Vec::default(),
-                    };
-
-                    TransactionReceiptWrapper {
-                        events: BoundedVec::try_from(events).map_err(|_| Error::<T>::ReachedBoundedVecLimit)?,
-                        transaction_hash: transaction.hash,
-                        tx_type: TxType::DeployAccount,
-                        block_hash: block.header().hash(T::SystemHash::hasher()),
-                        block_number: block.header().block_number.as_u64(),
-                        actual_fee: actual_fee.0.into(),
-                    }
+                Ok(execution_info) => {
+                    log!(trace, "Transaction executed successfully: {:?}", execution_info.execute_call_info);
+
+                    let mut receipt = Self::build_receipt(execution_info, transaction.hash, TxType::DeployAccount)?;
+                    receipt.contract_address = Some(contract_address);
+                    receipt.fee_unit = FeeUnit::from_tx_version(transaction.version);
+                    Self::emit_events(&receipt.events).map_err(|_| Error::<T>::EmitEventError)?;
+                    receipt
                 }
                 Err(e) => {
-                    log!(error, "Transaction execution failed: {:?}", e);
+                    log!(error, "Transaction execution failed: {} {:?}", transaction.to_log_string(), e);
                     return Err(Error::<T>::TransactionExecutionFailed.into());
                 }
             };
@@ -680,15 +660,19 @@ pub mod pallet {
         /// * Compute weight
         #[pallet::call_index(4)]
         #[pallet::weight({0})]
-        pub fn consume_l1_message(origin: OriginFor<T>, transaction: Transaction) -> DispatchResult {
+        pub fn consume_l1_message(origin: OriginFor<T>, mut transaction: Transaction) -> DispatchResult {
             // This ensures that the function can only be called via unsigned transaction.
             ensure_none(origin)?;
+            // The transaction is executed as an L1 handler, so it's checked as one whatever its type.
+            transaction.tx_type = TxType::L1Handler;
 
             // Check if contract is deployed
             ensure!(ContractClassHashes::<T>::contains_key(transaction.sender_address), Error::<T>::AccountNotDeployed);
+            // An L1 handler must have been paid for on L1.
+            transaction.check_l1_fee().map_err(|_| Error::<T>::MissingL1Fee)?;
 
             let block = Self::current_block();
-            let fee_token_address = Self::fee_token_address();
+            let fee_token_address = transaction.fee_token_address(&Self::fee_token_config());
             let chain_id = Self::chain_id_str();
             match transaction.execute(
                 &mut BlockifierStateAdapter::<T>::default(),
@@ -702,7 +686,7 @@ pub mod pallet {
                     log!(debug, "Transaction executed successfully: {:?}", v);
                 }
                 Err(e) => {
-                    log!(error, "Transaction execution failed: {:?}", e);
+                    log!(error, "Transaction execution failed: {} {:?}", transaction.to_log_string(), e);
                     return Err(Error::<T>::TransactionExecutionFailed.into());
                 }
             }
@@ -757,39 +741,69 @@ pub mod pallet {
         /// By default unsigned transactions are disallowed, but implementing the validator
         /// here we make sure that some particular calls (in this case all calls)
         /// are being whitelisted and marked as valid.
+        ///
+        /// The transaction of the call must fit in the limits of the runtime, see
+        /// [`Pallet::ensure_tx_limits`], it is admitted against the state of its sender, see
+        /// [`Transaction::validate_against`], and its nonce must fit in a `u64` to compute its
+        /// priority.
         fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
             // TODO: Call `__validate__` entrypoint of the contract. #69
             // The priority right now is the max u64 - nonce because for unsigned transactions we need to
             // determine an absolute priority. For now we use that for the benchmark (lowest nonce goes first)
             // otherwise we have a nonce error and everything fails.
             // Once we have a real fee market this is where we'll chose the most profitable transaction.
-            match call {
-                Call::invoke { transaction } => ValidTransaction::with_tag_prefix("starknet")
-                    .priority(u64::MAX - (TryInto::<u64>::try_into(transaction.nonce)).unwrap())
-                    .and_provides((transaction.sender_address, transaction.nonce))
-                    .longevity(64_u64)
-                    .propagate(true)
-                    .build(),
-                Call::declare { transaction } => ValidTransaction::with_tag_prefix("starknet")
-                    .priority(u64::MAX - (TryInto::<u64>::try_into(transaction.nonce)).unwrap())
-                    .and_provides((transaction.sender_address, transaction.nonce))
-                    .longevity(64_u64)
-                    .propagate(true)
-                    .build(),
-                Call::deploy_account { transaction } => ValidTransaction::with_tag_prefix("starknet")
-                    .priority(u64::MAX - (TryInto::<u64>::try_into(transaction.nonce)).unwrap())
-                    .and_provides((transaction.sender_address, transaction.nonce))
-                    .longevity(64_u64)
-                    .propagate(true)
-                    .build(),
-                Call::consume_l1_message { transaction } => ValidTransaction::with_tag_prefix("starknet")
-                    .priority(u64::MAX - (TryInto::<u64>::try_into(transaction.nonce)).unwrap())
-                    .and_provides((transaction.sender_address, transaction.nonce))
-                    .longevity(64_u64)
-                    .propagate(true)
-                    .build(),
-                _ => InvalidTransaction::Call.into(),
+            let chain_id = Self::chain_id().into();
+            let transaction = match call {
+                Call::invoke { transaction } => transaction.clone().from_invoke(chain_id).ok(),
+                Call::declare { transaction } => Some(transaction.clone().from_declare(chain_id)),
+                // The account is deployed at the address derived from the transaction, it must be the
+                // one it was submitted with.
+                Call::deploy_account { transaction } => match transaction.contract_address() {
+                    Ok(contract_address) if contract_address == transaction.sender_address => {
+                        transaction.clone().from_deploy(chain_id).ok()
+                    }
+                    _ => None,
+                },
+                // The transaction is executed as an L1 handler whatever its type.
+                Call::consume_l1_message { transaction } => {
+                    Some(Transaction { tx_type: TxType::L1Handler, ..transaction.clone() })
+                }
+                _ => None,
             }
+            .ok_or(InvalidTransaction::Call)?;
+            // The limits are checked here so that the pool, and the RPC submitting to it, rejects
+            // oversized transactions whatever their type.
+            Self::ensure_tx_limits(&transaction).map_err(|_| InvalidTransaction::ExhaustsResources)?;
+
+            let ctx = ValidationContext {
+                current_nonce: Self::nonce(transaction.sender_address)
+                    .try_into()
+                    .map_err(|_| InvalidTransaction::Call)?,
+                account_balance: Self::fee_token_balance(
+                    transaction.fee_token_address(&Self::fee_token_config()),
+                    transaction.sender_address,
+                ),
+                chain_id,
+                max_nonce_gap: T::MaxNonceGap::get(),
+                block_timestamp: Self::block_timestamp(),
+            };
+            transaction.validate_against(&ctx).map_err(|error| match error {
+                TransactionValidationErrorWrapper::InvalidTransactionHash => InvalidTransaction::BadProof,
+                TransactionValidationErrorWrapper::InvalidNonce { .. } => InvalidTransaction::Stale,
+                TransactionValidationErrorWrapper::NonceGapTooLarge { .. } => InvalidTransaction::Future,
+                TransactionValidationErrorWrapper::ZeroMaxFee
+                | TransactionValidationErrorWrapper::InsufficientBalance { .. }
+                | TransactionValidationErrorWrapper::MissingL1Fee => InvalidTransaction::Payment,
+                _ => InvalidTransaction::Call,
+            })?;
+            let nonce = u64::try_from(transaction.nonce).map_err(|_| InvalidTransaction::Call)?;
+
+            ValidTransaction::with_tag_prefix("starknet")
+                .priority(u64::MAX - nonce)
+                .and_provides((transaction.sender_address, transaction.nonce))
+                .longevity(64_u64)
+                .propagate(true)
+                .build()
         }
     }
 }
@@ -806,6 +820,26 @@ impl<T: Config> Pallet<T> {
         Self::current_block().header().hash(T::SystemHash::hasher())
     }
 
+    /// Ensures the transaction fits in the size limits of the runtime, including the calldata limit
+    /// of its type and the signature limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to check.
+    pub fn ensure_tx_limits(transaction: &Transaction) -> Result<(), Error<T>> {
+        let max_signature_size = <T::TxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+        let max_calldata_size = <T::TxLimits as TxLimits>::MaxCalldataSize::get() as usize;
+        ensure!(transaction.signature.len() <= max_signature_size, Error::<T>::SignatureTooLong);
+        ensure!(transaction.call_entrypoint.calldata.len() <= max_calldata_size, Error::<T>::TransactionTooLarge);
+        let max_tx_type_calldata_size = match transaction.tx_type {
+            TxType::Invoke => <T::TxLimits as TxLimits>::InvokeMaxCalldata::get(),
+            TxType::DeployAccount | TxType::Deploy => <T::TxLimits as TxLimits>::DeployConstructorMaxCalldata::get(),
+            TxType::Declare | TxType::L1Handler => <T::TxLimits as TxLimits>::MaxCalldataSize::get(),
+        } as usize;
+        ensure!(transaction.call_entrypoint.calldata.len() <= max_tx_type_calldata_size, Error::<T>::CalldataTooLong);
+        Ok(())
+    }
+
     /// convert chain_id
     #[inline(always)]
     pub fn chain_id_str() -> String {
@@ -868,7 +902,7 @@ impl<T: Config> Pallet<T> {
         let entrypoint = CallEntryPointWrapper::new(
             Some(class_hash),
             EntryPointTypeWrapper::External,
-            Some(function_selector),
+            Some(function_selector.into()),
             BoundedVec::try_from(calldata).unwrap_or_default(),
             address,
             ContractAddressWrapper::default(),
@@ -894,6 +928,37 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Returns the addresses of the fee tokens, see [`Transaction::fee_token_address`].
+    pub fn fee_token_config() -> FeeTokenConfig {
+        FeeTokenConfig { eth: Self::fee_token_address(), strk: Self::strk_fee_token_address() }
+    }
+
+    /// Returns the balance of an account in a fee token.
+    ///
+    /// The balance is an ERC20 `Uint256` stored as its low and high 128 bits. A balance with a
+    /// non-zero high part exceeds any max fee, so it's returned as [`Felt252Wrapper::MAX`].
+    ///
+    /// # Arguments
+    ///
+    /// * `fee_token_address` - The fee token, see [`Transaction::fee_token_address`].
+    /// * `account` - The account holding the balance.
+    pub fn fee_token_balance(
+        fee_token_address: ContractAddressWrapper,
+        account: ContractAddressWrapper,
+    ) -> Felt252Wrapper {
+        let keys = get_storage_var_address("ERC20_balances", &[account.into()]).ok().and_then(|key| {
+            let low_key = Felt252Wrapper::from(key.0.0);
+            Some((low_key, low_key.checked_add(Felt252Wrapper::ONE)?))
+        });
+        match keys {
+            Some((_, high_key)) if Self::storage((fee_token_address, high_key)) != Felt252Wrapper::ZERO => {
+                Felt252Wrapper::MAX
+            }
+            Some((low_key, _)) => Self::storage((fee_token_address, low_key)),
+            None => Felt252Wrapper::ZERO,
+        }
+    }
+
     /// Get storage value at
     pub fn get_storage_at(
         contract_address: ContractAddressWrapper,
@@ -921,35 +986,44 @@ impl<T: Config> Pallet<T> {
         let block_timestamp = Self::block_timestamp();
         let transaction_count = pending.len() as u128;
 
-        let mut transactions: Vec<Transaction> = Vec::with_capacity(pending.len());
-        let mut receipts: Vec<TransactionReceiptWrapper> = Vec::with_capacity(pending.len());
-
-        // For loop to iterate once on pending.
-        for (transaction, receipt) in pending.into_iter() {
-            transactions.push(transaction);
-            receipts.push(receipt);
-        }
+        // Split the pending transactions from their receipts, completed below.
+        let (transactions, mut receipts): (Vec<Transaction>, Vec<TransactionReceiptWrapper>) =
+            pending.into_iter().unzip();
 
         let events = Self::pending_events();
-        let (transaction_commitment, event_commitment) =
-            commitment::calculate_commitments::<T::SystemHash>(&transactions, &events);
         let protocol_version = None;
         let extra_data = None;
 
+        let mut header = StarknetHeader::new(
+            parent_block_hash,
+            block_number,
+            global_state_root,
+            Felt252Wrapper::try_from(&sequencer_address).unwrap(),
+            block_timestamp,
+            transaction_count,
+            Felt252Wrapper::ZERO,
+            events.len() as u128,
+            Felt252Wrapper::ZERO,
+            protocol_version,
+            extra_data,
+        );
+        // Always the Pedersen rules until headers carry a Starknet version, see
+        // `Header::commitment_version`.
+        let (transaction_commitment, event_commitment) =
+            commitment::calculate_versioned_commitments(&transactions, &events, header.commitment_version());
+        header.transaction_commitment = transaction_commitment.try_into().unwrap();
+        header.event_commitment = event_commitment.try_into().unwrap();
+        // The hash of the block only covers its header, the receipts can be completed with it.
+        let blockhash = header.hash(T::SystemHash::hasher());
+        for receipt in receipts.iter_mut() {
+            receipt.block_number = Some(block_number.as_u64());
+            receipt.block_hash = Some(blockhash);
+            receipt.block_timestamp = block_timestamp;
+            receipt.sequencer_address = Felt252Wrapper::try_from(&sequencer_address).unwrap();
+        }
+
         let block = StarknetBlock::new(
-            StarknetHeader::new(
-                parent_block_hash,
-                block_number,
-                global_state_root,
-                Felt252Wrapper::try_from(&sequencer_address).unwrap(),
-                block_timestamp,
-                transaction_count,
-                transaction_commitment.try_into().unwrap(),
-                events.len() as u128,
-                event_commitment.try_into().unwrap(),
-                protocol_version,
-                extra_data,
-            ),
+            header,
             // Safe because `transactions` is build from the `pending` bounded vec,
             // which has the same size limit of `MaxTransactions`
             BlockTransactions::Full(BoundedVec::try_from(transactions).unwrap()),
@@ -958,7 +1032,6 @@ impl<T: Config> Pallet<T> {
         // Save the current block.
         CurrentBlock::<T>::put(block.clone());
         // Save the block number <> hash mapping.
-        let blockhash = block.header().hash(T::SystemHash::hasher());
         BlockHash::<T>::insert(block_number, blockhash);
         Pending::<T>::kill();
         PendingEvents::<T>::kill();
@@ -967,65 +1040,63 @@ impl<T: Config> Pallet<T> {
         frame_system::Pallet::<T>::deposit_log(digest);
     }
 
-    /// Emit events from the call info.
+    /// Emit the events of a transaction receipt in substrate and append them to the pending
+    /// events.
     ///
     /// # Arguments
     ///
-    /// * `call_info` - The call info.
+    /// * `events` - The events of the receipt, see
+    ///   [`mp_starknet::transaction::types::TransactionExecutionInfoWrapper::into_receipt`].
     ///
-    /// # Returns
+    /// # Error
     ///
-    /// The result of the operation.
-    #[inline(always)]
-    fn emit_events(call_info: &mut CallInfo, tx_hash: TransactionHash) -> Result<Vec<StarknetEventType>, EventError> {
-        let mut events = Vec::new();
-
-        call_info.execution.events.sort_by_key(|ordered_event| ordered_event.order);
-        for ordered_event in &call_info.execution.events {
-            let event_type = Self::emit_event(&ordered_event.event, call_info.call.storage_address, tx_hash)?;
-            events.push(event_type);
+    /// Returns an error if there are too many pending events.
+    fn emit_events(events: &[StarknetEventType]) -> Result<(), EventError> {
+        for event in events {
+            log!(debug, "Transaction event: {:?}", event);
+            Self::deposit_event(Event::StarknetEvent(event.clone()));
+            PendingEvents::<T>::try_append(event.clone()).map_err(|_| EventError::TooManyEvents)?;
         }
-
-        for inner_call in &mut call_info.inner_calls {
-            inner_call.execution.events.sort_by_key(|ordered_event| ordered_event.order);
-            for ordered_event in &inner_call.execution.events {
-                let event_type = Self::emit_event(&ordered_event.event, inner_call.call.storage_address, tx_hash)?;
-                events.push(event_type);
-            }
-        }
-
-        Ok(events)
+        Ok(())
     }
 
-    /// Emit an event from the call info in substrate.
+    /// Builds the receipt of an executed transaction.
     ///
-    /// # Arguments
+    /// The transaction is already executed and charged, so if its events don't fit in the receipt,
+    /// see [`validate_events`], the ones that don't are dropped and the receipt is flagged as
+    /// truncated rather than failing the transaction.
     ///
-    /// * `event` - The Starknet event.
-    /// * `from_address` - The contract address that emitted the event.
-    ///
-    /// # Error
+    /// # Arguments
     ///
-    /// Returns an error if the event construction fails.
-    #[inline(always)]
-    fn emit_event(
-        event: &EventContent,
-        from_address: ContractAddress,
-        tx_hash: TransactionHash,
-    ) -> Result<StarknetEventType, EventError> {
-        log!(debug, "Transaction event: {:?}", event);
-        let sn_event = StarknetEventType::builder()
-            .with_event_content(event.clone())
-            .with_from_address(from_address)
-            .with_transaction_hash(tx_hash)
-            .build()?;
-        Self::deposit_event(Event::StarknetEvent(sn_event.clone()));
-
-        PendingEvents::<T>::try_append(sn_event.clone()).map_err(|_| EventError::TooManyEvents)?;
-        Ok(sn_event)
+    /// * `execution_info` - The execution info of the transaction.
+    /// * `tx_hash` - The hash of the transaction.
+    /// * `tx_type` - The type of the transaction.
+    fn build_receipt(
+        execution_info: TransactionExecutionInfoWrapper,
+        tx_hash: Felt252Wrapper,
+        tx_type: TxType,
+    ) -> Result<TransactionReceiptWrapper, Error<T>> {
+        match validate_events(&execution_info.events()) {
+            Ok(()) => execution_info.into_receipt(tx_hash, tx_type).map_err(Self::receipt_error),
+            Err(error) => {
+                log!(warn, "Truncating the events of transaction {:?}: {:?}", tx_hash, error);
+                Ok(execution_info.into_truncated_receipt(tx_hash, tx_type))
+            }
+        }
+    }
+
+    /// Maps an error raised while building a transaction receipt to the pallet error.
+    fn receipt_error(error: EventError) -> Error<T> {
+        match error {
+            EventError::TooManyEvents => Error::<T>::ReachedBoundedVecLimit,
+            _ => Error::<T>::EmitEventError,
+        }
     }
 
     /// Estimate the fee associated with transaction
+    ///
+    /// Fails with [`Error::TransactionExecutionFailed`] if the execution of the transaction
+    /// reverts.
     pub fn estimate_fee(transaction: Transaction) -> Result<(u64, u64), DispatchError> {
         // Check if contract is deployed
         ensure!(ContractClassHashes::<T>::contains_key(transaction.sender_address), Error::<T>::AccountNotDeployed);
@@ -1036,9 +1107,15 @@ impl<T: Config> Pallet<T> {
             Self::current_block(),
             TxType::Invoke,
             None,
-            Self::fee_token_address(),
+            transaction.fee_token_address(&Self::fee_token_config()),
             ChainId(chain_id),
         ) {
+            // A reverted transaction is still charged, but the fee of a failing transaction isn't
+            // worth quoting.
+            Ok(v) if v.revert_error.is_some() => {
+                log!(error, "Transaction execution reverted: {:?}", v.revert_error);
+                Err(Error::<T>::TransactionExecutionFailed.into())
+            }
             Ok(v) => {
                 log!(debug, "Transaction executed successfully: {:?}", v);
                 if let Some(gas_usage) = v.actual_resources.get("l1_gas_usage") {
@@ -1,7 +1,4 @@
-use frame_support::BoundedVec;
-use mp_starknet::execution::types::{
-    CallEntryPointWrapper, ContractAddressWrapper, EntryPointTypeWrapper, Felt252Wrapper,
-};
+use mp_starknet::execution::types::{ChainId, Felt252Wrapper};
 use mp_starknet::transaction::types::Transaction;
 use scale_codec::{Decode, Encode};
 use serde::Deserialize;
@@ -42,9 +39,15 @@ pub fn get_messages_events(from_block: u64, to_block: u64) -> String {
 }
 
 impl Message {
-    /// Converts a `Message` into a transaction object.
-    pub fn try_into_transaction(&self) -> Result<Transaction, OffchainWorkerError> {
-        // Data at least contains a nonce and at some point the fees.
+    /// Converts a `Message` into an L1 handler transaction, see [`Transaction::from_l1_message`].
+    ///
+    /// The data of a `LogMessageToL2` event is the ABI encoding of its payload, nonce and fee: the
+    /// offset of the payload, the nonce, the fee, then the length and the elements of the payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id` - The chain id of the network, used to hash the transaction.
+    pub fn try_into_transaction(&self, chain_id: ChainId) -> Result<Transaction, OffchainWorkerError> {
         if self.data.is_empty() {
             return Err(OffchainWorkerError::EmptyData);
         }
@@ -60,68 +63,105 @@ impl Message {
             Err(_) => return Err(OffchainWorkerError::ToTransactionError),
         };
 
-        // Add the from address here so it's directly in the calldata.
-        let char_vec = format!("{:}{:}", self.topics[1].trim_start_matches("0x"), self.data.trim_start_matches("0x"))
-            .chars()
-            .collect::<Vec<char>>();
-        // Split the data String into values. (The event Log(a: uin256, b: uin256, c: uin256) logs a single
-        // string which is the concatenation of those fields).
-        let data_map = char_vec.chunks(64).map(|chunk| chunk.iter().collect::<String>());
-        // L1 message nonce.
-        let nonce =
-            Felt252Wrapper::from_hex_be(&data_map.clone().last().ok_or(OffchainWorkerError::ToTransactionError)?)
-                .map_err(|_| OffchainWorkerError::ToTransactionError)?;
-        let mut calldata: Vec<Felt252Wrapper> = Vec::new();
-        for val in data_map.take(self.data.len() - 2) {
-            calldata.push(match Felt252Wrapper::from_hex_be(val.as_str()) {
-                Ok(f) => f,
-                Err(_) => return Err(OffchainWorkerError::ToTransactionError),
-            })
-        }
-        let calldata = BoundedVec::try_from(calldata).map_err(|_| OffchainWorkerError::ToTransactionError)?;
-        let call_entrypoint = CallEntryPointWrapper {
-            class_hash: None,
-            entrypoint_type: EntryPointTypeWrapper::L1Handler,
-            entrypoint_selector: Some(selector),
-            calldata,
-            storage_address: sender_address,
-            caller_address: ContractAddressWrapper::default(),
+        // L1 sender address, an Ethereum address left padded to 32 bytes in the topic.
+        let from_address = Felt252Wrapper::from_hex_be(self.topics[1].as_str())
+            .and_then(|felt| felt.try_into_eth_address())
+            .map(Felt252Wrapper::from_eth_address)
+            .map_err(|_| OffchainWorkerError::ToTransactionError)?;
+
+        let char_vec = self.data.trim_start_matches("0x").chars().collect::<Vec<char>>();
+        // Split the data String into its 32 bytes words.
+        let data = char_vec
+            .chunks(64)
+            .map(|chunk| Felt252Wrapper::from_hex_be(&chunk.iter().collect::<String>()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| OffchainWorkerError::ToTransactionError)?;
+        let (nonce, paid_fee_on_l1, payload) = match data.as_slice() {
+            [_payload_offset, nonce, fee, payload_len, payload @ ..]
+                if u64::try_from(*payload_len).ok() == Some(payload.len() as u64) =>
+            {
+                (*nonce, *fee, payload)
+            }
+            _ => return Err(OffchainWorkerError::ToTransactionError),
         };
-        Ok(Transaction { sender_address, nonce, call_entrypoint, ..Transaction::default() })
+
+        Transaction::from_l1_message(
+            from_address,
+            sender_address,
+            selector.into(),
+            payload,
+            nonce,
+            paid_fee_on_l1,
+            chain_id,
+        )
+        .map_err(|_| OffchainWorkerError::ToTransactionError)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use frame_support::bounded_vec;
+    use frame_support::{bounded_vec, BoundedVec};
+    use mp_starknet::crypto::commitment::calculate_l1_handler_tx_hash;
     use mp_starknet::execution::types::{CallEntryPointWrapper, ContractAddressWrapper, EntryPointTypeWrapper};
-    use mp_starknet::transaction::types::Transaction;
+    use mp_starknet::transaction::types::{Transaction, TxType};
     use pretty_assertions;
 
     use super::*;
     use crate::offchain_worker::OffchainWorkerError;
 
+    /// ABI encoding of the data of a `LogMessageToL2` event with a nonce of 1, a fee of 2 and a
+    /// payload of `[1]`.
+    const DATA: &str = concat!(
+        "0x",
+        "0000000000000000000000000000000000000000000000000000000000000060",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    );
+
+    fn chain_id() -> ChainId {
+        ChainId(Felt252Wrapper::from(42_u64))
+    }
+
     #[test]
     fn test_try_into_transaction_correct_message_should_work() {
         let felt_one = Felt252Wrapper::ONE;
         let sender_address = felt_one;
         let hex = "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned();
         let test_message: Message =
-            Message { topics: vec![hex.clone(), hex.clone(), hex.clone(), hex.clone()], data: hex };
+            Message { topics: vec![hex.clone(), hex.clone(), hex.clone(), hex], data: DATA.to_owned() };
+        let calldata: BoundedVec<_, _> = bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::ONE];
         let expected_tx = Transaction {
+            tx_type: TxType::L1Handler,
+            version: 0,
+            hash: calculate_l1_handler_tx_hash(sender_address, felt_one, &calldata, felt_one, chain_id()),
+            signature: BoundedVec::default(),
             sender_address,
             nonce: Felt252Wrapper::ONE,
             call_entrypoint: CallEntryPointWrapper {
                 class_hash: None,
                 entrypoint_type: EntryPointTypeWrapper::L1Handler,
-                entrypoint_selector: Some(felt_one),
-                calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::ONE],
+                entrypoint_selector: Some(felt_one.into()),
+                calldata,
                 storage_address: felt_one,
                 caller_address: ContractAddressWrapper::default(),
             },
-            ..Transaction::default()
+            contract_class: None,
+            contract_address_salt: None,
+            max_fee: Felt252Wrapper::ZERO,
+            paid_fee_on_l1: Some(Felt252Wrapper::TWO),
         };
-        pretty_assertions::assert_eq!(test_message.try_into_transaction().unwrap(), expected_tx);
+        pretty_assertions::assert_eq!(test_message.try_into_transaction(chain_id()).unwrap(), expected_tx);
+    }
+
+    #[test]
+    fn test_try_into_transaction_payload_length_mismatch_should_fail() {
+        let hex = "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned();
+        // The data announces a payload of one element but has none.
+        let data = DATA[..DATA.len() - 64].to_owned();
+        let test_message: Message = Message { topics: vec![hex.clone(), hex.clone(), hex.clone(), hex], data };
+        assert_eq!(test_message.try_into_transaction(chain_id()).unwrap_err(), OffchainWorkerError::ToTransactionError);
     }
 
     #[test]
@@ -129,7 +169,7 @@ mod test {
         let hex = "0x1".to_owned();
         let test_message: Message =
             Message { topics: vec![hex.clone(), hex.clone(), "foo".to_owned(), hex.clone()], data: hex };
-        assert_eq!(test_message.try_into_transaction().unwrap_err(), OffchainWorkerError::ToTransactionError);
+        assert_eq!(test_message.try_into_transaction(chain_id()).unwrap_err(), OffchainWorkerError::ToTransactionError);
     }
 
     #[test]
@@ -137,13 +177,22 @@ mod test {
         let hex = "0x1".to_owned();
         let test_message: Message =
             Message { topics: vec![hex.clone(), hex.clone(), hex.clone(), "foo".to_owned()], data: hex };
-        assert_eq!(test_message.try_into_transaction().unwrap_err(), OffchainWorkerError::ToTransactionError);
+        assert_eq!(test_message.try_into_transaction(chain_id()).unwrap_err(), OffchainWorkerError::ToTransactionError);
+    }
+    #[test]
+    fn test_try_into_transaction_from_address_over_20_bytes_should_fail() {
+        let hex = "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned();
+        let from_address = "0x0000000000000000000000010000000000000000000000000000000000000001".to_owned();
+        let test_message: Message =
+            Message { topics: vec![hex.clone(), from_address, hex.clone(), hex.clone()], data: hex };
+        assert_eq!(test_message.try_into_transaction(chain_id()).unwrap_err(), OffchainWorkerError::ToTransactionError);
     }
+
     #[test]
     fn test_try_into_transaction_empty_data_should_fail() {
         let hex = "0x1".to_owned();
         let test_message: Message =
             Message { topics: vec![hex.clone(), hex.clone(), hex.clone(), hex], data: "".to_owned() };
-        assert_eq!(test_message.try_into_transaction().unwrap_err(), OffchainWorkerError::EmptyData);
+        assert_eq!(test_message.try_into_transaction(chain_id()).unwrap_err(), OffchainWorkerError::EmptyData);
     }
 }
@@ -0,0 +1,501 @@
+//! Migrations of the pallet storage schema.
+
+/// Migration from the storage schema V1 to V2.
+///
+/// V2 adds the fee unit, the revert reason, the block timestamp, the sequencer address, the
+/// declared class hash and the deployed contract address to the transaction receipts and stores the
+/// contract address salt of the transactions as a felt instead of a `U256`, so the transactions and
+/// receipts stored in `CurrentBlock` and `Pending` are re-encoded. Receipts stored with V1 predate
+/// v3 transactions, so their fee is in wei, and they don't record the declared class hash nor the
+/// deployed contract address.
+///
+/// The transactions and receipts are written with the current layout, so the storage ends up in
+/// V5 without going through [`v3::migrate`], [`v4::migrate`] and [`v5::migrate`].
+pub mod v2 {
+    use alloc::vec::Vec;
+
+    use frame_support::pallet_prelude::*;
+    use mp_starknet::block::{Block as StarknetBlock, BlockTransactions, Header as StarknetHeader, MaxTransactions};
+    use mp_starknet::execution::types::{
+        CallEntryPointWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper,
+    };
+    use mp_starknet::storage::{StarknetStorageSchemaVersion, PALLET_STARKNET_SCHEMA};
+    use mp_starknet::transaction::types::{
+        EventWrapper, ExecutionStatus, FeeUnit, MaxArraySize, Transaction, TransactionReceiptWrapper, TxType,
+    };
+    use sp_core::U256;
+
+    use crate::{Config, CurrentBlock, Pending};
+
+    /// Transaction as stored with the schema V1.
+    #[derive(Decode)]
+    struct TransactionV1 {
+        tx_type: TxType,
+        version: u8,
+        hash: Felt252Wrapper,
+        signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
+        sender_address: ContractAddressWrapper,
+        nonce: Felt252Wrapper,
+        call_entrypoint: CallEntryPointWrapper,
+        contract_class: Option<ContractClassWrapper>,
+        contract_address_salt: Option<U256>,
+        max_fee: Felt252Wrapper,
+    }
+
+    impl TransactionV1 {
+        fn migrate(self) -> Transaction {
+            Transaction {
+                tx_type: self.tx_type,
+                version: self.version,
+                hash: self.hash,
+                signature: self.signature,
+                sender_address: self.sender_address,
+                nonce: self.nonce,
+                call_entrypoint: self.call_entrypoint,
+                contract_class: self.contract_class,
+                // The salt is hashed as a felt, an executed transaction can't have a larger one.
+                contract_address_salt: self.contract_address_salt.and_then(|salt| salt.try_into().ok()),
+                max_fee: self.max_fee,
+                paid_fee_on_l1: None,
+            }
+        }
+    }
+
+    /// Block transactions as stored with the schema V1.
+    #[derive(Decode)]
+    enum BlockTransactionsV1 {
+        Hashes(BoundedVec<Felt252Wrapper, MaxTransactions>),
+        Full(BoundedVec<TransactionV1, MaxTransactions>),
+    }
+
+    impl BlockTransactionsV1 {
+        fn migrate(self) -> BlockTransactions {
+            match self {
+                Self::Hashes(hashes) => BlockTransactions::Hashes(hashes),
+                Self::Full(transactions) => BlockTransactions::Full(BoundedVec::truncate_from(
+                    transactions.into_iter().map(TransactionV1::migrate).collect::<Vec<_>>(),
+                )),
+            }
+        }
+    }
+
+    /// Transaction receipt as stored with the schema V1.
+    #[derive(Decode)]
+    struct TransactionReceiptWrapperV1 {
+        transaction_hash: Felt252Wrapper,
+        actual_fee: Felt252Wrapper,
+        tx_type: TxType,
+        block_number: u64,
+        block_hash: Felt252Wrapper,
+        events: BoundedVec<EventWrapper, MaxArraySize>,
+    }
+
+    impl TransactionReceiptWrapperV1 {
+        /// Migrates the receipt of a transaction of a block with the given timestamp and sequencer
+        /// address, or of a pending transaction for [None].
+        fn migrate(self, block: Option<(u64, ContractAddressWrapper)>) -> TransactionReceiptWrapper {
+            let (block_timestamp, sequencer_address) = block.unwrap_or_default();
+            TransactionReceiptWrapper {
+                transaction_hash: self.transaction_hash,
+                actual_fee: self.actual_fee,
+                fee_unit: FeeUnit::Wei,
+                tx_type: self.tx_type,
+                block_number: block.map(|_| self.block_number),
+                block_hash: block.map(|_| self.block_hash),
+                block_timestamp,
+                sequencer_address,
+                class_hash: None,
+                contract_address: None,
+                events: self.events,
+                execution_status: ExecutionStatus::Succeeded,
+                revert_reason: None,
+                events_truncated: false,
+            }
+        }
+    }
+
+    /// Block as stored with the schema V1, SCALE encodes a struct as the tuple of its fields.
+    type StarknetBlockV1 =
+        (StarknetHeader, BlockTransactionsV1, BoundedVec<TransactionReceiptWrapperV1, MaxTransactions>);
+
+    /// Migrates a block stored with the schema V1 to the current layout.
+    fn migrate_block((header, transactions, receipts): StarknetBlockV1) -> StarknetBlock {
+        let block = Some((header.block_timestamp, header.sequencer_address));
+        let receipts = receipts.into_iter().map(|receipt| receipt.migrate(block)).collect::<Vec<_>>();
+        // Same bound as the V1 receipts.
+        StarknetBlock::new(header, transactions.migrate(), BoundedVec::truncate_from(receipts))
+    }
+
+    /// Decodes a block stored with the schema V1 into the current layout.
+    pub fn decode_block(input: &mut &[u8]) -> Result<StarknetBlock, scale_codec::Error> {
+        StarknetBlockV1::decode(input).map(migrate_block)
+    }
+
+    /// Migrates the storage from V1 to V2. Does nothing if the storage isn't V1.
+    pub fn migrate<T: Config>() -> Weight {
+        let onchain_version =
+            frame_support::storage::unhashed::get::<StarknetStorageSchemaVersion>(PALLET_STARKNET_SCHEMA)
+                .unwrap_or_default();
+        if onchain_version != StarknetStorageSchemaVersion::V1 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let _ = CurrentBlock::<T>::translate::<StarknetBlockV1, _>(|block| block.map(migrate_block));
+
+        // Pending receipts aren't part of a block yet, they are completed when it's finalized.
+        let _ = Pending::<T>::translate::<BoundedVec<(TransactionV1, TransactionReceiptWrapperV1), MaxTransactions>, _>(
+            |pending| {
+                pending.map(|pending| {
+                    let pending = pending
+                        .into_iter()
+                        .map(|(transaction, receipt)| (transaction.migrate(), receipt.migrate(None)))
+                        .collect::<Vec<_>>();
+                    BoundedVec::truncate_from(pending)
+                })
+            },
+        );
+
+        // The transactions and receipts are migrated straight to the V5 layout, see [`super::v3`],
+        // [`super::v4`] and [`super::v5`].
+        frame_support::storage::unhashed::put::<StarknetStorageSchemaVersion>(
+            PALLET_STARKNET_SCHEMA,
+            &StarknetStorageSchemaVersion::V5,
+        );
+
+        T::DbWeight::get().reads_writes(3, 3)
+    }
+}
+
+/// Migration from the storage schema V2 to V3.
+///
+/// V3 makes the block number and block hash of the transaction receipts optional. The receipts of
+/// `CurrentBlock` keep theirs while the ones of `Pending`, whose block isn't finalized yet, lose
+/// the placeholder values they were stored with.
+///
+/// The transactions and receipts are written with the current layout, so the storage ends up in
+/// V5 without going through [`v4::migrate`] and [`v5::migrate`].
+pub mod v3 {
+    use alloc::vec::Vec;
+
+    use frame_support::pallet_prelude::*;
+    use mp_starknet::block::{Block as StarknetBlock, Header as StarknetHeader, MaxTransactions};
+    use mp_starknet::execution::types::{ClassHashWrapper, ContractAddressWrapper, Felt252Wrapper};
+    use mp_starknet::storage::{StarknetStorageSchemaVersion, PALLET_STARKNET_SCHEMA};
+    use mp_starknet::transaction::types::{
+        EventWrapper, ExecutionStatus, FeeUnit, MaxArraySize, TransactionReceiptWrapper, TxType,
+    };
+
+    use super::v4::{BlockTransactionsV3, TransactionV3};
+    use crate::{Config, CurrentBlock, Pending};
+
+    /// Transaction receipt as stored with the schema V2.
+    #[derive(Decode)]
+    struct TransactionReceiptWrapperV2 {
+        transaction_hash: Felt252Wrapper,
+        actual_fee: Felt252Wrapper,
+        fee_unit: FeeUnit,
+        tx_type: TxType,
+        block_number: u64,
+        block_hash: Felt252Wrapper,
+        block_timestamp: u64,
+        sequencer_address: ContractAddressWrapper,
+        class_hash: Option<ClassHashWrapper>,
+        contract_address: Option<ContractAddressWrapper>,
+        events: BoundedVec<EventWrapper, MaxArraySize>,
+        execution_status: ExecutionStatus,
+        revert_reason: Option<BoundedVec<u8, MaxArraySize>>,
+    }
+
+    impl TransactionReceiptWrapperV2 {
+        /// Migrates the receipt, dropping its block number and hash if it's pending.
+        fn migrate(self, pending: bool) -> TransactionReceiptWrapper {
+            TransactionReceiptWrapper {
+                transaction_hash: self.transaction_hash,
+                actual_fee: self.actual_fee,
+                fee_unit: self.fee_unit,
+                tx_type: self.tx_type,
+                block_number: (!pending).then_some(self.block_number),
+                block_hash: (!pending).then_some(self.block_hash),
+                block_timestamp: self.block_timestamp,
+                sequencer_address: self.sequencer_address,
+                class_hash: self.class_hash,
+                contract_address: self.contract_address,
+                events: self.events,
+                execution_status: self.execution_status,
+                revert_reason: self.revert_reason,
+                events_truncated: false,
+            }
+        }
+    }
+
+    /// Block as stored with the schema V2, SCALE encodes a struct as the tuple of its fields.
+    type StarknetBlockV2 =
+        (StarknetHeader, BlockTransactionsV3, BoundedVec<TransactionReceiptWrapperV2, MaxTransactions>);
+
+    /// Migrates a block stored with the schema V2 to the current layout.
+    fn migrate_block((header, transactions, receipts): StarknetBlockV2) -> StarknetBlock {
+        let receipts = receipts.into_iter().map(|receipt| receipt.migrate(false)).collect::<Vec<_>>();
+        // Same bound as the V2 receipts.
+        StarknetBlock::new(header, transactions.migrate(), BoundedVec::truncate_from(receipts))
+    }
+
+    /// Decodes a block stored with the schema V2 into the current layout.
+    pub fn decode_block(input: &mut &[u8]) -> Result<StarknetBlock, scale_codec::Error> {
+        StarknetBlockV2::decode(input).map(migrate_block)
+    }
+
+    /// Migrates the storage from V2 to V3. Does nothing if the storage isn't V2.
+    pub fn migrate<T: Config>() -> Weight {
+        let onchain_version =
+            frame_support::storage::unhashed::get::<StarknetStorageSchemaVersion>(PALLET_STARKNET_SCHEMA)
+                .unwrap_or_default();
+        if onchain_version != StarknetStorageSchemaVersion::V2 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let _ = CurrentBlock::<T>::translate::<StarknetBlockV2, _>(|block| block.map(migrate_block));
+
+        let _ = Pending::<T>::translate::<BoundedVec<(TransactionV3, TransactionReceiptWrapperV2), MaxTransactions>, _>(
+            |pending| {
+                pending.map(|pending| {
+                    let pending = pending
+                        .into_iter()
+                        .map(|(transaction, receipt)| (transaction.migrate(), receipt.migrate(true)))
+                        .collect::<Vec<_>>();
+                    BoundedVec::truncate_from(pending)
+                })
+            },
+        );
+
+        frame_support::storage::unhashed::put::<StarknetStorageSchemaVersion>(
+            PALLET_STARKNET_SCHEMA,
+            &StarknetStorageSchemaVersion::V5,
+        );
+
+        T::DbWeight::get().reads_writes(3, 3)
+    }
+}
+
+/// Migration from the storage schema V3 to V4.
+///
+/// V4 adds the fee paid on L1 to the transactions. The fee of the L1 handler transactions stored
+/// before it was never recorded, so it is left unset for every migrated transaction.
+///
+/// The receipts are written with the current layout, so the storage ends up in V5 without going
+/// through [`v5::migrate`].
+pub mod v4 {
+    use alloc::vec::Vec;
+
+    use frame_support::pallet_prelude::*;
+    use mp_starknet::block::{Block as StarknetBlock, BlockTransactions, Header as StarknetHeader, MaxTransactions};
+    use mp_starknet::execution::types::{
+        CallEntryPointWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper,
+    };
+    use mp_starknet::storage::{StarknetStorageSchemaVersion, PALLET_STARKNET_SCHEMA};
+    use mp_starknet::transaction::types::{MaxArraySize, Transaction, TxType};
+
+    use super::v5::TransactionReceiptWrapperV4;
+    use crate::{Config, CurrentBlock, Pending};
+
+    /// Transaction as stored with the schemas V2 and V3.
+    #[derive(Decode)]
+    pub(super) struct TransactionV3 {
+        tx_type: TxType,
+        version: u8,
+        hash: Felt252Wrapper,
+        signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
+        sender_address: ContractAddressWrapper,
+        nonce: Felt252Wrapper,
+        call_entrypoint: CallEntryPointWrapper,
+        contract_class: Option<ContractClassWrapper>,
+        contract_address_salt: Option<Felt252Wrapper>,
+        max_fee: Felt252Wrapper,
+    }
+
+    impl TransactionV3 {
+        pub(super) fn migrate(self) -> Transaction {
+            Transaction {
+                tx_type: self.tx_type,
+                version: self.version,
+                hash: self.hash,
+                signature: self.signature,
+                sender_address: self.sender_address,
+                nonce: self.nonce,
+                call_entrypoint: self.call_entrypoint,
+                contract_class: self.contract_class,
+                contract_address_salt: self.contract_address_salt,
+                max_fee: self.max_fee,
+                paid_fee_on_l1: None,
+            }
+        }
+    }
+
+    /// Block transactions as stored with the schemas V2 and V3.
+    #[derive(Decode)]
+    pub(super) enum BlockTransactionsV3 {
+        Hashes(BoundedVec<Felt252Wrapper, MaxTransactions>),
+        Full(BoundedVec<TransactionV3, MaxTransactions>),
+    }
+
+    impl BlockTransactionsV3 {
+        pub(super) fn migrate(self) -> BlockTransactions {
+            match self {
+                Self::Hashes(hashes) => BlockTransactions::Hashes(hashes),
+                Self::Full(transactions) => BlockTransactions::Full(BoundedVec::truncate_from(
+                    transactions.into_iter().map(TransactionV3::migrate).collect::<Vec<_>>(),
+                )),
+            }
+        }
+    }
+
+    /// Block as stored with the schema V3, SCALE encodes a struct as the tuple of its fields.
+    type StarknetBlockV3 =
+        (StarknetHeader, BlockTransactionsV3, BoundedVec<TransactionReceiptWrapperV4, MaxTransactions>);
+
+    /// Migrates a block stored with the schema V3 to the current layout.
+    fn migrate_block((header, transactions, receipts): StarknetBlockV3) -> StarknetBlock {
+        let receipts = receipts.into_iter().map(TransactionReceiptWrapperV4::migrate).collect::<Vec<_>>();
+        // Same bound as the V3 receipts.
+        StarknetBlock::new(header, transactions.migrate(), BoundedVec::truncate_from(receipts))
+    }
+
+    /// Decodes a block stored with the schema V3 into the current layout.
+    pub fn decode_block(input: &mut &[u8]) -> Result<StarknetBlock, scale_codec::Error> {
+        StarknetBlockV3::decode(input).map(migrate_block)
+    }
+
+    /// Migrates the storage from V3 to V4. Does nothing if the storage isn't V3.
+    pub fn migrate<T: Config>() -> Weight {
+        let onchain_version =
+            frame_support::storage::unhashed::get::<StarknetStorageSchemaVersion>(PALLET_STARKNET_SCHEMA)
+                .unwrap_or_default();
+        if onchain_version != StarknetStorageSchemaVersion::V3 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let _ = CurrentBlock::<T>::translate::<StarknetBlockV3, _>(|block| block.map(migrate_block));
+
+        let _ = Pending::<T>::translate::<BoundedVec<(TransactionV3, TransactionReceiptWrapperV4), MaxTransactions>, _>(
+            |pending| {
+                pending.map(|pending| {
+                    let pending = pending
+                        .into_iter()
+                        .map(|(transaction, receipt)| (transaction.migrate(), receipt.migrate()))
+                        .collect::<Vec<_>>();
+                    BoundedVec::truncate_from(pending)
+                })
+            },
+        );
+
+        frame_support::storage::unhashed::put::<StarknetStorageSchemaVersion>(
+            PALLET_STARKNET_SCHEMA,
+            &StarknetStorageSchemaVersion::V5,
+        );
+
+        T::DbWeight::get().reads_writes(3, 3)
+    }
+}
+
+/// Migration from the storage schema V4 to V5.
+///
+/// V5 adds the events truncated flag to the transaction receipts. The receipts stored before it
+/// were built failing on the events that didn't fit, so none of them was truncated.
+pub mod v5 {
+    use alloc::vec::Vec;
+
+    use frame_support::pallet_prelude::*;
+    use mp_starknet::block::{Block as StarknetBlock, BlockTransactions, Header as StarknetHeader, MaxTransactions};
+    use mp_starknet::execution::types::{ClassHashWrapper, ContractAddressWrapper, Felt252Wrapper};
+    use mp_starknet::storage::{StarknetStorageSchemaVersion, PALLET_STARKNET_SCHEMA};
+    use mp_starknet::transaction::types::{
+        EventWrapper, ExecutionStatus, FeeUnit, MaxArraySize, Transaction, TransactionReceiptWrapper, TxType,
+    };
+
+    use crate::{Config, CurrentBlock, Pending};
+
+    /// Transaction receipt as stored with the schemas V3 and V4.
+    #[derive(Decode)]
+    pub(super) struct TransactionReceiptWrapperV4 {
+        transaction_hash: Felt252Wrapper,
+        actual_fee: Felt252Wrapper,
+        fee_unit: FeeUnit,
+        tx_type: TxType,
+        block_number: Option<u64>,
+        block_hash: Option<Felt252Wrapper>,
+        block_timestamp: u64,
+        sequencer_address: ContractAddressWrapper,
+        class_hash: Option<ClassHashWrapper>,
+        contract_address: Option<ContractAddressWrapper>,
+        events: BoundedVec<EventWrapper, MaxArraySize>,
+        execution_status: ExecutionStatus,
+        revert_reason: Option<BoundedVec<u8, MaxArraySize>>,
+    }
+
+    impl TransactionReceiptWrapperV4 {
+        pub(super) fn migrate(self) -> TransactionReceiptWrapper {
+            TransactionReceiptWrapper {
+                transaction_hash: self.transaction_hash,
+                actual_fee: self.actual_fee,
+                fee_unit: self.fee_unit,
+                tx_type: self.tx_type,
+                block_number: self.block_number,
+                block_hash: self.block_hash,
+                block_timestamp: self.block_timestamp,
+                sequencer_address: self.sequencer_address,
+                class_hash: self.class_hash,
+                contract_address: self.contract_address,
+                events: self.events,
+                execution_status: self.execution_status,
+                revert_reason: self.revert_reason,
+                events_truncated: false,
+            }
+        }
+    }
+
+    /// Block as stored with the schema V4, SCALE encodes a struct as the tuple of its fields.
+    type StarknetBlockV4 =
+        (StarknetHeader, BlockTransactions, BoundedVec<TransactionReceiptWrapperV4, MaxTransactions>);
+
+    /// Migrates a block stored with the schema V4 to the current layout.
+    fn migrate_block((header, transactions, receipts): StarknetBlockV4) -> StarknetBlock {
+        let receipts = receipts.into_iter().map(TransactionReceiptWrapperV4::migrate).collect::<Vec<_>>();
+        // Same bound as the V4 receipts.
+        StarknetBlock::new(header, transactions, BoundedVec::truncate_from(receipts))
+    }
+
+    /// Decodes a block stored with the schema V4 into the current layout.
+    pub fn decode_block(input: &mut &[u8]) -> Result<StarknetBlock, scale_codec::Error> {
+        StarknetBlockV4::decode(input).map(migrate_block)
+    }
+
+    /// Migrates the storage from V4 to V5. Does nothing if the storage isn't V4.
+    pub fn migrate<T: Config>() -> Weight {
+        let onchain_version =
+            frame_support::storage::unhashed::get::<StarknetStorageSchemaVersion>(PALLET_STARKNET_SCHEMA)
+                .unwrap_or_default();
+        if onchain_version != StarknetStorageSchemaVersion::V4 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let _ = CurrentBlock::<T>::translate::<StarknetBlockV4, _>(|block| block.map(migrate_block));
+
+        let _ = Pending::<T>::translate::<BoundedVec<(Transaction, TransactionReceiptWrapperV4), MaxTransactions>, _>(
+            |pending| {
+                pending.map(|pending| {
+                    let pending = pending
+                        .into_iter()
+                        .map(|(transaction, receipt)| (transaction, receipt.migrate()))
+                        .collect::<Vec<_>>();
+                    BoundedVec::truncate_from(pending)
+                })
+            },
+        );
+
+        frame_support::storage::unhashed::put::<StarknetStorageSchemaVersion>(
+            PALLET_STARKNET_SCHEMA,
+            &StarknetStorageSchemaVersion::V5,
+        );
+
+        T::DbWeight::get().reads_writes(3, 3)
+    }
+}
@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 
 use frame_support::traits::OriginTrait;
 use frame_system::pallet_prelude::OriginFor;
+use mp_starknet::execution::types::ChainId;
 use serde_json::from_slice;
 use sp_runtime::offchain::http;
 use sp_runtime::offchain::storage::StorageValueRef;
@@ -38,7 +39,8 @@ impl<T: Config> Pallet<T> {
             // Iterate over the messages and execute them.
             res.result.iter().try_for_each(|message| {
                 // Execute the message.
-                Self::consume_l1_message(OriginFor::<T>::none(), message.try_into_transaction()?)
+                let transaction = message.try_into_transaction(ChainId(Self::chain_id()))?;
+                Self::consume_l1_message(OriginFor::<T>::none(), transaction)
                     .map_err(OffchainWorkerError::ConsumeMessageError)
             })?;
         }
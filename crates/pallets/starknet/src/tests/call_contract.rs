@@ -42,6 +42,7 @@ fn given_call_contract_call_works() {
             nonce: Felt252Wrapper::ZERO,
             calldata: constructor_calldata,
             max_fee: Felt252Wrapper::from(u128::MAX),
+            entry_point_selector: None,
         };
 
         assert_ok!(Starknet::invoke(origin, deploy_transaction));
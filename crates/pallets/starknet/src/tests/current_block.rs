@@ -46,3 +46,21 @@ fn given_normal_conditions_when_current_block_then_returns_correct_block() {
         debug(&current_block);
     });
 }
+
+#[test]
+fn given_normal_conditions_when_current_block_then_receipts_carry_block_info() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let current_block = Starknet::current_block();
+        let receipts = current_block.transaction_receipts();
+
+        pretty_assertions::assert_eq!(receipts.len(), 1);
+        let receipt = receipts.get(0).unwrap();
+        pretty_assertions::assert_eq!(receipt.block_timestamp, 12_000);
+        pretty_assertions::assert_eq!(receipt.sequencer_address, Felt252Wrapper::try_from(&SEQUENCER_ADDRESS).unwrap());
+        pretty_assertions::assert_eq!(receipt.block_number, Some(current_block.header().block_number.as_u64()));
+        pretty_assertions::assert_eq!(receipt.block_hash, Some(Starknet::current_block_hash()));
+    });
+}
@@ -30,6 +30,9 @@ fn given_contract_declare_tx_works_once_not_twice() {
         };
 
         assert_ok!(Starknet::declare(none_origin.clone(), transaction.clone()));
+        let receipt = &Starknet::pending().last().unwrap().1;
+        assert_eq!(receipt.class_hash, Some(erc20_class_hash));
+        assert_eq!(receipt.contract_address, None);
         // TODO: Uncomment once we have ABI support
         // assert_eq!(Starknet::contract_class_by_class_hash(erc20_class_hash), erc20_class);
         assert_err!(Starknet::declare(none_origin, transaction), Error::<MockRuntime>::ClassHashAlreadyDeclared);
@@ -66,6 +69,29 @@ fn given_contract_declare_tx_fails_sender_not_deployed() {
     })
 }
 
+#[test]
+fn given_contract_declare_tx_with_empty_class_fails_early() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+        let account_addr = get_account_address(AccountType::NoValidate);
+
+        let transaction = DeclareTransaction {
+            sender_address: account_addr,
+            version: 1,
+            compiled_class_hash: Felt252Wrapper::ONE,
+            contract_class: ContractClassWrapper::default(),
+            nonce: Felt252Wrapper::ZERO,
+            max_fee: Felt252Wrapper::from(u128::MAX),
+            signature: bounded_vec!(),
+        };
+
+        assert_err!(Starknet::declare(none_origin, transaction), Error::<MockRuntime>::EmptyContractClass);
+    });
+}
+
 #[test]
 fn given_contract_declare_tx_fails_wrong_tx_version() {
     new_test_ext().execute_with(|| {
@@ -1,8 +1,10 @@
 use std::str::FromStr;
 
+use frame_support::traits::Get;
 use frame_support::{assert_err, assert_ok, bounded_vec, BoundedVec};
 use mp_starknet::crypto::commitment::calculate_deploy_account_tx_hash;
 use mp_starknet::execution::types::Felt252Wrapper;
+use mp_starknet::traits::limits::{DefaultTxLimits, TxLimits};
 use mp_starknet::transaction::types::{DeployAccountTransaction, EventWrapper};
 use sp_core::U256;
 
@@ -42,12 +44,13 @@ fn given_contract_run_deploy_account_tx_works() {
             max_fee: Felt252Wrapper::from(u128::MAX),
             signature: bounded_vec!(),
         };
-        let chain_id = Starknet::chain_id().0.to_bytes_be();
-        let chain_id = std::str::from_utf8(&chain_id[..]).unwrap();
-        let transaction_hash = calculate_deploy_account_tx_hash(transaction.clone(), chain_id);
+        let transaction_hash = calculate_deploy_account_tx_hash(transaction.clone(), Starknet::chain_id().into());
 
         assert_ok!(Starknet::deploy_account(none_origin, transaction));
         assert_eq!(Starknet::contract_class_hash_by_address(test_addr).unwrap(), account_class_hash);
+        let receipt = &Starknet::pending().last().unwrap().1;
+        assert_eq!(receipt.contract_address, Some(test_addr));
+        assert_eq!(receipt.class_hash, None);
         let expected_fee_transfer_event = Event::StarknetEvent(EventWrapper {
             keys: bounded_vec![
                 Felt252Wrapper::from_hex_be("0x0099cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9")
@@ -67,6 +70,38 @@ fn given_contract_run_deploy_account_tx_works() {
     });
 }
 
+#[test]
+fn given_deploy_account_tx_with_another_sender_address_then_it_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+        let none_origin = RuntimeOrigin::none();
+        let salt = "0x03b37cbe4e9eac89d54c5f7cc6329a63a63e8c8db2bf936f981041e086752463";
+        let (test_addr, account_class_hash, calldata) = account_helper(salt, AccountType::NoValidate);
+        let other_addr = Felt252Wrapper::from_hex_be("0x1234").unwrap();
+
+        set_infinite_tokens(test_addr);
+
+        let transaction = DeployAccountTransaction {
+            account_class_hash,
+            sender_address: other_addr,
+            salt: U256::from_str(salt).unwrap(),
+            version: 1,
+            calldata: BoundedVec::try_from(
+                calldata.into_iter().map(|e| Felt252Wrapper::from_hex_be(e).unwrap()).collect::<Vec<Felt252Wrapper>>(),
+            )
+            .unwrap(),
+            nonce: Felt252Wrapper::ZERO,
+            max_fee: Felt252Wrapper::from(u128::MAX),
+            signature: bounded_vec!(),
+        };
+
+        assert_err!(Starknet::deploy_account(none_origin, transaction), Error::<MockRuntime>::InvalidSenderAddress);
+        assert!(Starknet::contract_class_hash_by_address(test_addr).is_none());
+        assert!(Starknet::contract_class_hash_by_address(other_addr).is_none());
+    });
+}
+
 #[test]
 fn given_contract_run_deploy_account_tx_twice_fails() {
     new_test_ext().execute_with(|| {
@@ -112,11 +147,10 @@ fn given_contract_run_deploy_account_tx_undeclared_then_it_fails() {
         run_to_block(2);
         let salt = "0x03b37cbe4e9eac89d54c5f7cc6329a63a63e8c8db2bf936f981041e086752463";
         let none_origin = RuntimeOrigin::none();
-        let rand_address = Felt252Wrapper::from_hex_be("0x1234").unwrap();
         let (_, account_class_hash, _) = account_helper(salt, AccountType::ArgentV0);
-        let transaction = DeployAccountTransaction {
+        let mut transaction = DeployAccountTransaction {
             account_class_hash,
-            sender_address: rand_address,
+            sender_address: Felt252Wrapper::ZERO,
             version: 1,
             calldata: bounded_vec!(),
             salt: U256::zero(),
@@ -124,6 +158,7 @@ fn given_contract_run_deploy_account_tx_undeclared_then_it_fails() {
             max_fee: Felt252Wrapper::from(u128::MAX),
             signature: bounded_vec!(),
         };
+        transaction.sender_address = transaction.contract_address().unwrap();
 
         assert_err!(
             Starknet::deploy_account(none_origin, transaction),
@@ -158,7 +193,7 @@ fn given_contract_run_deploy_account_tx_fails_wrong_tx_version() {
                     .collect::<Vec<Felt252Wrapper>>(),
             )
             .unwrap(),
-            salt: U256::zero(),
+            salt: U256::from_str(salt).unwrap(),
             nonce: Felt252Wrapper::ZERO,
             max_fee: Felt252Wrapper::from(u128::MAX),
             signature: bounded_vec!(),
@@ -171,6 +206,32 @@ fn given_contract_run_deploy_account_tx_fails_wrong_tx_version() {
     });
 }
 
+#[test]
+fn given_contract_run_deploy_account_tx_fails_constructor_calldata_too_long() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+        let salt = "0x03b37cbe4e9eac89d54c5f7cc6329a63a63e8c8db2bf936f981041e086752463";
+        let (test_addr, account_class_hash, _) = account_helper(salt, AccountType::NoValidate);
+        let max_calldata = <DefaultTxLimits as TxLimits>::DeployConstructorMaxCalldata::get() as usize;
+
+        let transaction = DeployAccountTransaction {
+            account_class_hash,
+            sender_address: test_addr,
+            version: 1,
+            calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata + 1]).unwrap(),
+            salt: U256::from_str(salt).unwrap(),
+            nonce: Felt252Wrapper::ZERO,
+            max_fee: Felt252Wrapper::from(u128::MAX),
+            signature: bounded_vec!(),
+        };
+
+        assert_err!(Starknet::deploy_account(none_origin, transaction), Error::<MockRuntime>::CalldataTooLong);
+    });
+}
+
 #[test]
 fn given_contract_run_deploy_account_openzeppelin_tx_works() {
     new_test_ext().execute_with(|| {
@@ -341,37 +402,22 @@ fn given_contract_run_deploy_account_braavos_tx_works() {
         // TEST ACCOUNT CONTRACT
         // - ref testnet tx(0x0751b4b5b95652ad71b1721845882c3852af17e2ed0c8d93554b5b292abb9810)
         let salt = "0x03b37cbe4e9eac89d54c5f7cc6329a63a63e8c8db2bf936f981041e086752463";
-        let (test_addr, proxy_class_hash, mut calldata) = account_helper(salt, AccountType::BraavosProxy);
+        let (_, proxy_class_hash, mut calldata) = account_helper(salt, AccountType::BraavosProxy);
         calldata.push("0x1");
         calldata.push(ACCOUNT_PUBLIC_KEY);
 
+        let mut transaction = braavos_deploy_account_transaction(salt, proxy_class_hash, calldata);
+        let test_addr = transaction.sender_address;
+
         set_infinite_tokens(test_addr);
         set_signer(test_addr, AccountType::Braavos);
 
-        let tx_hash =
-            Felt252Wrapper::from_hex_be("0x06ae3d81978d498def89e1121b2d84a873d63c30d80f7ed81e2dc9be6a961770").unwrap();
+        let tx_hash = calculate_deploy_account_tx_hash(transaction.clone(), Starknet::chain_id().into());
 
         let mut signatures: Vec<Felt252Wrapper> = sign_message_hash(tx_hash).into();
         let empty_signatures = [Felt252Wrapper::ZERO; 8];
         signatures.append(&mut empty_signatures.to_vec());
-
-        let transaction = DeployAccountTransaction {
-            account_class_hash: proxy_class_hash,
-            sender_address: test_addr,
-            salt: U256::from_str(salt).unwrap(),
-            version: 1,
-            calldata: BoundedVec::try_from(
-                calldata
-                    .clone()
-                    .into_iter()
-                    .map(|e| Felt252Wrapper::from_hex_be(e).unwrap())
-                    .collect::<Vec<Felt252Wrapper>>(),
-            )
-            .unwrap(),
-            nonce: Felt252Wrapper::ZERO,
-            max_fee: Felt252Wrapper::from(u128::MAX),
-            signature: signatures.try_into().unwrap(),
-        };
+        transaction.signature = signatures.try_into().unwrap();
 
         assert_ok!(Starknet::deploy_account(none_origin, transaction));
         assert_eq!(Starknet::contract_class_hash_by_address(test_addr).unwrap(), proxy_class_hash);
@@ -388,30 +434,18 @@ fn given_contract_run_deploy_account_braavos_with_incorrect_signature_then_it_fa
         // TEST ACCOUNT CONTRACT
         // - ref testnet tx(0x0751b4b5b95652ad71b1721845882c3852af17e2ed0c8d93554b5b292abb9810)
         let salt = "0x03b37cbe4e9eac89d54c5f7cc6329a63a63e8c8db2bf936f981041e086752463";
-        let (test_addr, proxy_class_hash, mut calldata) = account_helper(salt, AccountType::BraavosProxy);
+        let (_, proxy_class_hash, mut calldata) = account_helper(salt, AccountType::BraavosProxy);
         calldata.push("0x1");
         calldata.push(ACCOUNT_PUBLIC_KEY);
 
-        set_infinite_tokens(test_addr);
-        set_signer(test_addr, AccountType::Braavos);
-
         let transaction = DeployAccountTransaction {
-            account_class_hash: proxy_class_hash,
-            sender_address: test_addr,
-            salt: U256::from_str(salt).unwrap(),
-            version: 1,
-            calldata: BoundedVec::try_from(
-                calldata
-                    .clone()
-                    .into_iter()
-                    .map(|e| Felt252Wrapper::from_hex_be(e).unwrap())
-                    .collect::<Vec<Felt252Wrapper>>(),
-            )
-            .unwrap(),
-            nonce: Felt252Wrapper::ZERO,
-            max_fee: Felt252Wrapper::from(u128::MAX),
             signature: [Felt252Wrapper::ZERO; 10].to_vec().try_into().unwrap(),
+            ..braavos_deploy_account_transaction(salt, proxy_class_hash, calldata)
         };
+        let test_addr = transaction.sender_address;
+
+        set_infinite_tokens(test_addr);
+        set_signer(test_addr, AccountType::Braavos);
 
         assert_err!(
             Starknet::deploy_account(none_origin, transaction),
@@ -420,6 +454,30 @@ fn given_contract_run_deploy_account_braavos_with_incorrect_signature_then_it_fa
     });
 }
 
+/// Builds an unsigned deploy account transaction of the Braavos proxy, sent from the address
+/// derived from its whole constructor calldata, implementation initializer arguments included.
+fn braavos_deploy_account_transaction(
+    salt: &str,
+    proxy_class_hash: Felt252Wrapper,
+    calldata: Vec<&str>,
+) -> DeployAccountTransaction {
+    let mut transaction = DeployAccountTransaction {
+        account_class_hash: proxy_class_hash,
+        sender_address: Felt252Wrapper::ZERO,
+        salt: U256::from_str(salt).unwrap(),
+        version: 1,
+        calldata: BoundedVec::try_from(
+            calldata.into_iter().map(|e| Felt252Wrapper::from_hex_be(e).unwrap()).collect::<Vec<Felt252Wrapper>>(),
+        )
+        .unwrap(),
+        nonce: Felt252Wrapper::ZERO,
+        max_fee: Felt252Wrapper::from(u128::MAX),
+        signature: bounded_vec!(),
+    };
+    transaction.sender_address = transaction.contract_address().unwrap();
+    transaction
+}
+
 fn set_infinite_tokens(address: Felt252Wrapper) {
     StorageView::<MockRuntime>::insert(
         get_storage_key(&Starknet::fee_token_address(), "ERC20_balances", &[address], 0),
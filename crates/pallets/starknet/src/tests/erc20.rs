@@ -2,7 +2,7 @@ use frame_support::{assert_ok, bounded_vec};
 use lazy_static::lazy_static;
 use mp_starknet::crypto::commitment::calculate_invoke_tx_hash;
 use mp_starknet::execution::types::{ContractClassWrapper, Felt252Wrapper};
-use mp_starknet::transaction::types::{EventWrapper, InvokeTransaction};
+use mp_starknet::transaction::types::{EventWrapper, InvokeTransaction, Transaction};
 
 use super::mock::*;
 use super::utils::get_contract_class_wrapper;
@@ -43,10 +43,9 @@ fn given_erc20_transfer_when_invoke_then_it_works() {
             nonce: Felt252Wrapper::ZERO,
             max_fee: Felt252Wrapper::from(u128::MAX),
             signature: bounded_vec!(),
+            entry_point_selector: None,
         };
-        let chain_id = Starknet::chain_id().0.to_bytes_be();
-        let chain_id = std::str::from_utf8(&chain_id[..]).unwrap();
-        let transaction_hash = calculate_invoke_tx_hash(deploy_transaction.clone(), chain_id);
+        let transaction_hash = calculate_invoke_tx_hash(deploy_transaction.clone(), Starknet::chain_id().into());
 
         let expected_erc20_address =
             Felt252Wrapper::from_hex_be("0x00dc58c1280862c95964106ef9eba5d9ed8c0c16d05883093e4540f22b829dff").unwrap();
@@ -125,10 +124,9 @@ fn given_erc20_transfer_when_invoke_then_it_works() {
             nonce: Felt252Wrapper::ONE,
             max_fee: Felt252Wrapper::from(u128::MAX),
             signature: bounded_vec!(),
+            entry_point_selector: None,
         };
-        let chain_id = Starknet::chain_id().0.to_bytes_be();
-        let chain_id = std::str::from_utf8(&chain_id[..]).unwrap();
-        let transaction_hash = calculate_invoke_tx_hash(transfer_transaction.clone(), chain_id);
+        let transaction_hash = calculate_invoke_tx_hash(transfer_transaction.clone(), Starknet::chain_id().into());
 
         // Also asserts that the deployment has been saved.
         assert_ok!(Starknet::invoke(origin, transfer_transaction));
@@ -217,3 +215,22 @@ fn given_erc20_transfer_when_invoke_then_it_works() {
         );
     })
 }
+
+#[test]
+fn given_tx_versions_fee_token_balance_reads_the_token_they_pay_in() {
+    new_test_ext().execute_with(|| {
+        let account = get_account_address(AccountType::NoValidate);
+        let config = Starknet::fee_token_config();
+        let v1 = Transaction { version: 1, ..Transaction::default() };
+        let v3 = Transaction { version: 3, ..Transaction::default() };
+
+        // The mock only funds the accounts in the ETH fee token.
+        pretty_assertions::assert_eq!(v1.fee_token_address(&config), Starknet::fee_token_address());
+        pretty_assertions::assert_eq!(v3.fee_token_address(&config), Starknet::strk_fee_token_address());
+        assert_ne!(Starknet::fee_token_balance(v1.fee_token_address(&config), account), Felt252Wrapper::ZERO);
+        pretty_assertions::assert_eq!(
+            Starknet::fee_token_balance(v3.fee_token_address(&config), account),
+            Felt252Wrapper::ZERO
+        );
+    });
+}
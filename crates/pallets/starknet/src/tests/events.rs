@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use blockifier::execution::entry_point::{CallEntryPoint, CallExecution, CallInfo, OrderedEvent};
+use mp_starknet::execution::types::Felt252Wrapper;
+use mp_starknet::transaction::types::{TransactionExecutionInfoWrapper, TxType};
+use starknet_api::api_core::{ContractAddress, PatriciaKey};
+use starknet_api::hash::{StarkFelt, StarkHash};
+use starknet_api::patricia_key;
+use starknet_api::transaction::{EventContent, EventData, EventKey, Fee};
+
+use super::mock::*;
+
+fn ordered_event(order: usize, key: u64) -> OrderedEvent {
+    OrderedEvent { order, event: EventContent { keys: vec![EventKey(StarkFelt::from(key))], data: EventData(vec![]) } }
+}
+
+fn call_info(address: ContractAddress, events: Vec<OrderedEvent>, inner_calls: Vec<CallInfo>) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint { storage_address: address, ..CallEntryPoint::default() },
+        execution: CallExecution { events, ..CallExecution::default() },
+        inner_calls,
+        ..CallInfo::default()
+    }
+}
+
+#[test]
+fn given_nested_calls_emit_events_in_pre_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        // Given
+        let root = ContractAddress(patricia_key!("0x1"));
+        let first_inner = ContractAddress(patricia_key!("0x2"));
+        let nested = ContractAddress(patricia_key!("0x3"));
+        let second_inner = ContractAddress(patricia_key!("0x4"));
+        let call = call_info(
+            root,
+            vec![ordered_event(1, 1), ordered_event(0, 0)],
+            vec![
+                call_info(
+                    first_inner,
+                    vec![ordered_event(0, 2)],
+                    vec![call_info(nested, vec![ordered_event(0, 3)], vec![])],
+                ),
+                call_info(second_inner, vec![ordered_event(0, 4)], vec![]),
+            ],
+        );
+        let execution_info = TransactionExecutionInfoWrapper {
+            validate_call_info: None,
+            execute_call_info: Some(call),
+            fee_transfer_call_info: None,
+            actual_fee: Fee::default(),
+            actual_resources: BTreeMap::new(),
+            revert_error: None,
+            state_diff: None,
+            execution_duration_micros: None,
+        };
+
+        // When
+        let receipt = execution_info.into_receipt(Felt252Wrapper::ONE, TxType::Invoke).unwrap();
+        Starknet::emit_events(&receipt.events).unwrap();
+
+        // Then
+        let emitted: Vec<(Felt252Wrapper, Felt252Wrapper)> =
+            Starknet::pending_events().iter().map(|event| (event.keys[0], event.from_address)).collect();
+        let expected: Vec<(Felt252Wrapper, Felt252Wrapper)> = [(0_u64, 1_u64), (1, 1), (2, 2), (3, 3), (4, 4)]
+            .into_iter()
+            .map(|(key, address)| (Felt252Wrapper::from(key), Felt252Wrapper::from(address)))
+            .collect();
+        pretty_assertions::assert_eq!(emitted, expected);
+    })
+}
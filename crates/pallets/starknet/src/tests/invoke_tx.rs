@@ -1,16 +1,19 @@
 use core::str::FromStr;
 
 use blockifier::abi::abi_utils::get_storage_var_address;
-use frame_support::{assert_err, assert_ok, bounded_vec};
-use mp_starknet::crypto::commitment::{self, calculate_invoke_tx_hash};
-use mp_starknet::execution::types::Felt252Wrapper;
+use frame_support::traits::Get;
+use frame_support::{assert_err, assert_ok, bounded_vec, BoundedVec};
+use mp_starknet::crypto::commitment::{self, calculate_invoke_tx_hash, CommitmentVersion};
+use mp_starknet::execution::types::{ChainId, Felt252Wrapper};
 use mp_starknet::starknet_serde::transaction_from_json;
+use mp_starknet::traits::limits::{DefaultTxLimits, TxLimits};
 use mp_starknet::transaction::types::{
-    EventWrapper, InvokeTransaction, Transaction, TransactionReceiptWrapper, TxType,
+    EventWrapper, ExecutionStatus, FeeUnit, InvokeTransaction, Transaction, TransactionReceiptWrapper, TxType,
 };
 use sp_core::H256;
 use starknet_core::utils::get_selector_from_name;
 
+use super::constants::{TEST_CLASS_HASH, TEST_CONTRACT_ADDRESS};
 use super::mock::*;
 use super::utils::sign_message_hash;
 use crate::message::Message;
@@ -35,6 +38,7 @@ fn given_hardcoded_contract_run_invoke_tx_fails_sender_not_deployed() {
             nonce: Felt252Wrapper::ZERO,
             max_fee: Felt252Wrapper::from(u128::MAX),
             signature: bounded_vec!(),
+            entry_point_selector: None,
         };
 
         assert_err!(Starknet::invoke(none_origin, transaction), Error::<MockRuntime>::AccountNotDeployed);
@@ -56,6 +60,41 @@ fn given_hardcoded_contract_run_invoke_tx_fails_invalid_tx_version() {
     });
 }
 
+#[test]
+fn given_invoke_v0_tx_without_entry_point_selector_then_it_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+
+        let sender_add = get_account_address(AccountType::NoValidate);
+        let transaction = InvokeTransaction { version: 0, sender_address: sender_add, ..InvokeTransaction::default() };
+
+        assert_err!(Starknet::invoke(none_origin, transaction), Error::<MockRuntime>::InvalidEntryPointSelector);
+    });
+}
+
+#[test]
+fn given_hardcoded_contract_run_invoke_tx_fails_signature_too_long() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+
+        let sender_add = get_account_address(AccountType::NoValidate);
+        let max_signature = <DefaultTxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+        let transaction = InvokeTransaction {
+            sender_address: sender_add,
+            signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature + 1]).unwrap(),
+            ..InvokeTransaction::default()
+        };
+
+        assert_err!(Starknet::invoke(none_origin, transaction), Error::<MockRuntime>::SignatureTooLong);
+    });
+}
+
 #[test]
 fn given_hardcoded_contract_run_invoke_tx_then_it_works() {
     new_test_ext().execute_with(|| {
@@ -67,9 +106,7 @@ fn given_hardcoded_contract_run_invoke_tx_then_it_works() {
         let json_content: &str = include_str!("../../../../../resources/transactions/invoke.json");
         let transaction: InvokeTransaction =
             transaction_from_json(json_content, &[]).expect("Failed to create Transaction from JSON").into();
-        let chain_id = Starknet::chain_id().0.to_bytes_be();
-        let chain_id = std::str::from_utf8(&chain_id[..]).unwrap();
-        let transaction_hash = calculate_invoke_tx_hash(transaction.clone(), chain_id);
+        let transaction_hash = calculate_invoke_tx_hash(transaction.clone(), Starknet::chain_id().into());
 
         let tx = Message {
             topics: vec![
@@ -78,9 +115,18 @@ fn given_hardcoded_contract_run_invoke_tx_then_it_works() {
                 "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned(),
                 "0x01310e2c127c3b511c5ac0fd7949d544bb4d75b8bc83aaeb357e712ecf582771".to_owned(),
             ],
-            data: "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned(),
+            // Payload offset, nonce, fee, payload length and payload.
+            data: concat!(
+                "0x",
+                "0000000000000000000000000000000000000000000000000000000000000060",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            )
+            .to_owned(),
         }
-        .try_into_transaction()
+        .try_into_transaction(ChainId(Starknet::chain_id()))
         .unwrap();
 
         assert_ok!(Starknet::invoke(none_origin.clone(), transaction));
@@ -96,12 +142,14 @@ fn given_hardcoded_contract_run_invoke_tx_then_it_works() {
             )
             .unwrap(),
             actual_fee: Felt252Wrapper::from(52980_u128),
+            fee_unit: FeeUnit::Wei,
             tx_type: TxType::Invoke,
-            block_number: 2_u64,
-            block_hash: Felt252Wrapper::from_hex_be(
-                "0x62A89580109015D0B9B899DF7A158C248654EFDEC86C600228AC90D4D1E74FA",
-            )
-            .unwrap(),
+            block_number: None,
+            block_hash: None,
+            block_timestamp: 0,
+            sequencer_address: Felt252Wrapper::ZERO,
+            class_hash: None,
+            contract_address: None,
             events: bounded_vec![EventWrapper {
                 keys: bounded_vec!(
                     Felt252Wrapper::from_hex_be("0x0099cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9")
@@ -119,12 +167,48 @@ fn given_hardcoded_contract_run_invoke_tx_then_it_works() {
                 from_address: Starknet::fee_token_address(),
                 transaction_hash
             },],
+            execution_status: ExecutionStatus::Succeeded,
+            revert_reason: None,
+            events_truncated: false,
         };
 
         pretty_assertions::assert_eq!(*receipt, expected_receipt);
     });
 }
 
+#[test]
+fn given_l1_handler_without_paid_fee_when_consume_l1_message_then_it_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+
+        let sender_address = get_account_address(AccountType::NoValidate);
+        let transaction =
+            Transaction { tx_type: TxType::L1Handler, sender_address, paid_fee_on_l1: None, ..Transaction::default() };
+
+        assert_err!(Starknet::consume_l1_message(none_origin, transaction), Error::<MockRuntime>::MissingL1Fee);
+    });
+}
+
+#[test]
+fn given_invoke_tx_without_paid_fee_when_consume_l1_message_then_it_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+
+        // The extrinsic executes it as an L1 handler, so it's checked as one.
+        let sender_address = get_account_address(AccountType::NoValidate);
+        let transaction =
+            Transaction { tx_type: TxType::Invoke, sender_address, paid_fee_on_l1: None, ..Transaction::default() };
+
+        assert_err!(Starknet::consume_l1_message(none_origin, transaction), Error::<MockRuntime>::MissingL1Fee);
+    });
+}
+
 #[test]
 fn given_hardcoded_contract_run_invoke_tx_then_event_is_emitted() {
     new_test_ext().execute_with(|| {
@@ -136,9 +220,7 @@ fn given_hardcoded_contract_run_invoke_tx_then_event_is_emitted() {
         let json_content: &str = include_str!("../../../../../resources/transactions/invoke_emit_event.json");
         let transaction: InvokeTransaction =
             transaction_from_json(json_content, &[]).expect("Failed to create Transaction from JSON").into();
-        let chain_id = Starknet::chain_id().0.to_bytes_be();
-        let chain_id = std::str::from_utf8(&chain_id[..]).unwrap();
-        let transaction_hash = calculate_invoke_tx_hash(transaction.clone(), chain_id);
+        let transaction_hash = calculate_invoke_tx_hash(transaction.clone(), Starknet::chain_id().into());
 
         assert_ok!(Starknet::invoke(none_origin, transaction));
 
@@ -185,7 +267,7 @@ fn given_hardcoded_contract_run_invoke_tx_then_event_is_emitted() {
         let events = Starknet::pending_events();
         let transactions: Vec<Transaction> = pending.clone().into_iter().map(|(transaction, _)| transaction).collect();
         let (_transaction_commitment, event_commitment) =
-            commitment::calculate_commitments::<<MockRuntime as crate::Config>::SystemHash>(&transactions, &events);
+            commitment::calculate_versioned_commitments(&transactions, &events, CommitmentVersion::Pedersen);
 
         assert_eq!(
             event_commitment,
@@ -200,13 +282,18 @@ fn given_hardcoded_contract_run_invoke_tx_then_event_is_emitted() {
             )
             .unwrap(),
             actual_fee: Felt252Wrapper::from(53490_u128),
+            fee_unit: FeeUnit::Wei,
             tx_type: TxType::Invoke,
-            block_number: 2_u64,
-            block_hash: Felt252Wrapper::from_hex_be(
-                "0x62A89580109015D0B9B899DF7A158C248654EFDEC86C600228AC90D4D1E74FA",
-            )
-            .unwrap(),
+            block_number: None,
+            block_hash: None,
+            block_timestamp: 0,
+            sequencer_address: Felt252Wrapper::ZERO,
+            class_hash: None,
+            contract_address: None,
             events: bounded_vec!(emitted_event, expected_fee_transfer_event),
+            execution_status: ExecutionStatus::Succeeded,
+            revert_reason: None,
+            events_truncated: false,
         };
         let receipt = &pending.get(0).unwrap().1;
         pretty_assertions::assert_eq!(*receipt, expected_receipt);
@@ -242,6 +329,73 @@ fn given_hardcoded_contract_run_storage_read_and_write_it_works() {
     });
 }
 
+#[test]
+fn given_hardcoded_contract_run_invoke_tx_with_unknown_selector_then_it_reverts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+
+        let json_content: &str = include_str!("../../../../../resources/transactions/invoke.json");
+        let mut transaction: InvokeTransaction =
+            transaction_from_json(json_content, &[]).expect("Failed to create Transaction from JSON").into();
+        // Call a function that doesn't exist on the target contract.
+        let mut calldata = transaction.calldata.to_vec();
+        calldata[1] = Felt252Wrapper::from(get_selector_from_name("unknown_function").unwrap());
+        transaction.calldata = calldata.try_into().unwrap();
+
+        assert_ok!(Starknet::invoke(none_origin, transaction));
+
+        let pending = Starknet::pending();
+        pretty_assertions::assert_eq!(pending.len(), 1);
+
+        let receipt = &pending.get(0).unwrap().1;
+        let revert_reason = receipt.revert_reason.clone().expect("Transaction should have reverted");
+        assert!(!revert_reason.is_empty());
+        // The fee is charged even though the execution reverted.
+        assert_ne!(receipt.actual_fee, Felt252Wrapper::ZERO);
+    });
+}
+
+#[test]
+fn given_hardcoded_contract_run_invoke_tx_that_writes_then_fails_then_the_write_is_rolled_back() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let none_origin = RuntimeOrigin::none();
+
+        let json_content: &str = include_str!("../../../../../resources/transactions/storage_read_write.json");
+        let mut transaction: InvokeTransaction =
+            transaction_from_json(json_content, include_bytes!("../../../../../resources/account/simple/account.json"))
+                .expect("Failed to create Transaction from JSON")
+                .into();
+        let target_contract_address = Felt252Wrapper::from_hex_be(TEST_CONTRACT_ADDRESS).unwrap();
+        let selector = |name: &str| Felt252Wrapper::from(get_selector_from_name(name).unwrap());
+        // The nested library call writes the storage at `0`, then the outer one fails writing at an
+        // address out of the storage range.
+        transaction.calldata = bounded_vec![
+            target_contract_address,
+            selector("test_nested_library_call"),
+            Felt252Wrapper::from(6_u64),
+            Felt252Wrapper::from_hex_be(TEST_CLASS_HASH).unwrap(),
+            selector("test_library_call"),
+            selector("test_storage_read_write"),
+            Felt252Wrapper::TWO,
+            Felt252Wrapper::MAX,
+            Felt252Wrapper::ONE,
+        ];
+
+        assert_ok!(Starknet::invoke(none_origin, transaction));
+
+        let pending = Starknet::pending();
+        let receipt = &pending.get(0).unwrap().1;
+        assert_eq!(receipt.execution_status, ExecutionStatus::Reverted);
+        assert_eq!(Starknet::storage((target_contract_address, Felt252Wrapper::ZERO)), Felt252Wrapper::ZERO);
+    });
+}
+
 #[test]
 fn test_verify_nonce() {
     new_test_ext().execute_with(|| {
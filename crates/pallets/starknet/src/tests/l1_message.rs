@@ -1,9 +1,15 @@
-use frame_support::assert_err;
-use mp_starknet::execution::types::{ContractClassWrapper, Felt252Wrapper};
-use mp_starknet::transaction::types::DeclareTransaction;
+use frame_support::{assert_err, bounded_vec};
+use mp_starknet::execution::types::{
+    CallEntryPointWrapper, ContractClassWrapper, EntryPointTypeWrapper, Felt252Wrapper,
+};
+use mp_starknet::transaction::types::{DeclareTransaction, Transaction, TxType};
+use starknet_api::api_core::ChainId;
+use starknet_api::transaction::Fee;
 
+use super::constants::{L1_HANDLER_CLASS_HASH, L1_HANDLER_CONTRACT_ADDRESS};
 use super::mock::*;
 use super::utils::get_contract_class;
+use crate::blockifier_state_adapter::BlockifierStateAdapter;
 use crate::Error;
 
 #[test]
@@ -29,3 +35,46 @@ fn given_contract_l1_message_fails_sender_not_deployed() {
         assert_err!(Starknet::declare(none_origin, transaction), Error::<MockRuntime>::AccountNotDeployed);
     })
 }
+
+#[test]
+fn given_l1_handler_tx_execution_charges_no_fee() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let contract_address = Felt252Wrapper::from_hex_be(L1_HANDLER_CONTRACT_ADDRESS).unwrap();
+        let transaction = Transaction {
+            tx_type: TxType::L1Handler,
+            version: 1,
+            sender_address: contract_address,
+            call_entrypoint: CallEntryPointWrapper::new(
+                Some(Felt252Wrapper::from_hex_be(L1_HANDLER_CLASS_HASH).unwrap()),
+                EntryPointTypeWrapper::L1Handler,
+                // assert_calldata_is_one
+                Some(
+                    Felt252Wrapper::from_hex_be("0x01310e2c127c3b511c5ac0fd7949d544bb4d75b8bc83aaeb357e712ecf582771")
+                        .unwrap()
+                        .into(),
+                ),
+                bounded_vec![Felt252Wrapper::ZERO, Felt252Wrapper::ONE],
+                contract_address,
+                Felt252Wrapper::ZERO,
+            ),
+            ..Transaction::default()
+        };
+
+        let execution_info = transaction
+            .execute(
+                &mut BlockifierStateAdapter::<MockRuntime>::default(),
+                Starknet::current_block(),
+                TxType::L1Handler,
+                None,
+                Starknet::fee_token_address(),
+                ChainId(Starknet::chain_id_str()),
+            )
+            .unwrap();
+
+        pretty_assertions::assert_eq!(execution_info.actual_fee, Fee(0));
+        assert!(execution_info.fee_transfer_call_info.is_none());
+    })
+}
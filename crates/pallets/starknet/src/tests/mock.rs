@@ -71,6 +71,7 @@ impl system::Config for MockRuntime {
 
 parameter_types! {
     pub const UnsignedPriority: u64 = 1 << 20;
+    pub const MaxNonceGap: u64 = 16;
 }
 
 impl pallet_starknet::Config for MockRuntime {
@@ -79,6 +80,8 @@ impl pallet_starknet::Config for MockRuntime {
     type SystemHash = mp_starknet::crypto::hash::pedersen::PedersenHasher;
     type TimestampProvider = Timestamp;
     type UnsignedPriority = UnsignedPriority;
+    type TxLimits = mp_starknet::traits::limits::DefaultTxLimits;
+    type MaxNonceGap = MaxNonceGap;
 }
 
 // Build genesis storage according to the mock runtime.
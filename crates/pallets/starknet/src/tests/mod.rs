@@ -4,9 +4,11 @@ mod current_block;
 mod declare_tx;
 mod deploy_account_tx;
 mod erc20;
+mod events;
 mod invoke_tx;
 mod l1_message;
 mod query_tx;
+mod validate_unsigned;
 
 mod constants;
 mod mock;
@@ -1,7 +1,13 @@
-use frame_support::assert_ok;
+use frame_support::{assert_err, assert_ok};
+use mp_starknet::execution::types::Felt252Wrapper;
 use mp_starknet::starknet_serde::transaction_from_json;
+use mp_starknet::transaction::types::{SimulationFlags, TransactionExecutionInfoWrapper, TxType};
+use starknet_api::api_core::ChainId;
+use starknet_core::utils::get_selector_from_name;
 
 use super::mock::*;
+use crate::blockifier_state_adapter::BlockifierStateAdapter;
+use crate::Error;
 
 #[test]
 fn estimates_tx_fee_successfully() {
@@ -34,3 +40,120 @@ fn estimate_does_not_add_to_pending() {
         assert!(pre_storage == Starknet::pending().len(), "estimate should not add a tx to pending");
     });
 }
+
+#[test]
+fn estimate_fails_when_the_execution_reverts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let json_content: &str = include_str!("../../../../../resources/transactions/invoke.json");
+        let mut tx = transaction_from_json(json_content, &[]).expect("Failed to create Transaction from JSON");
+        // Call a function that doesn't exist on the target contract.
+        let mut calldata = tx.call_entrypoint.calldata.to_vec();
+        calldata[1] = Felt252Wrapper::from(get_selector_from_name("unknown_function").unwrap());
+        tx.call_entrypoint.calldata = calldata.try_into().unwrap();
+
+        assert_err!(Starknet::estimate_fee(tx), Error::<MockRuntime>::TransactionExecutionFailed);
+    });
+}
+
+/// Simulates the invoke transaction of `resources/transactions/invoke.json`.
+///
+/// Returns the execution info and the balance of the sender before and after the simulation.
+fn simulate_invoke(flags: SimulationFlags) -> (TransactionExecutionInfoWrapper, Felt252Wrapper, Felt252Wrapper) {
+    let json_content: &str = include_str!("../../../../../resources/transactions/invoke.json");
+    let tx = transaction_from_json(json_content, &[]).expect("Failed to create Transaction from JSON");
+    let balance_key = get_storage_key(&Starknet::fee_token_address(), "ERC20_balances", &[tx.sender_address], 0);
+
+    let balance_before = Starknet::storage(balance_key);
+    let execution_info = tx
+        .simulate(
+            &mut BlockifierStateAdapter::<MockRuntime>::default(),
+            Starknet::current_block(),
+            TxType::Invoke,
+            None,
+            Starknet::fee_token_address(),
+            ChainId(Starknet::chain_id_str()),
+            flags,
+        )
+        .unwrap();
+    let balance_after = Starknet::storage(balance_key);
+
+    (execution_info, balance_before, balance_after)
+}
+
+#[test]
+fn simulate_without_flags_validates_and_charges_fee() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let (execution_info, balance_before, balance_after) = simulate_invoke(SimulationFlags::default());
+
+        assert!(execution_info.validate_call_info.is_some());
+        assert!(execution_info.fee_transfer_call_info.is_some());
+        assert!(execution_info.actual_fee.0 > 0, "actual fee is missing");
+        assert_ne!(balance_before, balance_after);
+    });
+}
+
+#[test]
+fn simulate_measures_the_execution_duration() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let (execution_info, _, _) = simulate_invoke(SimulationFlags::default());
+
+        assert!(execution_info.execution_duration_micros().is_some(), "execution duration is missing");
+    });
+}
+
+#[test]
+fn simulate_skip_validate_doesnt_validate() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let flags = SimulationFlags { skip_validate: true, skip_fee_charge: false };
+        let (execution_info, balance_before, balance_after) = simulate_invoke(flags);
+
+        assert!(execution_info.validate_call_info.is_none());
+        assert!(execution_info.fee_transfer_call_info.is_some());
+        assert!(execution_info.actual_fee.0 > 0, "actual fee is missing");
+        assert_ne!(balance_before, balance_after);
+    });
+}
+
+#[test]
+fn simulate_skip_fee_charge_reports_fee_without_charging_it() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let flags = SimulationFlags { skip_validate: false, skip_fee_charge: true };
+        let (execution_info, balance_before, balance_after) = simulate_invoke(flags);
+
+        assert!(execution_info.validate_call_info.is_some());
+        assert!(execution_info.fee_transfer_call_info.is_none());
+        assert!(execution_info.actual_fee.0 > 0, "actual fee is missing");
+        pretty_assertions::assert_eq!(balance_before, balance_after);
+    });
+}
+
+#[test]
+fn simulate_skip_validate_and_fee_charge() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let flags = SimulationFlags { skip_validate: true, skip_fee_charge: true };
+        let (execution_info, balance_before, balance_after) = simulate_invoke(flags);
+
+        assert!(execution_info.validate_call_info.is_none());
+        assert!(execution_info.fee_transfer_call_info.is_none());
+        assert!(execution_info.actual_fee.0 > 0, "actual fee is missing");
+        pretty_assertions::assert_eq!(balance_before, balance_after);
+    });
+}
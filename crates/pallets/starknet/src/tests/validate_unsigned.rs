@@ -0,0 +1,157 @@
+use frame_support::traits::Get;
+use frame_support::unsigned::ValidateUnsigned;
+use mp_starknet::crypto::commitment::calculate_transaction_hash;
+use mp_starknet::execution::types::Felt252Wrapper;
+use mp_starknet::traits::limits::TxLimits;
+use mp_starknet::transaction::types::{DeployAccountTransaction, InvokeTransaction, Transaction, TxType};
+use sp_core::U256;
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError};
+
+use super::constants::TEST_CONTRACT_ADDRESS;
+use super::mock::*;
+use crate::{Call, Nonces};
+
+fn funded_invoke(nonce: Felt252Wrapper) -> InvokeTransaction {
+    InvokeTransaction {
+        sender_address: get_account_address(AccountType::NoValidate),
+        nonce,
+        max_fee: Felt252Wrapper::from(u128::MAX),
+        ..InvokeTransaction::default()
+    }
+}
+
+fn validate_invoke(transaction: InvokeTransaction) -> Result<(), TransactionValidityError> {
+    Starknet::validate_unsigned(TransactionSource::External, &Call::invoke { transaction }).map(|_| ())
+}
+
+#[test]
+fn given_invoke_tx_validate_unsigned_provides_its_sender_and_nonce() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let transaction = funded_invoke(Felt252Wrapper::ONE);
+        let call = Call::invoke { transaction: transaction.clone() };
+
+        let validity = Starknet::validate_unsigned(TransactionSource::External, &call).unwrap();
+
+        pretty_assertions::assert_eq!(validity.priority, u64::MAX - 1);
+        pretty_assertions::assert_eq!(
+            validity.provides,
+            vec![scale_codec::Encode::encode(&("starknet", (transaction.sender_address, transaction.nonce)))]
+        );
+    });
+}
+
+#[test]
+fn given_invoke_tx_with_nonce_far_ahead_validate_unsigned_is_future() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let within_gap = validate_invoke(funded_invoke(Felt252Wrapper::from(MaxNonceGap::get())));
+        let over_gap = validate_invoke(funded_invoke(Felt252Wrapper::from(MaxNonceGap::get() + 1)));
+        let over_u64 = validate_invoke(funded_invoke(Felt252Wrapper::from(u128::from(u64::MAX) + 1)));
+
+        assert!(within_gap.is_ok());
+        pretty_assertions::assert_eq!(over_gap, Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
+        pretty_assertions::assert_eq!(over_u64, Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
+    });
+}
+
+#[test]
+fn given_invoke_tx_with_used_nonce_validate_unsigned_is_stale() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        Nonces::<MockRuntime>::insert(get_account_address(AccountType::NoValidate), U256::from(2));
+
+        let validity = validate_invoke(funded_invoke(Felt252Wrapper::ONE));
+
+        pretty_assertions::assert_eq!(validity, Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)));
+    });
+}
+
+#[test]
+fn given_invoke_tx_that_cant_pay_validate_unsigned_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let zero_max_fee = InvokeTransaction { max_fee: Felt252Wrapper::ZERO, ..funded_invoke(Felt252Wrapper::ZERO) };
+        let no_balance = InvokeTransaction {
+            sender_address: Felt252Wrapper::from_hex_be(TEST_CONTRACT_ADDRESS).unwrap(),
+            ..funded_invoke(Felt252Wrapper::ZERO)
+        };
+
+        let zero_max_fee = validate_invoke(zero_max_fee);
+        let no_balance = validate_invoke(no_balance);
+
+        pretty_assertions::assert_eq!(
+            zero_max_fee,
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+        );
+        pretty_assertions::assert_eq!(no_balance, Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)));
+    });
+}
+
+#[test]
+fn given_txs_over_the_runtime_limits_validate_unsigned_exhausts_resources() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let max_signature_size = <<MockRuntime as crate::Config>::TxLimits as TxLimits>::MaxSignatureSize::get();
+        let max_constructor_calldata =
+            <<MockRuntime as crate::Config>::TxLimits as TxLimits>::DeployConstructorMaxCalldata::get();
+        let invoke = InvokeTransaction {
+            signature: vec![Felt252Wrapper::ONE; max_signature_size as usize + 1].try_into().unwrap(),
+            ..funded_invoke(Felt252Wrapper::ZERO)
+        };
+        let mut deploy_account = DeployAccountTransaction {
+            calldata: vec![Felt252Wrapper::ONE; max_constructor_calldata as usize + 1].try_into().unwrap(),
+            ..DeployAccountTransaction::default()
+        };
+        deploy_account.sender_address = deploy_account.contract_address().unwrap();
+
+        let invoke = validate_invoke(invoke);
+        let deploy_account = Starknet::validate_unsigned(
+            TransactionSource::External,
+            &Call::deploy_account { transaction: deploy_account },
+        )
+        .map(|_| ());
+
+        let exhausts_resources = Err(TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources));
+        pretty_assertions::assert_eq!(invoke, exhausts_resources);
+        pretty_assertions::assert_eq!(deploy_account, exhausts_resources);
+    });
+}
+
+#[test]
+fn given_l1_message_with_wrong_hash_validate_unsigned_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(0);
+        run_to_block(2);
+
+        let chain_id = Starknet::chain_id().into();
+        let transaction = Transaction {
+            tx_type: TxType::L1Handler,
+            sender_address: get_account_address(AccountType::NoValidate),
+            paid_fee_on_l1: Some(Felt252Wrapper::ONE),
+            ..Transaction::default()
+        };
+        let hashed = Transaction { hash: calculate_transaction_hash(&transaction, chain_id), ..transaction };
+        let tampered = Transaction { nonce: Felt252Wrapper::ONE, ..hashed.clone() };
+
+        let valid =
+            Starknet::validate_unsigned(TransactionSource::External, &Call::consume_l1_message { transaction: hashed });
+        let invalid = Starknet::validate_unsigned(
+            TransactionSource::External,
+            &Call::consume_l1_message { transaction: tampered },
+        );
+
+        assert!(valid.is_ok());
+        pretty_assertions::assert_eq!(invalid, Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof)));
+    });
+}
@@ -6,6 +6,7 @@ use starknet_api::block::{BlockNumber, BlockTimestamp};
 use starknet_api::hash::StarkFelt;
 use starknet_api::stdlib::collections::HashMap;
 
+use crate::crypto::commitment::CommitmentVersion;
 use crate::execution::types::{ContractAddressWrapper, Felt252Wrapper};
 use crate::traits::hash::HasherT;
 
@@ -101,6 +102,17 @@ impl Header {
         }
     }
 
+    /// Returns the rules the transaction and event commitments of the block are computed with.
+    ///
+    /// Always [`CommitmentVersion::Pedersen`] for now: the version selection isn't wired into
+    /// Madara blocks yet. Their headers don't record the Starknet version they were built with,
+    /// and their `protocol_version` is unrelated to it, so the Poseidon commitments of
+    /// [`CommitmentVersion::from_starknet_version`] are never picked from a header.
+    #[must_use]
+    pub fn commitment_version(&self) -> CommitmentVersion {
+        CommitmentVersion::Pedersen
+    }
+
     /// Compute the hash of the header.
     #[must_use]
     pub fn hash<H: HasherT>(&self, hasher: H) -> Felt252Wrapper {
@@ -1,14 +1,15 @@
 //! StarkNet block primitives.
 
 mod header;
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
 use frame_support::BoundedVec;
 pub use header::*;
 use sp_core::ConstU32;
 
-use crate::execution::types::Felt252Wrapper;
-use crate::transaction::types::{Transaction, TransactionReceiptWrapper};
+use crate::execution::types::{ContractAddressWrapper, Felt252Wrapper};
+use crate::transaction::types::{EventWrapper, Transaction, TransactionReceiptWrapper};
 
 /// Block transactions max size
 // TODO: add real value (#250)
@@ -103,3 +104,54 @@ impl Block {
         }
     }
 }
+
+/// Iterates over the events emitted in a block with their global index.
+///
+/// Events are ordered by transaction then by emission order within the transaction,
+/// and indexed sequentially across the whole block. This is the ordering used for
+/// the event commitment and for events pagination.
+///
+/// # Arguments
+///
+/// * `receipts` - The receipts of the block transactions, in execution order.
+pub fn enumerate_block_events(receipts: &[TransactionReceiptWrapper]) -> impl Iterator<Item = (u64, &EventWrapper)> {
+    receipts.iter().flat_map(|receipt| receipt.events.iter()).enumerate().map(|(index, event)| (index as u64, event))
+}
+
+/// Returns the sum of the fees charged for the transactions of a block.
+///
+/// The addition is done in the field, so the result wraps modulo the field prime. This can't
+/// happen in practice since every fee is bounded by the fee token supply.
+///
+/// # Arguments
+///
+/// * `receipts` - The receipts of the block transactions.
+pub fn total_fees(receipts: &[TransactionReceiptWrapper]) -> Felt252Wrapper {
+    Felt252Wrapper(receipts.iter().fold(Felt252Wrapper::ZERO.0, |total, receipt| total + receipt.actual_fee.0))
+}
+
+/// Returns the number of events emitted by the transactions of a block.
+///
+/// # Arguments
+///
+/// * `receipts` - The receipts of the block transactions.
+pub fn total_events(receipts: &[TransactionReceiptWrapper]) -> usize {
+    receipts.iter().map(|receipt| receipt.events.len()).sum()
+}
+
+/// Groups the events emitted in a block by the address of the contract that emitted them.
+///
+/// The events of each address keep the block ordering, see [`enumerate_block_events`].
+///
+/// # Arguments
+///
+/// * `receipts` - The receipts of the block transactions, in execution order.
+pub fn group_events_by_address(
+    receipts: &[TransactionReceiptWrapper],
+) -> BTreeMap<ContractAddressWrapper, Vec<&EventWrapper>> {
+    let mut events_by_address: BTreeMap<ContractAddressWrapper, Vec<&EventWrapper>> = BTreeMap::new();
+    for (_, event) in enumerate_block_events(receipts) {
+        events_by_address.entry(event.from_address).or_default().push(event);
+    }
+    events_by_address
+}
@@ -1,16 +1,19 @@
 use alloc::vec;
 use alloc::vec::Vec;
+use core::borrow::Borrow;
 
 use bitvec::vec::BitVec;
 use sp_core::H256;
-use starknet_crypto::FieldElement;
+use starknet_crypto::{poseidon_hash_many, FieldElement};
 
 use super::hash::pedersen::PedersenHasher;
+use super::hash::poseidon::PoseidonHasher;
 use super::merkle_patricia_tree::merkle_tree::MerkleTree;
-use crate::execution::types::Felt252Wrapper;
-use crate::traits::hash::CryptoHasherT;
+use crate::execution::types::{ChainId, ContractAddressWrapper, EntryPointSelector, Felt252Wrapper};
+use crate::traits::hash::{CryptoHasherT, TransactionHasher};
 use crate::transaction::types::{
-    DeclareTransaction, DeployAccountTransaction, EventWrapper, InvokeTransaction, Transaction,
+    DataAvailabilityMode, DeclareTransaction, DeployAccountTransaction, DeployTransaction, EventWrapper,
+    InvokeTransaction, Transaction, TxType,
 };
 
 /// A Patricia Merkle tree with height 64 used to compute transaction and event commitments.
@@ -48,6 +51,154 @@ impl<T: CryptoHasherT> CommitmentTree<T> {
     }
 }
 
+/// Upper bound of the L2 contract addresses, `2**251 - 256`.
+const L2_ADDRESS_UPPER_BOUND: &str = "0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00";
+
+/// First Starknet version whose block commitments are computed with Poseidon.
+pub const POSEIDON_COMMITMENT_STARKNET_VERSION: [u64; 3] = [0, 13, 2];
+
+/// The hash function used to compute the commitments of a block.
+///
+/// Blocks before Starknet 0.13.2 use Pedersen based commitments while newer ones use Poseidon,
+/// so this must be picked from the version of the block to compute historically-correct
+/// commitments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitmentVersion {
+    /// Commitments computed with the Pedersen hash.
+    Pedersen,
+    /// Commitments computed with the Poseidon hash, from Starknet 0.13.2.
+    Poseidon,
+}
+
+/// Error of [`CommitmentVersion::from_starknet_version`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidStarknetVersion;
+
+impl CommitmentVersion {
+    /// Returns the commitment version used by blocks of the given Starknet version.
+    ///
+    /// # Arguments
+    ///
+    /// * `starknet_version` - The Starknet version of the block, e.g. `"0.13.2"`, [None] for the
+    ///   blocks that predate the version field.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidStarknetVersion`] if the version isn't made of dot separated numbers.
+    pub fn from_starknet_version(starknet_version: Option<&str>) -> Result<Self, InvalidStarknetVersion> {
+        let starknet_version = match starknet_version {
+            Some(starknet_version) => starknet_version,
+            None => return Ok(Self::Pedersen),
+        };
+        // Missing trailing components are zeros, `0.13` is `0.13.0`.
+        let mut version = [0_u64; 3];
+        let mut components = starknet_version.split('.');
+        for (slot, component) in version.iter_mut().zip(components.by_ref()) {
+            *slot = component.parse().map_err(|_| InvalidStarknetVersion)?;
+        }
+        if components.next().is_some() {
+            return Err(InvalidStarknetVersion);
+        }
+
+        Ok(if version >= POSEIDON_COMMITMENT_STARKNET_VERSION { Self::Poseidon } else { Self::Pedersen })
+    }
+}
+
+/// Calculate the transaction commitment and the event commitment with the rules of the
+/// commitment version of the block.
+///
+/// From Starknet 0.13.2 the trees are built with Poseidon and so are their leaves: a transaction
+/// leaf is `poseidon([tx_hash, ...signature])`, with a zero signature when it is empty, and an
+/// event leaf is `poseidon([from_address, tx_hash, keys_len, ...keys, data_len, ...data])`.
+///
+/// # Arguments
+///
+/// * `transactions` - The transactions of the block
+/// * `events` - The events of the block
+/// * `version` - The commitment version of the block
+///
+/// # Returns
+///
+/// The transaction commitment and the event commitment.
+pub fn calculate_versioned_commitments(
+    transactions: &[Transaction],
+    events: &[EventWrapper],
+    version: CommitmentVersion,
+) -> (H256, H256) {
+    (
+        calculate_versioned_transaction_commitment(transactions, version),
+        calculate_versioned_event_commitment(events, version),
+    )
+}
+
+/// Calculate the transaction commitment with the rules of the commitment version of the block,
+/// see [`calculate_versioned_commitments`].
+///
+/// # Arguments
+///
+/// * `transactions` - The transactions of the block
+/// * `version` - The commitment version of the block
+pub fn calculate_versioned_transaction_commitment(transactions: &[Transaction], version: CommitmentVersion) -> H256 {
+    match version {
+        CommitmentVersion::Pedersen => calculate_transaction_commitment::<PedersenHasher>(transactions),
+        CommitmentVersion::Poseidon => {
+            commit_leaves::<PoseidonHasher>(transactions.iter().map(calculate_poseidon_transaction_leaf))
+        }
+    }
+}
+
+/// Calculate the event commitment with the rules of the commitment version of the block, see
+/// [`calculate_versioned_commitments`].
+///
+/// # Arguments
+///
+/// * `events` - The events of the block
+/// * `version` - The commitment version of the block
+pub fn calculate_versioned_event_commitment(events: &[EventWrapper], version: CommitmentVersion) -> H256 {
+    match version {
+        CommitmentVersion::Pedersen => calculate_event_commitment::<PedersenHasher>(events),
+        CommitmentVersion::Poseidon => {
+            commit_leaves::<PoseidonHasher>(events.iter().map(calculate_poseidon_event_leaf))
+        }
+    }
+}
+
+/// Builds a commitment tree from its leaves, keyed by their index, and returns its root.
+fn commit_leaves<T: CryptoHasherT>(leaves: impl Iterator<Item = FieldElement>) -> H256 {
+    let mut tree = CommitmentTree::<T>::default();
+    leaves.enumerate().for_each(|(idx, leaf)| {
+        let idx: u64 = idx.try_into().expect("too many leaves while calculating commitment");
+        tree.set(idx, leaf);
+    });
+    H256::from_slice(&tree.commit().to_bytes_be())
+}
+
+/// The leaf of a transaction in a Poseidon era transaction commitment,
+/// `poseidon([tx_hash, ...signature])`, or `poseidon([tx_hash, 0])` without signature.
+fn calculate_poseidon_transaction_leaf(tx: &Transaction) -> FieldElement {
+    let mut elements = Vec::with_capacity(tx.signature.len().max(1) + 1);
+    elements.push(FieldElement::from(tx.hash));
+    if tx.signature.is_empty() {
+        elements.push(FieldElement::ZERO);
+    } else {
+        elements.extend(tx.signature.iter().map(|&elt| FieldElement::from(elt)));
+    }
+    poseidon_hash_many(&elements)
+}
+
+/// The leaf of an event in a Poseidon era event commitment,
+/// `poseidon([from_address, tx_hash, keys_len, ...keys, data_len, ...data])`.
+fn calculate_poseidon_event_leaf(event: &EventWrapper) -> FieldElement {
+    let mut elements = Vec::with_capacity(event.keys.len() + event.data.len() + 4);
+    elements.push(FieldElement::from(event.from_address));
+    elements.push(FieldElement::from(event.transaction_hash));
+    elements.push(FieldElement::from(event.keys.len() as u64));
+    elements.extend(event.keys.iter().map(|&key| FieldElement::from(key)));
+    elements.push(FieldElement::from(event.data.len() as u64));
+    elements.extend(event.data.iter().map(|&data| FieldElement::from(data)));
+    poseidon_hash_many(&elements)
+}
+
 /// Calculate the transaction commitment, the event commitment and the event count.
 ///
 /// # Arguments
@@ -130,12 +281,52 @@ where
     );
     <T as CryptoHasherT>::hash(FieldElement::from(tx.hash), signature_hash)
 }
+/// The transaction hashing rules of Starknet, computed with pedersen.
+///
+/// This is the default [`TransactionHasher`], each method delegates to the matching
+/// `calculate_*_tx_hash` function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StarknetV0;
+
+impl TransactionHasher for StarknetV0 {
+    fn invoke_tx_hash(transaction: &InvokeTransaction, chain_id: ChainId) -> Felt252Wrapper {
+        calculate_invoke_tx_hash(transaction, chain_id)
+    }
+
+    fn declare_tx_hash(transaction: &DeclareTransaction, chain_id: ChainId) -> Felt252Wrapper {
+        calculate_declare_tx_hash(transaction, chain_id)
+    }
+
+    fn deploy_account_tx_hash(transaction: &DeployAccountTransaction, chain_id: ChainId) -> Felt252Wrapper {
+        calculate_deploy_account_tx_hash(transaction, chain_id)
+    }
+
+    fn deploy_tx_hash(transaction: &DeployTransaction, chain_id: ChainId) -> Felt252Wrapper {
+        calculate_deploy_tx_hash(transaction, chain_id)
+    }
+}
+
 /// Computes the transaction hash of an invoke transaction.
 ///
+/// A v0 transaction calls an entry point of a contract directly, its hash commits to the entry
+/// point selector and has no nonce, see [`calculate_transaction_hash_preimage`].
+///
 /// # Argument
 ///
-/// * `transaction` - The invoke transaction to get the hash of.
-pub fn calculate_invoke_tx_hash(transaction: InvokeTransaction, chain_id: &str) -> Felt252Wrapper {
+/// * `transaction` - The invoke transaction to get the hash of, owned or borrowed.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_invoke_tx_hash(transaction: impl Borrow<InvokeTransaction>, chain_id: ChainId) -> Felt252Wrapper {
+    let transaction = transaction.borrow();
+    if transaction.version == 0 {
+        let preimage = invoke_v0_tx_hash_preimage::<PedersenHasher>(
+            transaction.sender_address,
+            transaction.entry_point_selector.unwrap_or_default().into(),
+            &transaction.calldata,
+            transaction.max_fee,
+            chain_id.into(),
+        );
+        return <PedersenHasher as CryptoHasherT>::compute_hash_on_elements(&preimage).into();
+    }
     calculate_transaction_hash_common::<PedersenHasher>(
         transaction.sender_address.into(),
         transaction.calldata.as_slice(),
@@ -143,7 +334,7 @@ pub fn calculate_invoke_tx_hash(transaction: InvokeTransaction, chain_id: &str)
         transaction.nonce,
         transaction.version,
         b"invoke",
-        chain_id,
+        chain_id.into(),
     )
 }
 
@@ -151,8 +342,10 @@ pub fn calculate_invoke_tx_hash(transaction: InvokeTransaction, chain_id: &str)
 ///
 /// # Argument
 ///
-/// * `transaction` - The declare transaction to get the hash of.
-pub fn calculate_declare_tx_hash(transaction: DeclareTransaction, chain_id: &str) -> Felt252Wrapper {
+/// * `transaction` - The declare transaction to get the hash of, owned or borrowed.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_declare_tx_hash(transaction: impl Borrow<DeclareTransaction>, chain_id: ChainId) -> Felt252Wrapper {
+    let transaction = transaction.borrow();
     calculate_transaction_hash_common::<PedersenHasher>(
         transaction.sender_address.into(),
         &[transaction.compiled_class_hash],
@@ -160,7 +353,7 @@ pub fn calculate_declare_tx_hash(transaction: DeclareTransaction, chain_id: &str
         transaction.nonce,
         transaction.version,
         b"declare",
-        chain_id,
+        chain_id.into(),
     )
 }
 
@@ -168,8 +361,13 @@ pub fn calculate_declare_tx_hash(transaction: DeclareTransaction, chain_id: &str
 ///
 /// # Argument
 ///
-/// * `transaction` - The deploy account transaction to get the hash of.
-pub fn calculate_deploy_account_tx_hash(transaction: DeployAccountTransaction, chain_id: &str) -> Felt252Wrapper {
+/// * `transaction` - The deploy account transaction to get the hash of, owned or borrowed.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_deploy_account_tx_hash(
+    transaction: impl Borrow<DeployAccountTransaction>,
+    chain_id: ChainId,
+) -> Felt252Wrapper {
+    let transaction = transaction.borrow();
     calculate_transaction_hash_common::<PedersenHasher>(
         transaction.sender_address.into(),
         &vec![
@@ -181,10 +379,227 @@ pub fn calculate_deploy_account_tx_hash(transaction: DeployAccountTransaction, c
         transaction.nonce,
         transaction.version,
         b"deploy_account",
-        chain_id,
+        chain_id.into(),
     )
 }
 
+/// Computes the transaction hash of a legacy deploy transaction.
+///
+/// The hash commits to the constructor selector where the other transactions have a zero, and has
+/// neither a nonce nor a max fee since the transaction isn't sent by an account.
+///
+/// # Argument
+///
+/// * `transaction` - The legacy deploy transaction to get the hash of, owned or borrowed.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_deploy_tx_hash(transaction: impl Borrow<DeployTransaction>, chain_id: ChainId) -> Felt252Wrapper {
+    let transaction = transaction.borrow();
+    <PedersenHasher as CryptoHasherT>::compute_hash_on_elements(&deploy_tx_hash_preimage::<PedersenHasher>(
+        transaction.contract_address(),
+        &transaction.constructor_calldata,
+        transaction.version,
+        chain_id.into(),
+    ))
+    .into()
+}
+
+/// Computes the transaction hash of an L1 handler transaction.
+///
+/// The hash commits to the entry point selector where the other transactions have a zero, and to
+/// a null max fee since the fee is paid on L1.
+///
+/// # Arguments
+///
+/// * `contract_address` - The address of the L2 contract handling the message.
+/// * `entry_point_selector` - The selector of the L1 handler.
+/// * `calldata` - The calldata of the handler, the L1 sender address followed by the payload.
+/// * `nonce` - The nonce of the message on L1.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_l1_handler_tx_hash(
+    contract_address: ContractAddressWrapper,
+    entry_point_selector: Felt252Wrapper,
+    calldata: &[Felt252Wrapper],
+    nonce: Felt252Wrapper,
+    chain_id: ChainId,
+) -> Felt252Wrapper {
+    <PedersenHasher as CryptoHasherT>::compute_hash_on_elements(&l1_handler_tx_hash_preimage::<PedersenHasher>(
+        contract_address,
+        entry_point_selector,
+        calldata,
+        nonce,
+        chain_id.into(),
+    ))
+    .into()
+}
+
+/// Computes the address a contract is deployed at, the Pedersen hash of
+/// `["STARKNET_CONTRACT_ADDRESS", deployer_address, salt, class_hash, h(constructor_calldata)]`
+/// reduced modulo `2**251 - 256`, the upper bound of the L2 addresses.
+///
+/// # Arguments
+///
+/// * `salt` - The salt of the deployment.
+/// * `class_hash` - The class hash of the deployed contract.
+/// * `constructor_calldata` - The calldata of the constructor.
+/// * `deployer_address` - The address of the deployer, zero for a deploy account transaction.
+pub fn calculate_contract_address(
+    salt: Felt252Wrapper,
+    class_hash: Felt252Wrapper,
+    constructor_calldata: &[Felt252Wrapper],
+    deployer_address: ContractAddressWrapper,
+) -> ContractAddressWrapper {
+    let constructor_calldata_hash = <PedersenHasher as CryptoHasherT>::compute_hash_on_elements(
+        &constructor_calldata.iter().map(|&val| FieldElement::from(val)).collect::<Vec<FieldElement>>(),
+    );
+    let address = <PedersenHasher as CryptoHasherT>::compute_hash_on_elements(&[
+        FieldElement::from_byte_slice_be(b"STARKNET_CONTRACT_ADDRESS").unwrap(),
+        deployer_address.into(),
+        salt.into(),
+        class_hash.into(),
+        constructor_calldata_hash,
+    ]);
+
+    // The hash is lower than the field modulus, which is lower than twice the bound, so a single
+    // subtraction is enough to reduce it.
+    let l2_address_upper_bound = FieldElement::from_hex_be(L2_ADDRESS_UPPER_BOUND).unwrap();
+    let address = if address >= l2_address_upper_bound { address - l2_address_upper_bound } else { address };
+    address.into()
+}
+
+/// Packs the data availability modes of a v3 transaction into the felt that goes into its hash,
+/// `nonce_mode << 32 | fee_mode`.
+///
+/// # Arguments
+///
+/// * `nonce_mode` - The data availability mode of the nonce.
+/// * `fee_mode` - The data availability mode of the fee.
+pub fn pack_da_modes(nonce_mode: DataAvailabilityMode, fee_mode: DataAvailabilityMode) -> Felt252Wrapper {
+    Felt252Wrapper::from(((nonce_mode as u64) << 32) | fee_mode as u64)
+}
+
+/// Computes the hash of a transaction from its fields, the Pedersen hash of its
+/// [preimage](calculate_transaction_hash_preimage).
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction to get the hash of.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_transaction_hash(transaction: &Transaction, chain_id: ChainId) -> Felt252Wrapper {
+    let preimage = calculate_transaction_hash_preimage(transaction, chain_id);
+    <PedersenHasher as CryptoHasherT>::compute_hash_on_elements(
+        &preimage.into_iter().map(FieldElement::from).collect::<Vec<FieldElement>>(),
+    )
+    .into()
+}
+
+/// Computes the ordered felts the hash of a transaction is computed on:
+/// `[tx_prefix, version, sender_address, 0, calldata_hash, max_fee, chain_id, nonce]`.
+///
+/// The calldata of a declare transaction is its class hash, and the one of a deploy account
+/// transaction is its class hash and salt followed by the constructor calldata. Legacy deploy
+/// transactions have their own preimage, see [`calculate_deploy_tx_hash`], and so do v0 invoke
+/// transactions, `["invoke", 0, contract_address, entry_point_selector, calldata_hash, max_fee,
+/// chain_id]`, and L1 handler transactions, see [`calculate_l1_handler_tx_hash`].
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction to get the hash preimage of.
+/// * `chain_id` - The chain id of the network.
+pub fn calculate_transaction_hash_preimage(transaction: &Transaction, chain_id: ChainId) -> Vec<Felt252Wrapper> {
+    let (calldata, tx_prefix): (Vec<Felt252Wrapper>, &[u8]) = match transaction.tx_type {
+        TxType::Deploy => {
+            return deploy_tx_hash_preimage::<PedersenHasher>(
+                transaction.sender_address,
+                &transaction.call_entrypoint.calldata,
+                transaction.version,
+                chain_id.into(),
+            )
+            .into_iter()
+            .map(Felt252Wrapper::from)
+            .collect();
+        }
+        TxType::Invoke if transaction.version == 0 => {
+            return invoke_v0_tx_hash_preimage::<PedersenHasher>(
+                transaction.sender_address,
+                transaction.call_entrypoint.entrypoint_selector.unwrap_or_default().into(),
+                &transaction.call_entrypoint.calldata,
+                transaction.max_fee,
+                chain_id.into(),
+            )
+            .into_iter()
+            .map(Felt252Wrapper::from)
+            .collect();
+        }
+        TxType::Invoke => (transaction.call_entrypoint.calldata.to_vec(), b"invoke"),
+        TxType::Declare => (vec![transaction.call_entrypoint.class_hash.unwrap_or_default()], b"declare"),
+        TxType::DeployAccount => (
+            vec![
+                vec![
+                    transaction.call_entrypoint.class_hash.unwrap_or_default(),
+                    transaction.contract_address_salt.unwrap_or_default(),
+                ],
+                transaction.call_entrypoint.calldata.to_vec(),
+            ]
+            .concat(),
+            b"deploy_account",
+        ),
+        TxType::L1Handler => {
+            return l1_handler_tx_hash_preimage::<PedersenHasher>(
+                transaction.sender_address,
+                transaction.call_entrypoint.entrypoint_selector.unwrap_or_default().into(),
+                &transaction.call_entrypoint.calldata,
+                transaction.nonce,
+                chain_id.into(),
+            )
+            .into_iter()
+            .map(Felt252Wrapper::from)
+            .collect();
+        }
+    };
+    transaction_hash_preimage::<PedersenHasher>(
+        transaction.sender_address.into(),
+        &calldata,
+        transaction.max_fee,
+        transaction.nonce,
+        transaction.version,
+        tx_prefix,
+        chain_id.into(),
+    )
+    .into_iter()
+    .map(Felt252Wrapper::from)
+    .collect()
+}
+
+/// Computes the hashes of a block's transactions, see [`calculate_transaction_hash`].
+///
+/// The hashes are independent from each other, so they are computed in parallel with `std` and
+/// sequentially otherwise. They are returned in the order of the transactions.
+///
+/// # Arguments
+///
+/// * `txs` - The transactions to get the hashes of.
+/// * `chain_id` - The chain id of the network.
+#[cfg(feature = "std")]
+pub fn hash_transactions_parallel(txs: &[Transaction], chain_id: ChainId) -> Vec<Felt252Wrapper> {
+    use rayon::prelude::*;
+
+    txs.par_iter().map(|tx| calculate_transaction_hash(tx, chain_id)).collect()
+}
+
+/// Computes the hashes of a block's transactions, see [`calculate_transaction_hash`].
+///
+/// Without `std` the hashes are computed sequentially. They are returned in the order of the
+/// transactions.
+///
+/// # Arguments
+///
+/// * `txs` - The transactions to get the hashes of.
+/// * `chain_id` - The chain id of the network.
+#[cfg(not(feature = "std"))]
+pub fn hash_transactions_parallel(txs: &[Transaction], chain_id: ChainId) -> Vec<Felt252Wrapper> {
+    txs.iter().map(|tx| calculate_transaction_hash(tx, chain_id)).collect()
+}
+
 fn calculate_transaction_hash_common<T>(
     sender_address: [u8; 32],
     calldata: &[Felt252Wrapper],
@@ -192,8 +607,104 @@ fn calculate_transaction_hash_common<T>(
     nonce: Felt252Wrapper,
     version: u8,
     tx_prefix: &[u8],
-    chain_id: &str,
+    chain_id: FieldElement,
 ) -> Felt252Wrapper
+where
+    T: CryptoHasherT,
+{
+    let preimage =
+        transaction_hash_preimage::<T>(sender_address, calldata, max_fee, nonce, version, tx_prefix, chain_id);
+    <T as CryptoHasherT>::compute_hash_on_elements(&preimage).into()
+}
+
+/// Computes the ordered felts the hash of a legacy deploy transaction is computed on:
+/// `["deploy", version, contract_address, constructor_selector, calldata_hash, 0, chain_id]`.
+fn deploy_tx_hash_preimage<T>(
+    contract_address: ContractAddressWrapper,
+    constructor_calldata: &[Felt252Wrapper],
+    version: u8,
+    chain_id: FieldElement,
+) -> Vec<FieldElement>
+where
+    T: CryptoHasherT,
+{
+    let calldata_hash = <T as CryptoHasherT>::compute_hash_on_elements(
+        &constructor_calldata.iter().map(|&val| FieldElement::from(val)).collect::<Vec<FieldElement>>(),
+    );
+
+    vec![
+        FieldElement::from_byte_slice_be(b"deploy").unwrap(),
+        FieldElement::from_byte_slice_be(&version.to_be_bytes()).unwrap(),
+        contract_address.into(),
+        EntryPointSelector::CONSTRUCTOR.0.into(),
+        calldata_hash,
+        FieldElement::ZERO,
+        chain_id,
+    ]
+}
+
+/// Computes the ordered felts the hash of a v0 invoke transaction is computed on:
+/// `["invoke", 0, contract_address, entry_point_selector, calldata_hash, max_fee, chain_id]`.
+fn invoke_v0_tx_hash_preimage<T>(
+    contract_address: ContractAddressWrapper,
+    entry_point_selector: Felt252Wrapper,
+    calldata: &[Felt252Wrapper],
+    max_fee: Felt252Wrapper,
+    chain_id: FieldElement,
+) -> Vec<FieldElement>
+where
+    T: CryptoHasherT,
+{
+    let calldata_hash = <T as CryptoHasherT>::compute_hash_on_elements(
+        &calldata.iter().map(|&val| FieldElement::from(val)).collect::<Vec<FieldElement>>(),
+    );
+
+    vec![
+        FieldElement::from_byte_slice_be(b"invoke").unwrap(),
+        FieldElement::ZERO,
+        contract_address.into(),
+        entry_point_selector.into(),
+        calldata_hash,
+        max_fee.into(),
+        chain_id,
+    ]
+}
+
+fn l1_handler_tx_hash_preimage<T>(
+    contract_address: ContractAddressWrapper,
+    entry_point_selector: Felt252Wrapper,
+    calldata: &[Felt252Wrapper],
+    nonce: Felt252Wrapper,
+    chain_id: FieldElement,
+) -> Vec<FieldElement>
+where
+    T: CryptoHasherT,
+{
+    let calldata_hash = <T as CryptoHasherT>::compute_hash_on_elements(
+        &calldata.iter().map(|&val| FieldElement::from(val)).collect::<Vec<FieldElement>>(),
+    );
+
+    vec![
+        FieldElement::from_byte_slice_be(b"l1_handler").unwrap(),
+        FieldElement::ZERO,
+        contract_address.into(),
+        entry_point_selector.into(),
+        calldata_hash,
+        FieldElement::ZERO,
+        chain_id,
+        nonce.into(),
+    ]
+}
+
+fn transaction_hash_preimage<T>(
+    sender_address: [u8; 32],
+    calldata: &[Felt252Wrapper],
+    max_fee: Felt252Wrapper,
+    nonce: Felt252Wrapper,
+    version: u8,
+    tx_prefix: &[u8],
+    chain_id: FieldElement,
+) -> Vec<FieldElement>
 where
     T: CryptoHasherT,
 {
@@ -207,20 +718,7 @@ where
     let version = FieldElement::from_byte_slice_be(&version.to_be_bytes()).unwrap();
     let tx_prefix = FieldElement::from_byte_slice_be(tx_prefix).unwrap();
 
-    let chain_id = FieldElement::from_byte_slice_be(chain_id.as_bytes()).unwrap();
-
-    let tx_hash = <T as CryptoHasherT>::compute_hash_on_elements(&vec![
-        tx_prefix,
-        version,
-        sender_address,
-        FieldElement::ZERO,
-        calldata_hash,
-        max_fee,
-        chain_id,
-        nonce,
-    ]);
-
-    tx_hash.into()
+    vec![tx_prefix, version, sender_address, FieldElement::ZERO, calldata_hash, max_fee, chain_id, nonce]
 }
 
 /// Calculate the hash of an event.
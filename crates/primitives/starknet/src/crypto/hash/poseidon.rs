@@ -2,7 +2,7 @@
 use poseidon_hash::convert::{felts_from_u8s, u8s_from_felts};
 use poseidon_hash::hash_sw8;
 use poseidon_hash::parameters::sw8::GF;
-use starknet_crypto::FieldElement;
+use starknet_crypto::{poseidon_hash, poseidon_hash_many, FieldElement};
 
 use crate::execution::felt252_wrapper::Felt252Wrapper;
 use crate::traits::hash::{CryptoHasherT, DefaultHasher, HasherT};
@@ -32,13 +32,15 @@ impl DefaultHasher for PoseidonHasher {
     }
 }
 
-/// The poseidon CryptoHasher implementation.
+/// The poseidon CryptoHasher implementation, with Starknet's Poseidon over the felt252 field.
 impl CryptoHasherT for PoseidonHasher {
     fn hash(a: FieldElement, b: FieldElement) -> FieldElement {
-        let input = felts_from_u8s::<GF>(&[a.to_bytes_be(), b.to_bytes_be()].concat());
-        FieldElement::from_byte_slice_be(&u8s_from_felts(&poseidon_hash::hash_sw8(&input))).unwrap()
+        poseidon_hash(a, b)
     }
-    fn compute_hash_on_elements(_elements: &[FieldElement]) -> FieldElement {
-        todo!()
+
+    /// Unlike the Pedersen hash chain, Starknet hashes arrays with Poseidon in a single sponge,
+    /// see [`poseidon_hash_many`].
+    fn compute_hash_on_elements(elements: &[FieldElement]) -> FieldElement {
+        poseidon_hash_many(elements)
     }
 }
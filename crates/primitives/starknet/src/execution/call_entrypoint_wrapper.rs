@@ -5,8 +5,7 @@ use blockifier::execution::entry_point::{CallEntryPoint, CallInfo, CallType, Exe
 use blockifier::state::state_api::State;
 use blockifier::transaction::objects::AccountTransactionContext;
 use frame_support::BoundedVec;
-use sp_core::ConstU32;
-use starknet_api::api_core::{ChainId, ClassHash, ContractAddress, EntryPointSelector};
+use starknet_api::api_core::{ChainId, ClassHash, ContractAddress};
 use starknet_api::hash::StarkFelt;
 use starknet_api::transaction::Calldata;
 use starknet_api::StarknetApiError;
@@ -14,11 +13,12 @@ use starknet_api::StarknetApiError;
 use super::entrypoint_wrapper::{
     EntryPointExecutionErrorWrapper, EntryPointExecutionResultWrapper, EntryPointTypeWrapper,
 };
-use super::types::{ClassHashWrapper, ContractAddressWrapper, Felt252Wrapper};
+use super::types::{ClassHashWrapper, ContractAddressWrapper, EntryPointSelector, Felt252Wrapper};
 use crate::block::Block as StarknetBlock;
+use crate::traits::limits::{DefaultTxLimits, TxLimits};
 
 /// Max number of calldata / tx.
-pub type MaxCalldataSize = ConstU32<{ u32::MAX }>;
+pub type MaxCalldataSize = <DefaultTxLimits as TxLimits>::MaxCalldataSize;
 
 /// Representation of a Starknet Call Entry Point.
 #[derive(
@@ -32,28 +32,43 @@ pub type MaxCalldataSize = ConstU32<{ u32::MAX }>;
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CallEntryPointWrapper {
     /// The class hash
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
     pub class_hash: Option<ClassHashWrapper>,
     /// The entrypoint type
+    ///
+    /// Not serialized, like the addresses: the Starknet spec has no such transaction fields and a
+    /// [`crate::transaction::types::Transaction`] flattens its call entrypoint.
+    #[cfg_attr(feature = "std", serde(skip_serializing, default = "default_entrypoint_type"))]
     pub entrypoint_type: EntryPointTypeWrapper,
     /// The entrypoint selector
     /// An invoke transaction without an entry point selector invokes the 'execute' function.
-    pub entrypoint_selector: Option<Felt252Wrapper>,
+    #[cfg_attr(feature = "std", serde(rename = "entry_point_selector", skip_serializing_if = "Option::is_none"))]
+    pub entrypoint_selector: Option<EntryPointSelector>,
     /// The Calldata
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>,
     /// The storage address
+    #[cfg_attr(feature = "std", serde(skip_serializing, default))]
     pub storage_address: ContractAddressWrapper,
     /// The caller address
+    #[cfg_attr(feature = "std", serde(skip_serializing, default))]
     pub caller_address: ContractAddressWrapper,
 }
+
+#[cfg(feature = "std")]
+fn default_entrypoint_type() -> EntryPointTypeWrapper {
+    EntryPointTypeWrapper::External
+}
 // Regular implementation.
 impl CallEntryPointWrapper {
     /// Creates a new instance of a call entrypoint.
     pub fn new(
         class_hash: Option<ClassHashWrapper>,
         entrypoint_type: EntryPointTypeWrapper,
-        entrypoint_selector: Option<Felt252Wrapper>,
+        entrypoint_selector: Option<EntryPointSelector>,
         calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>,
         storage_address: ContractAddressWrapper,
         caller_address: ContractAddressWrapper,
@@ -102,7 +117,7 @@ impl Default for CallEntryPointWrapper {
         Self {
             class_hash: None,
             entrypoint_type: EntryPointTypeWrapper::External,
-            entrypoint_selector: Some(Felt252Wrapper::default()),
+            entrypoint_selector: Some(EntryPointSelector::default()),
             calldata: BoundedVec::default(),
             storage_address: ContractAddressWrapper::default(),
             caller_address: ContractAddressWrapper::default(),
@@ -123,9 +138,7 @@ impl TryInto<CallEntryPoint> for CallEntryPointWrapper {
         let entrypoint = CallEntryPoint {
             class_hash,
             entry_point_type: self.entrypoint_type.clone().into(),
-            entry_point_selector: EntryPointSelector(StarkFelt::new(
-                self.entrypoint_selector.unwrap_or_default().into(),
-            )?),
+            entry_point_selector: self.entrypoint_selector.unwrap_or_default().into(),
             calldata: Calldata(Arc::new(
                 self.calldata
                     .clone()
@@ -0,0 +1,91 @@
+//! Chain id.
+//!
+//! The chain id of a Starknet network is the felt whose big-endian bytes are the ascii encoding of
+//! its name, `SN_MAIN` is `0x534e5f4d41494e`. It is part of every transaction hash, so it is passed
+//! around as a felt rather than as the name it encodes.
+
+use core::str::FromStr;
+
+use starknet_ff::FieldElement;
+
+use super::types::{Felt252Wrapper, Felt252WrapperError};
+
+/// Chain id of a Starknet network.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ChainId(pub Felt252Wrapper);
+
+// The constants are built from the Montgomery representation of the chain ids, the only way to
+// build a `FieldElement` in a const context. Their value is checked against the encoded network
+// names in the tests.
+impl ChainId {
+    /// Chain id of the mainnet, `SN_MAIN`: 0x534e5f4d41494e.
+    pub const SN_MAIN: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        17696389056366564951,
+        18446744073709551615,
+        18446744073709551615,
+        502562008147966918,
+    ])));
+    /// Chain id of the goerli testnet, `SN_GOERLI`: 0x534e5f474f45524c49.
+    pub const SN_GOERLI: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        3753493103916128178,
+        18446744073709548950,
+        18446744073709551615,
+        398700013197595345,
+    ])));
+
+    /// Initializes from the ascii encoding of a network name, `b"SN_MAIN"` is [`ChainId::SN_MAIN`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The big-endian bytes of the chain id.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Felt252WrapperError`] if the bytes don't fit in a felt252.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Felt252WrapperError> {
+        Felt252Wrapper::from_bytes_be(bytes).map(Self)
+    }
+}
+
+impl FromStr for ChainId {
+    type Err = Felt252WrapperError;
+
+    /// Parses either a known network name, `SN_MAIN` or `SN_GOERLI`, or a hex felt.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "SN_MAIN" => Ok(Self::SN_MAIN),
+            "SN_GOERLI" => Ok(Self::SN_GOERLI),
+            _ => Felt252Wrapper::from_hex_be(value).map(Self),
+        }
+    }
+}
+
+impl From<Felt252Wrapper> for ChainId {
+    fn from(value: Felt252Wrapper) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ChainId> for Felt252Wrapper {
+    fn from(value: ChainId) -> Self {
+        value.0
+    }
+}
+
+impl From<ChainId> for FieldElement {
+    fn from(value: ChainId) -> Self {
+        value.0.0
+    }
+}
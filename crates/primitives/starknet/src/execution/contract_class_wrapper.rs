@@ -7,12 +7,19 @@ use frame_support::{BoundedBTreeMap, BoundedVec};
 use sp_core::ConstU32;
 use starknet_api::deprecated_contract_class::EntryPoint;
 use starknet_api::stdlib::collections::HashMap;
+#[cfg(feature = "std")]
+use starknet_core::types::{
+    CompressedLegacyContractClass, ContractClass as RPCContractClass, FieldElement, LegacyContractEntryPoint,
+    LegacyEntryPointsByType,
+};
 use thiserror_no_std::Error;
 
 use super::entrypoint_wrapper::{EntryPointTypeWrapper, EntryPointWrapper, MaxEntryPoints};
 use super::program_wrapper::ProgramWrapper;
 #[cfg(feature = "std")]
 use super::{deserialize_bounded_btreemap, serialize_bounded_btreemap};
+#[cfg(feature = "std")]
+use crate::transaction::types::RPCTransactionConversionError;
 
 /// Max number of entrypoints types (EXTERNAL/L1_HANDLER/CONSTRUCTOR)
 type MaxEntryPointsType = ConstU32<3>;
@@ -55,6 +62,66 @@ impl ContractClassWrapper {
     ) -> Self {
         Self { program, entry_points_by_type }
     }
+
+    /// Returns the number of felts of the contract class: the words of the program bytecode, and
+    /// the selector and offset of each entry point.
+    pub fn felt_count(&self) -> usize {
+        let entry_points: usize = self.entry_points_by_type.values().map(|entry_points| entry_points.len()).sum();
+        self.program.data_len() + 2 * entry_points
+    }
+}
+
+#[cfg(feature = "std")]
+impl ContractClassWrapper {
+    /// Converts the contract class to its RPC representation.
+    ///
+    /// Only legacy (Cairo 0) classes are stored, so the result is always a
+    /// [RPCContractClass::Legacy] whose program is the gzip compressed cairo-lang program json,
+    /// see [ProgramWrapper::to_cairo_lang_json]. The ABI isn't stored on chain and is left empty.
+    pub fn to_rpc_contract_class(&self) -> Result<RPCContractClass, RPCTransactionConversionError> {
+        self.to_compressed_contract_class().map(RPCContractClass::Legacy)
+    }
+
+    /// Converts the contract class to the compressed legacy class sent along declare transactions,
+    /// see [ContractClassWrapper::to_rpc_contract_class].
+    pub fn to_compressed_contract_class(&self) -> Result<CompressedLegacyContractClass, RPCTransactionConversionError> {
+        let program =
+            self.program.to_cairo_lang_json().map_err(|_| RPCTransactionConversionError::InvalidContractClass)?;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        serde_json::to_writer(&mut encoder, &program)
+            .map_err(|_| RPCTransactionConversionError::InvalidContractClass)?;
+        let program = encoder.finish().map_err(|_| RPCTransactionConversionError::InvalidContractClass)?;
+
+        let entry_points = |entry_point_type| -> Result<Vec<LegacyContractEntryPoint>, RPCTransactionConversionError> {
+            self.entry_points_by_type
+                .get(&entry_point_type)
+                .map(|entry_points| entry_points.iter().map(to_rpc_entry_point).collect())
+                .unwrap_or_else(|| Ok(Vec::new()))
+        };
+
+        Ok(CompressedLegacyContractClass {
+            program,
+            entry_points_by_type: LegacyEntryPointsByType {
+                constructor: entry_points(EntryPointTypeWrapper::Constructor)?,
+                external: entry_points(EntryPointTypeWrapper::External)?,
+                l1_handler: entry_points(EntryPointTypeWrapper::L1Handler)?,
+            },
+            abi: None,
+        })
+    }
+}
+
+/// Converts an entry point to its RPC representation, failing with
+/// [RPCTransactionConversionError::InvalidContractClass] if its selector isn't a valid felt.
+#[cfg(feature = "std")]
+fn to_rpc_entry_point(
+    entry_point: &EntryPointWrapper,
+) -> Result<LegacyContractEntryPoint, RPCTransactionConversionError> {
+    Ok(LegacyContractEntryPoint {
+        offset: entry_point.offset.try_into().map_err(|_| RPCTransactionConversionError::InvalidContractClass)?,
+        selector: FieldElement::from_bytes_be(&entry_point.selector)
+            .map_err(|_| RPCTransactionConversionError::InvalidContractClass)?,
+    })
 }
 
 /// Errors in the try_from implementation of [ContractClassWrapper]
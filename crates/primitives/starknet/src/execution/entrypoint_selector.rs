@@ -0,0 +1,110 @@
+//! Entry point selector.
+//!
+//! The selector of an entry point is the starknet keccak of its name. The selectors of the entry
+//! points called by the protocol itself are provided as constants so they never have to be hashed
+//! from their name at runtime.
+
+use starknet_api::api_core::EntryPointSelector as StarknetEntryPointSelector;
+use starknet_ff::FieldElement;
+
+use super::types::Felt252Wrapper;
+
+/// Selector of a contract entry point.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct EntryPointSelector(pub Felt252Wrapper);
+
+// The constants are built from the Montgomery representation of the selectors, the only way to
+// build a `FieldElement` in a const context. Their value is checked against the hashed entry point
+// names in the tests.
+impl EntryPointSelector {
+    /// Selector of `__execute__`:
+    /// 0x15d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad.
+    pub const EXECUTE: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        12003533864240545316,
+        425026474450283495,
+        15935222606396478900,
+        305947032915839070,
+    ])));
+    /// Selector of `__validate__`:
+    /// 0x162da33a4585851fe8d3af3c2a9c60b557814e221e0d4f30ff0b2189d9c7775.
+    pub const VALIDATE: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        12839380474732690315,
+        12285784131008321395,
+        8092503639404068326,
+        53381634366368345,
+    ])));
+    /// Selector of `__validate_declare__`:
+    /// 0x289da278a8dc833409cabfdad1581e8e7d40e42dcaed693fa4008dcdb4963b3.
+    pub const VALIDATE_DECLARE: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        340343150128810881,
+        18436580835198274529,
+        9970126352767775036,
+        508946360546229717,
+    ])));
+    /// Selector of `__validate_deploy__`:
+    /// 0x36fcbf06cd96843058359e1a75928beacfac10727dab22a3972f0af8aa92895.
+    pub const VALIDATE_DEPLOY: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        4798691400136798273,
+        12056803431508544661,
+        126277488086795526,
+        442734643552595162,
+    ])));
+    /// Selector of `constructor`:
+    /// 0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194.
+    pub const CONSTRUCTOR: Self = Self(Felt252Wrapper(FieldElement::from_mont([
+        15115658135017714733,
+        593331496434639853,
+        3549610360694334178,
+        297097713911849609,
+    ])));
+    /// Selector of `__l1_default__`, the L1 handler called when no other one matches: 0x0.
+    pub const L1_HANDLER: Self = Self(Felt252Wrapper::ZERO);
+
+    /// Returns true if this is the selector of `__execute__`.
+    pub fn is_execute(&self) -> bool {
+        *self == Self::EXECUTE
+    }
+
+    /// Returns true if this is the selector of one of the validate entry points, `__validate__`,
+    /// `__validate_declare__` or `__validate_deploy__`.
+    pub fn is_validate(&self) -> bool {
+        [Self::VALIDATE, Self::VALIDATE_DECLARE, Self::VALIDATE_DEPLOY].contains(self)
+    }
+}
+
+impl From<Felt252Wrapper> for EntryPointSelector {
+    fn from(value: Felt252Wrapper) -> Self {
+        Self(value)
+    }
+}
+
+impl From<EntryPointSelector> for Felt252Wrapper {
+    fn from(value: EntryPointSelector) -> Self {
+        value.0
+    }
+}
+
+impl From<StarknetEntryPointSelector> for EntryPointSelector {
+    fn from(value: StarknetEntryPointSelector) -> Self {
+        Self(value.0.into())
+    }
+}
+
+impl From<EntryPointSelector> for StarknetEntryPointSelector {
+    fn from(value: EntryPointSelector) -> Self {
+        Self(value.0.into())
+    }
+}
@@ -29,6 +29,7 @@ pub type EntryPointExecutionResultWrapper<T> = Result<T, EntryPointExecutionErro
     Hash,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum EntryPointTypeWrapper {
     /// Constructor.
     Constructor,
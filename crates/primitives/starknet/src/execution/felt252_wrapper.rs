@@ -15,11 +15,12 @@ use scale_info::build::Fields;
 use scale_info::{Path, Type, TypeInfo};
 use sp_core::{H256, U256};
 use starknet_api::hash::StarkFelt;
-use starknet_ff::{FieldElement, FromByteSliceError, FromStrError};
+use starknet_api::transaction::Fee;
+use starknet_ff::{FieldElement, FromStrError};
 use thiserror_no_std::Error;
 
 ///
-#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Copy)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Felt252Wrapper(pub FieldElement);
 
@@ -66,6 +67,106 @@ impl Felt252Wrapper {
         let fe = FieldElement::from_dec_str(value)?;
         Ok(Self(fe))
     }
+
+    /// Initializes from a hex string prefixed with `0x`, or from a decimal string otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A valid `0x` prefixed hex string or decimal string.
+    ///
+    /// # Errors
+    ///
+    /// See [`Felt252Wrapper::from_hex_be`] and [`Felt252Wrapper::from_dec_str`].
+    pub fn from_str_auto(value: &str) -> Result<Self, Felt252WrapperError> {
+        if value.starts_with("0x") {
+            Self::from_hex_be(value)
+        } else {
+            Self::from_dec_str(value)
+        }
+    }
+
+    /// Initializes from big-endian bytes, `[0x01, 0x02]` is `0x0102`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - At most 32 bytes, shorter inputs are padded with leading zeros.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Felt252WrapperError::InvalidLength`] if there are more than 32 bytes and
+    /// [`Felt252WrapperError::OutOfRange`] if the value overflows felt252.
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self, Felt252WrapperError> {
+        if bytes.len() > 32 {
+            return Err(Felt252WrapperError::InvalidLength);
+        }
+        let mut buf = [0_u8; 32];
+        buf[32 - bytes.len()..].copy_from_slice(bytes);
+        FieldElement::from_bytes_be(&buf).map(Self).map_err(|_| Felt252WrapperError::OutOfRange)
+    }
+
+    /// Initializes from little-endian bytes, `[0x01, 0x02]` is `0x0201`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - At most 32 bytes, shorter inputs are padded with trailing zeros.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Felt252WrapperError::InvalidLength`] if there are more than 32 bytes and
+    /// [`Felt252WrapperError::OutOfRange`] if the value overflows felt252.
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Self, Felt252WrapperError> {
+        if bytes.len() > 32 {
+            return Err(Felt252WrapperError::InvalidLength);
+        }
+        let mut buf = [0_u8; 32];
+        buf[32 - bytes.len()..].copy_from_slice(bytes);
+        buf[32 - bytes.len()..].reverse();
+        Self::from_bytes_be(&buf)
+    }
+
+    /// Returns the 32 bytes big-endian encoding, padded with leading zeros.
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        self.0.to_bytes_be()
+    }
+
+    /// Returns the 32 bytes little-endian encoding, padded with trailing zeros.
+    pub fn to_bytes_le(&self) -> [u8; 32] {
+        let mut bytes = self.to_bytes_be();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Initializes from an Ethereum address, the 20 bytes are the low bytes of the felt.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The big-endian bytes of the address.
+    pub fn from_eth_address(address: [u8; 20]) -> Self {
+        let mut buf = [0_u8; 32];
+        buf[12..].copy_from_slice(&address);
+        Self(FieldElement::from_bytes_be(&buf).expect("20 bytes always fit in a felt252"))
+    }
+
+    /// Returns the Ethereum address encoded in the felt, its 20 low bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Felt252WrapperError::ValueTooLarge`] if the felt doesn't fit in 20 bytes.
+    pub fn try_into_eth_address(&self) -> Result<[u8; 20], Felt252WrapperError> {
+        let bytes = self.to_bytes_be();
+        if bytes[..12].iter().any(|&byte| byte != 0) {
+            return Err(Felt252WrapperError::ValueTooLarge);
+        }
+        let mut address = [0_u8; 20];
+        address.copy_from_slice(&bytes[12..]);
+        Ok(address)
+    }
+
+    /// Returns the felt plus one, or `None` for [`Felt252Wrapper::MAX`] as the addition would wrap
+    /// around the field modulus to zero.
+    pub fn checked_increment(&self) -> Option<Self> {
+        self.checked_add(Self::ONE)
+    }
 }
 
 impl Default for Felt252Wrapper {
@@ -77,7 +178,7 @@ impl Default for Felt252Wrapper {
 /// Array of bytes from [`Felt252Wrapper`].
 impl From<Felt252Wrapper> for [u8; 32] {
     fn from(felt: Felt252Wrapper) -> Self {
-        felt.0.to_bytes_be()
+        felt.to_bytes_be()
     }
 }
 
@@ -87,10 +188,7 @@ impl TryFrom<&[u8; 32]> for Felt252Wrapper {
     type Error = Felt252WrapperError;
 
     fn try_from(bytes: &[u8; 32]) -> Result<Self, Felt252WrapperError> {
-        match FieldElement::from_bytes_be(bytes) {
-            Ok(ff) => Ok(Self(ff)),
-            Err(_) => Err(Felt252WrapperError::FromArrayError),
-        }
+        Self::from_bytes_be(bytes)
     }
 }
 
@@ -100,13 +198,7 @@ impl TryFrom<&[u8]> for Felt252Wrapper {
     type Error = Felt252WrapperError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Felt252WrapperError> {
-        match FieldElement::from_byte_slice_be(bytes) {
-            Ok(ff) => Ok(Self(ff)),
-            Err(e) => match e {
-                FromByteSliceError::InvalidLength => Err(Felt252WrapperError::InvalidLength),
-                FromByteSliceError::OutOfRange => Err(Felt252WrapperError::OutOfRange),
-            },
-        }
+        Self::from_bytes_be(bytes)
     }
 }
 
@@ -134,10 +226,27 @@ impl TryFrom<Felt252Wrapper> for u64 {
     }
 }
 
+/// [`Fee`] to [`Felt252Wrapper`].
+impl From<Fee> for Felt252Wrapper {
+    fn from(value: Fee) -> Self {
+        Self::from(value.0)
+    }
+}
+
+/// [`Felt252Wrapper`] to [`Fee`].
+/// Overflow may occur and return [`Felt252WrapperError::ValueTooLarge`].
+impl TryFrom<Felt252Wrapper> for Fee {
+    type Error = Felt252WrapperError;
+
+    fn try_from(value: Felt252Wrapper) -> Result<Self, Self::Error> {
+        u128::try_from(U256::from(value)).map(Fee).map_err(|_| Felt252WrapperError::ValueTooLarge)
+    }
+}
+
 /// [`Felt252Wrapper`] to [`U256`].
 impl From<Felt252Wrapper> for U256 {
     fn from(felt: Felt252Wrapper) -> Self {
-        U256::from_big_endian(&felt.0.to_bytes_be())
+        U256::from_big_endian(&felt.to_bytes_be())
     }
 }
 
@@ -219,7 +328,7 @@ impl From<Felt252Wrapper> for StarkFelt {
 /// SCALE trait.
 impl Encode for Felt252Wrapper {
     fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
-        dest.write(&self.0.to_bytes_be());
+        dest.write(&self.to_bytes_be());
     }
 }
 
@@ -259,6 +368,22 @@ impl TypeInfo for Felt252Wrapper {
     }
 }
 
+/// Fuzzing trait.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Felt252Wrapper {
+    // Clearing the 5 high bits keeps the value below 2^251, hence below the field modulus, so that
+    // every input yields a felt instead of being rejected.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut bytes: [u8; 32] = u.arbitrary()?;
+        bytes[0] &= 0x07;
+        Self::from_bytes_be(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (32, Some(32))
+    }
+}
+
 #[derive(Debug, PartialEq, Error)]
 /// Error related to Felt252Wrapper.
 pub enum Felt252WrapperError {
@@ -268,7 +393,7 @@ pub enum Felt252WrapperError {
     /// Provided byte array has incorrect lengths.
     #[error("invalid length")]
     InvalidLength,
-    /// Invalid character in hex string.
+    /// Invalid character in hex or decimal string.
     #[error("invalid character")]
     InvalidCharacter,
     /// Value is too large for FieldElement (felt252).
@@ -347,6 +472,27 @@ mod felt252_wrapper_tests {
         Felt252Wrapper::from_dec_str("1991991").unwrap();
     }
 
+    #[test]
+    fn from_str_auto_detects_the_base() {
+        assert_eq!(Felt252Wrapper::from_str_auto("1991991"), Ok(Felt252Wrapper::from(1991991_u64)));
+        assert_eq!(Felt252Wrapper::from_str_auto("0x1e6537"), Ok(Felt252Wrapper::from(1991991_u64)));
+        assert_eq!(Felt252Wrapper::from_str_auto("12a"), Err(Felt252WrapperError::InvalidCharacter));
+    }
+
+    #[test]
+    fn from_str_auto_rejects_values_over_the_modulus() {
+        // The largest felt and the field modulus, 2^251 + 17 * 2^192 + 1.
+        let max = "3618502788666131213697322783095070105623107215331596699973092056135872020480";
+        let modulus = "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+
+        assert_eq!(Felt252Wrapper::from_str_auto(max), Ok(Felt252Wrapper::MAX));
+        assert_eq!(Felt252Wrapper::from_str_auto(modulus), Err(Felt252WrapperError::OutOfRange));
+        assert_eq!(
+            Felt252Wrapper::from_str_auto("0x800000000000011000000000000000000000000000000000000000000000001"),
+            Err(Felt252WrapperError::OutOfRange)
+        );
+    }
+
     #[test]
     fn felt252_from_fieldelement_twoway() {
         let fe = FieldElement::TWO;
@@ -425,4 +571,103 @@ mod felt252_wrapper_tests {
         let felt_u64 = Felt252Wrapper::from(4_294_967_296u64);
         assert_eq!(TryInto::<u64>::try_into(felt_u64).unwrap(), 4_294_967_296u64);
     }
+
+    #[test]
+    fn from_bytes_be_and_le_differ() {
+        let felt = Felt252Wrapper::from(0x0102_u64);
+
+        assert_eq!(Felt252Wrapper::from_bytes_be(&[0x01, 0x02]), Ok(felt));
+        assert_eq!(Felt252Wrapper::from_bytes_le(&[0x02, 0x01]), Ok(felt));
+        assert_eq!(Felt252Wrapper::from_bytes_le(&[0x01, 0x02]), Ok(Felt252Wrapper::from(0x0201_u64)));
+    }
+
+    #[test]
+    fn to_bytes_be_and_le_differ() {
+        let felt = Felt252Wrapper::from(0x0102_u64);
+
+        let be = felt.to_bytes_be();
+        assert_eq!(be[30..], [0x01, 0x02]);
+        assert_eq!(be[..30], [0; 30]);
+
+        let le = felt.to_bytes_le();
+        assert_eq!(le[..2], [0x02, 0x01]);
+        assert_eq!(le[2..], [0; 30]);
+
+        assert_eq!(Felt252Wrapper::from_bytes_be(&be), Ok(felt));
+        assert_eq!(Felt252Wrapper::from_bytes_le(&le), Ok(felt));
+    }
+
+    #[test]
+    fn eth_address_round_trip() {
+        let address: [u8; 20] = [
+            0xc6, 0x62, 0xc4, 0x10, 0xc0, 0xec, 0xf7, 0x47, 0x54, 0x3f, 0x5b, 0xa9, 0x06, 0x60, 0xf6, 0xab, 0xeb, 0xd9,
+            0xc8, 0xc4,
+        ];
+
+        let felt = Felt252Wrapper::from_eth_address(address);
+        assert_eq!(felt, Felt252Wrapper::from_hex_be("0xc662c410c0ecf747543f5ba90660f6abebd9c8c4").unwrap());
+        assert_eq!(felt.try_into_eth_address(), Ok(address));
+    }
+
+    #[test]
+    fn try_into_eth_address_rejects_values_over_20_bytes() {
+        let felt = Felt252Wrapper::from_hex_be("0x01c662c410c0ecf747543f5ba90660f6abebd9c8c4").unwrap();
+        assert_eq!(felt.try_into_eth_address(), Err(Felt252WrapperError::ValueTooLarge));
+        assert_eq!(Felt252Wrapper::MAX.try_into_eth_address(), Err(Felt252WrapperError::ValueTooLarge));
+    }
+
+    #[test]
+    fn fee_round_trip() {
+        let fee = Fee(1_000_000_000_000_000);
+
+        let felt = Felt252Wrapper::from(fee);
+        assert_eq!(felt, Felt252Wrapper::from(1_000_000_000_000_000_u128));
+        assert_eq!(Fee::try_from(felt), Ok(fee));
+        assert_eq!(Fee::try_from(Felt252Wrapper::from(u128::MAX)), Ok(Fee(u128::MAX)));
+    }
+
+    #[test]
+    fn try_into_fee_rejects_values_over_u128() {
+        let felt = Felt252Wrapper::try_from(U256::from(u128::MAX) + 1).unwrap();
+        assert_eq!(Fee::try_from(felt), Err(Felt252WrapperError::ValueTooLarge));
+        assert_eq!(Fee::try_from(Felt252Wrapper::MAX), Err(Felt252WrapperError::ValueTooLarge));
+    }
+
+    #[test]
+    fn from_bytes_rejects_invalid_length_and_overflow() {
+        assert_eq!(Felt252Wrapper::from_bytes_be(&[0; 33]), Err(Felt252WrapperError::InvalidLength));
+        assert_eq!(Felt252Wrapper::from_bytes_le(&[0; 33]), Err(Felt252WrapperError::InvalidLength));
+        assert_eq!(Felt252Wrapper::from_bytes_be(&[0xff; 32]), Err(Felt252WrapperError::OutOfRange));
+        assert_eq!(Felt252Wrapper::from_bytes_le(&[0xff; 32]), Err(Felt252WrapperError::OutOfRange));
+    }
+
+    #[test]
+    fn existing_conversions_are_big_endian() {
+        let felt = Felt252Wrapper::from(0x0102_u64);
+
+        assert_eq!(<[u8; 32]>::from(felt), felt.to_bytes_be());
+        assert_eq!(Felt252Wrapper::try_from(&[0x01_u8, 0x02][..]), Ok(felt));
+        assert_eq!(Felt252Wrapper::try_from(&felt.to_bytes_be()), Ok(felt));
+    }
+
+    #[test]
+    fn checked_increment_adds_one() {
+        assert_eq!(Felt252Wrapper::ZERO.checked_increment(), Some(Felt252Wrapper::ONE));
+        assert_eq!(Felt252Wrapper::from(41_u64).checked_increment(), Some(Felt252Wrapper::from(42_u64)));
+    }
+
+    #[test]
+    fn checked_increment_fails_at_the_field_modulus() {
+        let before_max = Felt252Wrapper(Felt252Wrapper::MAX.0 - FieldElement::ONE);
+
+        assert_eq!(before_max.checked_increment(), Some(Felt252Wrapper::MAX));
+        assert_eq!(Felt252Wrapper::MAX.checked_increment(), None);
+    }
+
+    #[test]
+    fn felts_are_ordered_by_value() {
+        assert!(Felt252Wrapper::ONE < Felt252Wrapper::TWO);
+        assert!(Felt252Wrapper::from(256_u64) > Felt252Wrapper::from(255_u64));
+        assert!(Felt252Wrapper::MAX > Felt252Wrapper::from(u128::MAX));
+    }
 }
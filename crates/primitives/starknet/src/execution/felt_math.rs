@@ -0,0 +1,106 @@
+//! Overflow checked arithmetic on [Felt252Wrapper].
+//!
+//! The operations are done in the field, they fail when the result of the same operation on
+//! integers doesn't fit below the field modulus, i.e. when the field operation wraps around.
+
+use sp_core::{U256, U512};
+
+use super::felt252_wrapper::{Felt252Wrapper, Felt252WrapperError};
+
+impl Felt252Wrapper {
+    /// Returns `self + rhs`, or `None` if the sum is greater than [`Felt252Wrapper::MAX`].
+    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        let sum = Self(self.0 + rhs.0);
+        // Both operands are below the modulus, so the sum wraps around it iff it ends up below
+        // one of them.
+        (U256::from(sum) >= U256::from(*self)).then_some(sum)
+    }
+
+    /// Returns `self - rhs`, or `None` if `rhs` is greater than `self`.
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        (U256::from(*self) >= U256::from(rhs)).then(|| Self(self.0 - rhs.0))
+    }
+
+    /// Returns `self * rhs`, or `None` if the product is greater than [`Felt252Wrapper::MAX`].
+    pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        let product = U256::from(*self).full_mul(U256::from(rhs));
+        (product <= U512::from(U256::from(Self::MAX))).then(|| Self(self.0 * rhs.0))
+    }
+
+    /// Returns `self + rhs`, see [`Felt252Wrapper::checked_add`].
+    ///
+    /// # Errors
+    ///
+    /// [`Felt252WrapperError::OutOfRange`] if the sum is greater than [`Felt252Wrapper::MAX`].
+    pub fn add_or_err(&self, rhs: Self) -> Result<Self, Felt252WrapperError> {
+        self.checked_add(rhs).ok_or(Felt252WrapperError::OutOfRange)
+    }
+
+    /// Returns `self - rhs`, see [`Felt252Wrapper::checked_sub`].
+    ///
+    /// # Errors
+    ///
+    /// [`Felt252WrapperError::OutOfRange`] if `rhs` is greater than `self`.
+    pub fn sub_or_err(&self, rhs: Self) -> Result<Self, Felt252WrapperError> {
+        self.checked_sub(rhs).ok_or(Felt252WrapperError::OutOfRange)
+    }
+
+    /// Returns `self * rhs`, see [`Felt252Wrapper::checked_mul`].
+    ///
+    /// # Errors
+    ///
+    /// [`Felt252WrapperError::OutOfRange`] if the product is greater than [`Felt252Wrapper::MAX`].
+    pub fn mul_or_err(&self, rhs: Self) -> Result<Self, Felt252WrapperError> {
+        self.checked_mul(rhs).ok_or(Felt252WrapperError::OutOfRange)
+    }
+}
+
+#[cfg(test)]
+mod felt_math_tests {
+    use starknet_ff::FieldElement;
+
+    use super::*;
+
+    fn before_max() -> Felt252Wrapper {
+        Felt252Wrapper(Felt252Wrapper::MAX.0 - FieldElement::ONE)
+    }
+
+    #[test]
+    fn checked_add_fails_past_the_field_modulus() {
+        assert_eq!(Felt252Wrapper::TWO.checked_add(Felt252Wrapper::THREE), Some(Felt252Wrapper::from(5_u64)));
+        assert_eq!(before_max().checked_add(Felt252Wrapper::ONE), Some(Felt252Wrapper::MAX));
+        assert_eq!(Felt252Wrapper::MAX.checked_add(Felt252Wrapper::ZERO), Some(Felt252Wrapper::MAX));
+        assert_eq!(Felt252Wrapper::MAX.checked_add(Felt252Wrapper::ONE), None);
+        assert_eq!(Felt252Wrapper::MAX.checked_add(Felt252Wrapper::MAX), None);
+        assert_eq!(Felt252Wrapper::MAX.add_or_err(Felt252Wrapper::ONE), Err(Felt252WrapperError::OutOfRange));
+    }
+
+    #[test]
+    fn checked_sub_fails_below_zero() {
+        assert_eq!(Felt252Wrapper::THREE.checked_sub(Felt252Wrapper::TWO), Some(Felt252Wrapper::ONE));
+        assert_eq!(Felt252Wrapper::MAX.checked_sub(Felt252Wrapper::MAX), Some(Felt252Wrapper::ZERO));
+        assert_eq!(Felt252Wrapper::MAX.checked_sub(Felt252Wrapper::ONE), Some(before_max()));
+        assert_eq!(Felt252Wrapper::ZERO.checked_sub(Felt252Wrapper::ONE), None);
+        assert_eq!(before_max().checked_sub(Felt252Wrapper::MAX), None);
+        assert_eq!(Felt252Wrapper::ZERO.sub_or_err(Felt252Wrapper::ONE), Err(Felt252WrapperError::OutOfRange));
+    }
+
+    #[test]
+    fn checked_mul_fails_past_the_field_modulus() {
+        assert_eq!(Felt252Wrapper::TWO.checked_mul(Felt252Wrapper::THREE), Some(Felt252Wrapper::from(6_u64)));
+        assert_eq!(Felt252Wrapper::MAX.checked_mul(Felt252Wrapper::ONE), Some(Felt252Wrapper::MAX));
+        assert_eq!(Felt252Wrapper::MAX.checked_mul(Felt252Wrapper::ZERO), Some(Felt252Wrapper::ZERO));
+        assert_eq!(Felt252Wrapper::MAX.checked_mul(Felt252Wrapper::TWO), None);
+        // 2^126 * 2^126 = 2^252, above the modulus 2^251 + 17 * 2^192 + 1.
+        let two_pow_126 = Felt252Wrapper::from(1_u128 << 126);
+        assert_eq!(two_pow_126.checked_mul(two_pow_126), None);
+        assert_eq!(
+            Felt252Wrapper::from(1_u128 << 125).checked_mul(two_pow_126),
+            Some(
+                Felt252Wrapper::from_hex_be("0x800000000000000000000000000000000000000000000000000000000000000")
+                    .unwrap()
+            )
+        );
+        assert_eq!(Felt252Wrapper::MAX.mul_or_err(Felt252Wrapper::TWO), Err(Felt252WrapperError::OutOfRange));
+    }
+}
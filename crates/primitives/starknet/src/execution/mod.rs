@@ -11,12 +11,18 @@ use sp_core::Get;
 
 /// Call Entrypoint Wrapper related types
 pub mod call_entrypoint_wrapper;
+/// Chain id related types
+pub mod chain_id;
 /// Contract Class Wrapper related types
 pub mod contract_class_wrapper;
+/// Entrypoint selector related types
+pub mod entrypoint_selector;
 /// Entrypoint Wrapper related types
 pub mod entrypoint_wrapper;
 /// Felt252Wrapper type
 pub mod felt252_wrapper;
+/// Overflow checked Felt252Wrapper arithmetic
+pub mod felt_math;
 /// Program Wrapper related types
 pub mod program_wrapper;
 
@@ -99,7 +105,9 @@ pub mod types {
     /// Wrapper type for class hash field.
     pub type ClassHashWrapper = Felt252Wrapper;
     pub use super::call_entrypoint_wrapper::*;
+    pub use super::chain_id::*;
     pub use super::contract_class_wrapper::*;
+    pub use super::entrypoint_selector::*;
     pub use super::entrypoint_wrapper::*;
     pub use super::felt252_wrapper::*;
     pub use super::program_wrapper::*;
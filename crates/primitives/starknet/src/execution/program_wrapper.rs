@@ -93,14 +93,13 @@ impl From<MaybeRelocatableWrapper> for MaybeRelocatable {
     Constructor,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "std", serde(into = "String"))]
+#[cfg_attr(feature = "std", serde(try_from = "String", into = "String"))]
 struct StringWrapper(BoundedVec<u8, MaxStringLength>);
 
-impl From<String> for StringWrapper {
-    /// WARNING This function can panic if the string is over 2**32-1 bytes but in our case it can
-    /// never happen as the cairo compiler cannot deal with such big strings.
-    fn from(value: String) -> Self {
-        Self(BoundedVec::try_from(value.as_bytes().to_vec()).unwrap())
+impl TryFrom<String> for StringWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        BoundedVec::try_from(value.into_bytes()).map(Self).map_err(|_| VecConversionError)
     }
 }
 impl From<StringWrapper> for String {
@@ -133,6 +132,256 @@ pub struct ProgramWrapper {
     reference_manager: ReferenceManagerWrapper,
 }
 
+impl ProgramWrapper {
+    /// Returns the number of words of the program bytecode.
+    pub fn data_len(&self) -> usize {
+        self.shared_program_data.data.len()
+    }
+}
+
+/// Prime of the field the cairo-lang programs are compiled for.
+#[cfg(feature = "std")]
+const CAIRO_PRIME: &str = "0x800000000000011000000000000000000000000000000000000000000000001";
+
+#[cfg(feature = "std")]
+impl ProgramWrapper {
+    /// Serializes the program to the cairo-lang program json, the format of compiled programs
+    /// that [Program::from_bytes] and the starknet spec (compressed legacy programs) expect.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [ProgramConversionError::RelocatableData] if the bytecode contains a relocatable
+    /// value, which compiled programs never do.
+    pub fn to_cairo_lang_json(&self) -> Result<serde_json::Value, ProgramConversionError> {
+        use serde_json::{json, Map, Value};
+
+        let program = &self.shared_program_data;
+        let data = program
+            .data
+            .iter()
+            .map(|word| match word {
+                MaybeRelocatableWrapper::Int(felt) => Ok(Value::String(format!("{:#x}", felt.0))),
+                MaybeRelocatableWrapper::RelocatableValue { .. } => Err(ProgramConversionError::RelocatableData),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let hints = program
+            .hints
+            .iter()
+            .map(|(pc, hints)| (pc.to_string(), hints.iter().map(HintParamsWrapper::to_json).collect()))
+            .collect::<Map<_, _>>();
+        let identifiers = program
+            .identifiers
+            .iter()
+            .map(|(name, identifier)| (String::from(name.clone()), identifier.to_json()))
+            .collect::<Map<_, _>>();
+        let debug_info = program.instruction_locations.as_ref().map(|locations| {
+            let locations =
+                locations.iter().map(|(pc, location)| (pc.to_string(), location.to_json())).collect::<Map<_, _>>();
+            json!({ "instruction_locations": locations })
+        });
+
+        Ok(json!({
+            "attributes": program.error_message_attributes.iter().map(AttributeWrapper::to_json).collect::<Vec<_>>(),
+            "builtins": program.builtins.iter().map(BuiltinNameWrapper::name).collect::<Vec<_>>(),
+            "data": data,
+            "debug_info": debug_info,
+            "hints": hints,
+            "identifiers": identifiers,
+            "main_scope": "__main__",
+            "prime": CAIRO_PRIME,
+            "reference_manager": {
+                "references": self.reference_manager.references.iter().map(ReferenceWrapper::to_json).collect::<Vec<_>>(),
+            },
+        }))
+    }
+}
+
+// Pieces of the cairo-lang program json, see [ProgramWrapper::to_cairo_lang_json].
+#[cfg(feature = "std")]
+impl BuiltinNameWrapper {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::output => "output",
+            Self::range_check => "range_check",
+            Self::pedersen => "pedersen",
+            Self::ecdsa => "ecdsa",
+            Self::keccak => "keccak",
+            Self::bitwise => "bitwise",
+            Self::ec_op => "ec_op",
+            Self::poseidon => "poseidon",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl HintParamsWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "accessible_scopes": self.accessible_scopes.iter().cloned().map(String::from).collect::<Vec<_>>(),
+            "code": String::from(self.code.clone()),
+            "flow_tracking_data": self.flow_tracking_data.to_json(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl FlowTrackingDataWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        let reference_ids = self
+            .reference_ids
+            .iter()
+            .map(|(name, id)| (String::from(name.clone()), serde_json::Value::from(*id as u64)))
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::json!({ "ap_tracking": self.ap_tracking.to_json(), "reference_ids": reference_ids })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ApTrackingWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "group": self.group as u64, "offset": self.offset as u64 })
+    }
+}
+
+#[cfg(feature = "std")]
+impl AttributeWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "accessible_scopes": [],
+            "end_pc": self.end_pc as u64,
+            "flow_tracking_data": self.flow_tracking_data.as_ref().map(FlowTrackingDataWrapper::to_json),
+            "name": String::from(self.name.clone()),
+            "start_pc": self.start_pc as u64,
+            "value": String::from(self.value.clone()),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl IdentifierWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        let mut identifier = serde_json::Map::new();
+        if let Some(pc) = self.pc {
+            identifier.insert("pc".into(), (pc as u64).into());
+        }
+        if let Some(type_) = &self.type_ {
+            identifier.insert("type".into(), String::from(type_.clone()).into());
+        }
+        if let Some(value) = &self.value {
+            // Constants are json numbers which can be larger than 64 bits, a decimal string is
+            // always a valid json number.
+            identifier.insert("value".into(), serde_json::from_str(&value.0.to_string()).unwrap_or_default());
+        }
+        if let Some(full_name) = &self.full_name {
+            identifier.insert("full_name".into(), String::from(full_name.clone()).into());
+        }
+        if let Some(members) = &self.members {
+            let members = members
+                .iter()
+                .map(|(name, member)| {
+                    let member = serde_json::json!({
+                        "cairo_type": String::from(member.cairo_type.clone()),
+                        "offset": member.offset as u64,
+                    });
+                    (String::from(name.clone()), member)
+                })
+                .collect::<serde_json::Map<_, _>>();
+            identifier.insert("members".into(), members.into());
+        }
+        if let Some(cairo_type) = &self.cairo_type {
+            identifier.insert("cairo_type".into(), String::from(cairo_type.clone()).into());
+        }
+        identifier.into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl InstructionLocationWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        let hints = self
+            .hints
+            .iter()
+            .map(|hint| {
+                serde_json::json!({
+                    "location": hint.location.to_json(),
+                    "n_prefix_newlines": hint.n_prefix_newlines,
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({ "hints": hints, "inst": self.inst.to_json() })
+    }
+}
+
+#[cfg(feature = "std")]
+impl LocationWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        let mut location = serde_json::json!({
+            "end_col": self.end_col,
+            "end_line": self.end_line,
+            "input_file": { "filename": String::from(self.input_file.clone()) },
+            "start_col": self.start_col,
+            "start_line": self.start_line,
+        });
+        if let Some((parent, message)) = &self.parent_location {
+            location["parent_location"] = serde_json::json!([parent.to_json(), String::from(message.clone())]);
+        }
+        location
+    }
+}
+
+#[cfg(feature = "std")]
+impl ReferenceWrapper {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "ap_tracking_data": self.ap_tracking_data.to_json(),
+            "pc": self.pc.map(|pc| pc as u64),
+            "value": self.value_address.to_cairo_expression(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ValueAddressWrapper {
+    /// Formats the address the way the cairo compiler does, e.g. `[cast([fp + (-4)] + 1, felt*)]`.
+    fn to_cairo_expression(&self) -> String {
+        let mut address = self.offset1.to_cairo_expression();
+        if self.offset2 != OffsetValueWrapper::Value(0) {
+            address = format!("{address} + {}", self.offset2.to_cairo_expression());
+        }
+        let value_type = String::from(self.value_type.clone());
+        // The dereferenced value is the pointee of the casted address.
+        if self.dereference {
+            format!("[cast({address}, {value_type}*)]")
+        } else {
+            format!("cast({address}, {value_type})")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl OffsetValueWrapper {
+    fn to_cairo_expression(&self) -> String {
+        let signed = |value: i32| if value < 0 { format!("({value})") } else { value.to_string() };
+        match self {
+            Self::Immediate(value) => value.0.to_string(),
+            Self::Value(value) => signed(*value),
+            Self::Reference(register, offset, dereference) => {
+                let register = match register {
+                    RegisterWrapper::AP => "ap",
+                    RegisterWrapper::FP => "fp",
+                };
+                let address =
+                    if *offset == 0 { register.to_string() } else { format!("{register} + {}", signed(*offset)) };
+                if *dereference {
+                    format!("[{address}]")
+                } else {
+                    address
+                }
+            }
+        }
+    }
+}
+
 #[derive(
     Clone,
     Debug,
@@ -182,6 +431,8 @@ pub enum ProgramConversionError {
     VecConversion(VecConversionError),
     /// Failed to create a [Program]
     PogramCreationError,
+    /// The program bytecode contains a relocatable value
+    RelocatableData,
 }
 
 impl From<HashMapConversionError> for ProgramConversionError {
@@ -533,7 +784,7 @@ impl TryFrom<HintParams> for HintParamsWrapper {
     type Error = VecConversionError;
     fn try_from(value: HintParams) -> Result<Self, Self::Error> {
         Ok(Self {
-            code: value.code.into(),
+            code: value.code.try_into()?,
             accessible_scopes: VecWrapper::<String>(value.accessible_scopes).try_into()?,
             flow_tracking_data: value.flow_tracking_data.into(),
         })
@@ -640,18 +891,10 @@ pub struct InstructionLocationWrapper {
     hints: BoundedVec<HintLocationWrapper, MaxHintSize>,
 }
 
-impl From<InstructionLocation> for InstructionLocationWrapper {
-    fn from(value: InstructionLocation) -> Self {
-        Self {
-            inst: value.inst.into(),
-            hints: value
-                .hints
-                .iter()
-                .map(|hint| hint.clone().into())
-                .collect::<Vec<HintLocationWrapper>>()
-                .try_into()
-                .unwrap(),
-        }
+impl TryFrom<InstructionLocation> for InstructionLocationWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: InstructionLocation) -> Result<Self, Self::Error> {
+        Ok(Self { inst: value.inst.try_into()?, hints: VecWrapper(value.hints).try_into()? })
     }
 }
 
@@ -686,18 +929,22 @@ pub struct LocationWrapper {
     start_col: u32,
 }
 
-impl From<Location> for LocationWrapper {
-    fn from(value: Location) -> Self {
+impl TryFrom<Location> for LocationWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: Location) -> Result<Self, Self::Error> {
         let InputFile { filename } = value.input_file;
-        let parent_loc = value.parent_location.map(|(loc, name)| (Box::from(LocationWrapper::from(*loc)), name.into()));
-        Self {
+        let parent_loc = match value.parent_location {
+            Some((loc, name)) => Some((Box::new(LocationWrapper::try_from(*loc)?), name.try_into()?)),
+            None => None,
+        };
+        Ok(Self {
             end_line: value.end_line,
             end_col: value.end_col,
-            input_file: filename.into(),
+            input_file: filename.try_into()?,
             parent_location: parent_loc,
             start_line: value.start_line,
             start_col: value.start_col,
-        }
+        })
     }
 }
 
@@ -734,9 +981,10 @@ pub struct HintLocationWrapper {
     n_prefix_newlines: u32,
 }
 
-impl From<HintLocation> for HintLocationWrapper {
-    fn from(value: HintLocation) -> Self {
-        Self { location: value.location.into(), n_prefix_newlines: value.n_prefix_newlines }
+impl TryFrom<HintLocation> for HintLocationWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: HintLocation) -> Result<Self, Self::Error> {
+        Ok(Self { location: value.location.try_into()?, n_prefix_newlines: value.n_prefix_newlines })
     }
 }
 impl From<HintLocationWrapper> for HintLocation {
@@ -766,15 +1014,16 @@ struct AttributeWrapper {
     flow_tracking_data: Option<FlowTrackingDataWrapper>,
 }
 
-impl From<Attribute> for AttributeWrapper {
-    fn from(value: Attribute) -> Self {
-        Self {
-            name: value.name.into(),
+impl TryFrom<Attribute> for AttributeWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: Attribute) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.try_into()?,
             start_pc: value.start_pc as u128,
             end_pc: value.end_pc as u128,
-            value: value.value.into(),
+            value: value.value.try_into()?,
             flow_tracking_data: value.flow_tracking_data.map(|flow| flow.into()),
-        }
+        })
     }
 }
 
@@ -821,17 +1070,17 @@ struct IdentifierWrapper {
     members: Option<BoundedBTreeMap<StringWrapper, MemberWrapper, MaxMemberLength>>,
     cairo_type: Option<StringWrapper>,
 }
-impl From<Identifier> for IdentifierWrapper {
-    fn from(value: Identifier) -> Self {
-        Self {
+impl TryFrom<Identifier> for IdentifierWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: Identifier) -> Result<Self, Self::Error> {
+        Ok(Self {
             pc: value.pc.map(|v| v as u128),
-            type_: value.type_.map(|v| v.into()),
+            type_: value.type_.map(StringWrapper::try_from).transpose()?,
             value: value.value.map(|v| v.into()),
-            full_name: value.full_name.map(|v| v.into()),
-            // Nothing should have more than 2**32-1 members so it shouldn't panic.
-            members: value.members.map(|v| HashMapWrapper(v).try_into().unwrap()),
-            cairo_type: value.cairo_type.map(|v| v.into()),
-        }
+            full_name: value.full_name.map(StringWrapper::try_from).transpose()?,
+            members: value.members.map(|v| HashMapWrapper(v).try_into().map_err(|_| VecConversionError)).transpose()?,
+            cairo_type: value.cairo_type.map(StringWrapper::try_from).transpose()?,
+        })
     }
 }
 impl From<IdentifierWrapper> for Identifier {
@@ -869,9 +1118,10 @@ struct MemberWrapper {
     offset: u128,
 }
 
-impl From<Member> for MemberWrapper {
-    fn from(value: Member) -> Self {
-        Self { cairo_type: value.cairo_type.into(), offset: value.offset as u128 }
+impl TryFrom<Member> for MemberWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: Member) -> Result<Self, Self::Error> {
+        Ok(Self { cairo_type: value.cairo_type.try_into()?, offset: value.offset as u128 })
     }
 }
 impl From<MemberWrapper> for Member {
@@ -903,13 +1153,14 @@ struct ReferenceWrapper {
     value_address: ValueAddressWrapper,
 }
 
-impl From<Reference> for ReferenceWrapper {
-    fn from(value: Reference) -> Self {
-        Self {
+impl TryFrom<Reference> for ReferenceWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: Reference) -> Result<Self, Self::Error> {
+        Ok(Self {
             ap_tracking_data: value.ap_tracking_data.into(),
             pc: value.pc.map(|v| v as u128),
-            value_address: value.value_address.into(),
-        }
+            value_address: value.value_address.try_into()?,
+        })
     }
 }
 impl From<ReferenceWrapper> for Reference {
@@ -991,14 +1242,15 @@ pub struct ValueAddressWrapper {
     value_type: StringWrapper,
 }
 
-impl From<ValueAddress> for ValueAddressWrapper {
-    fn from(value: ValueAddress) -> Self {
-        Self {
+impl TryFrom<ValueAddress> for ValueAddressWrapper {
+    type Error = VecConversionError;
+    fn try_from(value: ValueAddress) -> Result<Self, Self::Error> {
+        Ok(Self {
             offset1: value.offset1.into(),
             offset2: value.offset2.into(),
             dereference: value.dereference,
-            value_type: value.value_type.into(),
-        }
+            value_type: value.value_type.try_into()?,
+        })
     }
 }
 
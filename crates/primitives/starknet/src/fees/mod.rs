@@ -18,6 +18,7 @@ use starknet_api::transaction::{Calldata, Fee};
 
 use super::state::StateChanges;
 use crate::alloc::string::ToString;
+use crate::execution::types::ContractAddressWrapper;
 use crate::transaction::types::{TransactionExecutionErrorWrapper, TxType};
 
 /// Number of storage updates for the fee transfer tx.
@@ -25,6 +26,27 @@ pub const FEE_TRANSFER_N_STORAGE_CHANGES: u8 = 2; // Sender and sequencer balanc
 /// Number of storage updates to actually charge for the fee transfer tx.
 pub const FEE_TRANSFER_N_STORAGE_CHANGES_TO_CHARGE: u8 = FEE_TRANSFER_N_STORAGE_CHANGES - 1; // Exclude the sequencer balance update, since it's charged once throughout the batch.
 
+/// Addresses of the ERC-20 contracts fees are paid in.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeTokenConfig {
+    /// ETH token, for the fees of v0 to v2 transactions.
+    pub eth: ContractAddressWrapper,
+    /// STRK token, for the fees of v3 transactions.
+    pub strk: ContractAddressWrapper,
+}
+
 /// Gets the transaction resources.
 ///
 /// # Arguments
@@ -129,7 +151,7 @@ fn execute_fee_transfer(
     // FIXME: https://github.com/keep-starknet-strange/madara/issues/331
     let max_fee = Fee(u128::MAX);
     if actual_fee > max_fee {
-        return Err(TransactionExecutionErrorWrapper::FeeTransferError { max_fee, actual_fee });
+        return Err(TransactionExecutionErrorWrapper::fee_transfer_error(max_fee, actual_fee));
     }
     // TODO: This is what's done in the blockifier but this should be improved.
     // FIXME: https://github.com/keep-starknet-strange/madara/issues/332
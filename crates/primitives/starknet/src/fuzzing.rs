@@ -0,0 +1,24 @@
+//! Generation of the fields that can't derive [`arbitrary::Arbitrary`] as the trait is not
+//! implemented for their foreign type.
+//!
+//! The fuzzed types use these functions through `#[arbitrary(with = ...)]`. Contract classes are
+//! not generated, they are left to their default value.
+
+use alloc::vec::Vec;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use frame_support::traits::Get;
+use frame_support::BoundedVec;
+use sp_core::U256;
+
+/// Generates a [BoundedVec], its length is capped to the bound `S`.
+pub(crate) fn bounded_vec<'a, T: Arbitrary<'a>, S: Get<u32>>(u: &mut Unstructured<'a>) -> Result<BoundedVec<T, S>> {
+    let len = u.arbitrary_len::<T>()?.min(S::get() as usize);
+    let items = (0..len).map(|_| T::arbitrary(u)).collect::<Result<Vec<T>>>()?;
+    Ok(BoundedVec::truncate_from(items))
+}
+
+/// Generates a [U256] over its whole range, including values that don't fit in a felt.
+pub(crate) fn u256(u: &mut Unstructured<'_>) -> Result<U256> {
+    Ok(U256::from_big_endian(&u.arbitrary::<[u8; 32]>()?))
+}
@@ -41,6 +41,10 @@ pub mod execution;
 
 /// Starknet Fees related functionality.
 pub mod fees;
+
+/// Bounded generation of arbitrary values, for fuzzing.
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
 /// Tests.
 #[cfg(test)]
 pub mod tests;
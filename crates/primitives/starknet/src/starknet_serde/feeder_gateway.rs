@@ -0,0 +1,443 @@
+//! Deserialization of the transactions served by the Starknet feeder gateway.
+//!
+//! The feeder gateway schema differs from the JSON-RPC one:
+//! * the transaction type is the name of the gateway object, e.g. `INVOKE_FUNCTION` for invoke
+//!   transactions,
+//! * the calldata of deploy account transactions is under `constructor_calldata`,
+//! * historic blocks contain legacy `DEPLOY` transactions, which are decoded but can't be executed,
+//! * numbers are hex strings, except in some old blocks where they are decimal strings,
+//! * old transactions may omit `max_fee`, `version`, `nonce` or `signature`, which then default to
+//!   zero or empty.
+//!
+//! The transaction hash served by the gateway is kept as is, it isn't recomputed.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use frame_support::traits::Get;
+use frame_support::BoundedVec;
+use serde::Deserialize;
+use thiserror_no_std::Error;
+
+use crate::execution::types::{
+    CallEntryPointWrapper, ContractAddressWrapper, EntryPointSelector, EntryPointTypeWrapper, Felt252Wrapper,
+    Felt252WrapperError,
+};
+use crate::transaction::types::{Transaction, TxType};
+
+/// Transaction as served by the feeder gateway.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum FeederGatewayTransaction {
+    /// Invoke transaction, v0 or v1.
+    #[serde(rename = "INVOKE_FUNCTION")]
+    Invoke(FeederGatewayInvokeTransaction),
+    /// Declare transaction, v1 or v2.
+    #[serde(rename = "DECLARE")]
+    Declare(FeederGatewayDeclareTransaction),
+    /// Deploy account transaction.
+    #[serde(rename = "DEPLOY_ACCOUNT")]
+    DeployAccount(FeederGatewayDeployAccountTransaction),
+    /// L1 handler transaction.
+    #[serde(rename = "L1_HANDLER")]
+    L1Handler(FeederGatewayL1HandlerTransaction),
+    /// Legacy deploy transaction, only found in historic blocks.
+    #[serde(rename = "DEPLOY")]
+    Deploy(FeederGatewayDeployTransaction),
+}
+
+/// Invoke transaction as served by the feeder gateway.
+///
+/// v0 transactions call `entry_point_selector` on `contract_address`, v1 transactions call
+/// `__execute__` on `sender_address`.
+#[derive(Debug, Deserialize)]
+pub struct FeederGatewayInvokeTransaction {
+    /// Transaction hash.
+    pub transaction_hash: String,
+    /// Version, `0x0` if omitted.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Max fee, zero if omitted.
+    #[serde(default)]
+    pub max_fee: Option<String>,
+    /// Signature.
+    #[serde(default)]
+    pub signature: Vec<String>,
+    /// Nonce, v1 only.
+    #[serde(default)]
+    pub nonce: Option<String>,
+    /// Sender address, v1 only.
+    #[serde(default)]
+    pub sender_address: Option<String>,
+    /// Called contract, v0 only.
+    #[serde(default)]
+    pub contract_address: Option<String>,
+    /// Called entry point, v0 only.
+    #[serde(default)]
+    pub entry_point_selector: Option<String>,
+    /// Calldata.
+    pub calldata: Vec<String>,
+}
+
+/// Declare transaction as served by the feeder gateway.
+///
+/// The declared class isn't part of the transaction, it has to be fetched separately.
+#[derive(Debug, Deserialize)]
+pub struct FeederGatewayDeclareTransaction {
+    /// Transaction hash.
+    pub transaction_hash: String,
+    /// Version.
+    pub version: String,
+    /// Max fee, zero if omitted.
+    #[serde(default)]
+    pub max_fee: Option<String>,
+    /// Signature.
+    #[serde(default)]
+    pub signature: Vec<String>,
+    /// Nonce.
+    pub nonce: String,
+    /// Hash of the declared class.
+    pub class_hash: String,
+    /// Hash of the compiled class, v2 only. Not part of [`Transaction`].
+    #[serde(default)]
+    pub compiled_class_hash: Option<String>,
+    /// Sender address.
+    pub sender_address: String,
+}
+
+/// Deploy account transaction as served by the feeder gateway.
+#[derive(Debug, Deserialize)]
+pub struct FeederGatewayDeployAccountTransaction {
+    /// Transaction hash.
+    pub transaction_hash: String,
+    /// Version.
+    pub version: String,
+    /// Max fee, zero if omitted.
+    #[serde(default)]
+    pub max_fee: Option<String>,
+    /// Signature.
+    #[serde(default)]
+    pub signature: Vec<String>,
+    /// Nonce.
+    pub nonce: String,
+    /// Class hash of the deployed account.
+    pub class_hash: String,
+    /// Address of the deployed account.
+    pub contract_address: String,
+    /// Salt of the account address.
+    pub contract_address_salt: String,
+    /// Calldata of the account constructor.
+    pub constructor_calldata: Vec<String>,
+}
+
+/// L1 handler transaction as served by the feeder gateway.
+#[derive(Debug, Deserialize)]
+pub struct FeederGatewayL1HandlerTransaction {
+    /// Transaction hash.
+    pub transaction_hash: String,
+    /// Version, `0x0` if omitted.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Nonce of the L1 message, zero if omitted.
+    #[serde(default)]
+    pub nonce: Option<String>,
+    /// Called contract.
+    pub contract_address: String,
+    /// Called L1 handler.
+    pub entry_point_selector: String,
+    /// Calldata, starting with the L1 sender address.
+    pub calldata: Vec<String>,
+}
+
+/// Legacy deploy transaction as served by the feeder gateway.
+#[derive(Debug, Deserialize)]
+pub struct FeederGatewayDeployTransaction {
+    /// Transaction hash.
+    pub transaction_hash: String,
+    /// Version, `0x0` if omitted.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Class hash of the deployed contract.
+    pub class_hash: String,
+    /// Address of the deployed contract.
+    pub contract_address: String,
+    /// Salt of the contract address.
+    pub contract_address_salt: String,
+    /// Calldata of the contract constructor.
+    pub constructor_calldata: Vec<String>,
+}
+
+/// Error of conversion of a feeder gateway transaction.
+#[derive(Debug, Error)]
+pub enum FeederGatewayConversionError {
+    /// The JSON isn't a feeder gateway transaction.
+    #[error("Failed to parse json: {0}")]
+    FailedToParse(String),
+    /// A field isn't a valid felt.
+    #[error("Invalid {field}: {error}")]
+    InvalidFelt {
+        /// Name of the field.
+        field: &'static str,
+        /// Parsing error.
+        error: Felt252WrapperError,
+    },
+    /// A field required by the transaction version is missing.
+    #[error("Missing {0}")]
+    MissingField(&'static str),
+    /// The version doesn't fit in a `u8`.
+    #[error("Invalid version: {0}")]
+    InvalidVersion(String),
+    /// The version isn't supported for the transaction type.
+    #[error("Unsupported version {version} for {tx_type:?} transaction")]
+    UnsupportedVersion {
+        /// Type of the transaction.
+        tx_type: TxType,
+        /// Version of the transaction.
+        version: u8,
+    },
+    /// An array exceeds its max size.
+    #[error("Too many elements in {0}")]
+    ArrayTooLong(&'static str),
+}
+
+/// Deserializes a feeder gateway transaction and converts it to a [`Transaction`].
+pub fn transaction_from_feeder_gateway_json(json_str: &str) -> Result<Transaction, FeederGatewayConversionError> {
+    let transaction: FeederGatewayTransaction =
+        serde_json::from_str(json_str).map_err(|e| FeederGatewayConversionError::FailedToParse(format!("{:?}", e)))?;
+    Transaction::try_from(transaction)
+}
+
+impl TryFrom<FeederGatewayTransaction> for Transaction {
+    type Error = FeederGatewayConversionError;
+
+    fn try_from(value: FeederGatewayTransaction) -> Result<Self, Self::Error> {
+        match value {
+            FeederGatewayTransaction::Invoke(tx) => tx.try_into(),
+            FeederGatewayTransaction::Declare(tx) => tx.try_into(),
+            FeederGatewayTransaction::DeployAccount(tx) => tx.try_into(),
+            FeederGatewayTransaction::L1Handler(tx) => tx.try_into(),
+            FeederGatewayTransaction::Deploy(tx) => tx.try_into(),
+        }
+    }
+}
+
+impl TryFrom<FeederGatewayInvokeTransaction> for Transaction {
+    type Error = FeederGatewayConversionError;
+
+    fn try_from(value: FeederGatewayInvokeTransaction) -> Result<Self, Self::Error> {
+        let version = parse_version(value.version.as_deref())?;
+        let (sender_address, nonce, entrypoint_selector) = match version {
+            0 => (
+                parse_felt("contract_address", required("contract_address", value.contract_address)?.as_str())?,
+                Felt252Wrapper::ZERO,
+                Some(EntryPointSelector(parse_felt(
+                    "entry_point_selector",
+                    required("entry_point_selector", value.entry_point_selector)?.as_str(),
+                )?)),
+            ),
+            1 => (
+                parse_felt("sender_address", required("sender_address", value.sender_address)?.as_str())?,
+                parse_felt("nonce", required("nonce", value.nonce)?.as_str())?,
+                None,
+            ),
+            version => {
+                return Err(FeederGatewayConversionError::UnsupportedVersion { tx_type: TxType::Invoke, version });
+            }
+        };
+
+        Ok(Transaction {
+            tx_type: TxType::Invoke,
+            version,
+            hash: parse_felt("transaction_hash", &value.transaction_hash)?,
+            signature: parse_felts("signature", &value.signature)?,
+            sender_address,
+            nonce,
+            call_entrypoint: CallEntryPointWrapper::new(
+                None,
+                EntryPointTypeWrapper::External,
+                entrypoint_selector,
+                parse_felts("calldata", &value.calldata)?,
+                sender_address,
+                sender_address,
+            ),
+            contract_class: None,
+            contract_address_salt: None,
+            max_fee: parse_max_fee(value.max_fee.as_deref())?,
+            paid_fee_on_l1: None,
+        })
+    }
+}
+
+impl TryFrom<FeederGatewayDeclareTransaction> for Transaction {
+    type Error = FeederGatewayConversionError;
+
+    fn try_from(value: FeederGatewayDeclareTransaction) -> Result<Self, Self::Error> {
+        let version = parse_version(Some(&value.version))?;
+        match version {
+            1 => {}
+            2 => {
+                required("compiled_class_hash", value.compiled_class_hash)?;
+            }
+            version => {
+                return Err(FeederGatewayConversionError::UnsupportedVersion { tx_type: TxType::Declare, version });
+            }
+        }
+        let sender_address = parse_felt("sender_address", &value.sender_address)?;
+
+        Ok(Transaction {
+            tx_type: TxType::Declare,
+            version,
+            hash: parse_felt("transaction_hash", &value.transaction_hash)?,
+            signature: parse_felts("signature", &value.signature)?,
+            sender_address,
+            nonce: parse_felt("nonce", &value.nonce)?,
+            call_entrypoint: CallEntryPointWrapper::new(
+                Some(parse_felt("class_hash", &value.class_hash)?),
+                EntryPointTypeWrapper::External,
+                None,
+                BoundedVec::default(),
+                sender_address,
+                sender_address,
+            ),
+            contract_class: None,
+            contract_address_salt: None,
+            max_fee: parse_max_fee(value.max_fee.as_deref())?,
+            paid_fee_on_l1: None,
+        })
+    }
+}
+
+impl TryFrom<FeederGatewayDeployAccountTransaction> for Transaction {
+    type Error = FeederGatewayConversionError;
+
+    fn try_from(value: FeederGatewayDeployAccountTransaction) -> Result<Self, Self::Error> {
+        let version = parse_version(Some(&value.version))?;
+        if version != 1 {
+            return Err(FeederGatewayConversionError::UnsupportedVersion { tx_type: TxType::DeployAccount, version });
+        }
+        let sender_address: ContractAddressWrapper = parse_felt("contract_address", &value.contract_address)?;
+
+        Ok(Transaction {
+            tx_type: TxType::DeployAccount,
+            version,
+            hash: parse_felt("transaction_hash", &value.transaction_hash)?,
+            signature: parse_felts("signature", &value.signature)?,
+            sender_address,
+            nonce: parse_felt("nonce", &value.nonce)?,
+            call_entrypoint: CallEntryPointWrapper::new(
+                Some(parse_felt("class_hash", &value.class_hash)?),
+                EntryPointTypeWrapper::External,
+                None,
+                parse_felts("constructor_calldata", &value.constructor_calldata)?,
+                sender_address,
+                sender_address,
+            ),
+            contract_class: None,
+            contract_address_salt: Some(parse_felt("contract_address_salt", &value.contract_address_salt)?),
+            max_fee: parse_max_fee(value.max_fee.as_deref())?,
+            paid_fee_on_l1: None,
+        })
+    }
+}
+
+impl TryFrom<FeederGatewayL1HandlerTransaction> for Transaction {
+    type Error = FeederGatewayConversionError;
+
+    fn try_from(value: FeederGatewayL1HandlerTransaction) -> Result<Self, Self::Error> {
+        let version = parse_version(value.version.as_deref())?;
+        if version != 0 {
+            return Err(FeederGatewayConversionError::UnsupportedVersion { tx_type: TxType::L1Handler, version });
+        }
+        let contract_address = parse_felt("contract_address", &value.contract_address)?;
+
+        Ok(Transaction {
+            tx_type: TxType::L1Handler,
+            version,
+            hash: parse_felt("transaction_hash", &value.transaction_hash)?,
+            signature: BoundedVec::default(),
+            sender_address: contract_address,
+            nonce: value.nonce.as_deref().map(|nonce| parse_felt("nonce", nonce)).transpose()?.unwrap_or_default(),
+            call_entrypoint: CallEntryPointWrapper::new(
+                None,
+                EntryPointTypeWrapper::L1Handler,
+                Some(EntryPointSelector(parse_felt("entry_point_selector", &value.entry_point_selector)?)),
+                parse_felts("calldata", &value.calldata)?,
+                contract_address,
+                ContractAddressWrapper::default(),
+            ),
+            contract_class: None,
+            contract_address_salt: None,
+            max_fee: Felt252Wrapper::ZERO,
+            paid_fee_on_l1: None,
+        })
+    }
+}
+
+impl TryFrom<FeederGatewayDeployTransaction> for Transaction {
+    type Error = FeederGatewayConversionError;
+
+    fn try_from(value: FeederGatewayDeployTransaction) -> Result<Self, Self::Error> {
+        let version = parse_version(value.version.as_deref())?;
+        if version > 1 {
+            return Err(FeederGatewayConversionError::UnsupportedVersion { tx_type: TxType::Deploy, version });
+        }
+        let contract_address = parse_felt("contract_address", &value.contract_address)?;
+
+        Ok(Transaction {
+            tx_type: TxType::Deploy,
+            version,
+            hash: parse_felt("transaction_hash", &value.transaction_hash)?,
+            signature: BoundedVec::default(),
+            sender_address: contract_address,
+            nonce: Felt252Wrapper::ZERO,
+            call_entrypoint: CallEntryPointWrapper::new(
+                Some(parse_felt("class_hash", &value.class_hash)?),
+                EntryPointTypeWrapper::Constructor,
+                None,
+                parse_felts("constructor_calldata", &value.constructor_calldata)?,
+                contract_address,
+                ContractAddressWrapper::default(),
+            ),
+            contract_class: None,
+            contract_address_salt: Some(parse_felt("contract_address_salt", &value.contract_address_salt)?),
+            max_fee: Felt252Wrapper::ZERO,
+            paid_fee_on_l1: None,
+        })
+    }
+}
+
+/// Parses a felt from a hex string prefixed with `0x` or from a decimal string.
+fn parse_felt(field: &'static str, value: &str) -> Result<Felt252Wrapper, FeederGatewayConversionError> {
+    Felt252Wrapper::from_str_auto(value).map_err(|error| FeederGatewayConversionError::InvalidFelt { field, error })
+}
+
+/// Parses an array of felts, see [`parse_felt`].
+fn parse_felts<S: Get<u32>>(
+    field: &'static str,
+    values: &[String],
+) -> Result<BoundedVec<Felt252Wrapper, S>, FeederGatewayConversionError> {
+    let felts = values.iter().map(|value| parse_felt(field, value)).collect::<Result<Vec<_>, _>>()?;
+    BoundedVec::try_from(felts).map_err(|_| FeederGatewayConversionError::ArrayTooLong(field))
+}
+
+/// Parses a version, `0x0` if omitted.
+fn parse_version(version: Option<&str>) -> Result<u8, FeederGatewayConversionError> {
+    let version = match version {
+        Some(version) => version,
+        None => return Ok(0),
+    };
+    u64::try_from(parse_felt("version", version)?)
+        .ok()
+        .and_then(|version| u8::try_from(version).ok())
+        .ok_or_else(|| FeederGatewayConversionError::InvalidVersion(String::from(version)))
+}
+
+/// Parses a max fee, zero if omitted as on older transactions.
+fn parse_max_fee(max_fee: Option<&str>) -> Result<Felt252Wrapper, FeederGatewayConversionError> {
+    max_fee.map(|max_fee| parse_felt("max_fee", max_fee)).transpose().map(Option::unwrap_or_default)
+}
+
+/// Returns the value of a field required by the transaction version.
+fn required(field: &'static str, value: Option<String>) -> Result<String, FeederGatewayConversionError> {
+    value.ok_or(FeederGatewayConversionError::MissingField(field))
+}
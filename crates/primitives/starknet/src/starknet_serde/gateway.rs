@@ -0,0 +1,45 @@
+//! Deserialization of the responses of the Starknet gateway.
+//!
+//! Transactions forwarded to an upstream sequencer are submitted to the `add_transaction`
+//! endpoint of its gateway, which answers with the hash it computed for the transaction.
+use serde::Deserialize;
+use thiserror_no_std::Error;
+
+use crate::execution::types::{ClassHashWrapper, ContractAddressWrapper, Felt252Wrapper};
+use crate::transaction::types::Transaction;
+
+/// Response of the gateway `add_transaction` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AddTransactionResponse {
+    /// Hash of the transaction, as computed by the gateway.
+    pub transaction_hash: Felt252Wrapper,
+    /// Address of the deployed contract, only set for deploy account transactions.
+    #[serde(default, alias = "address")]
+    pub contract_address: Option<ContractAddressWrapper>,
+    /// Hash of the declared class, only set for declare transactions.
+    #[serde(default)]
+    pub class_hash: Option<ClassHashWrapper>,
+}
+
+/// The gateway computed another hash than ours for a transaction.
+///
+/// This is usually due to a version or chain id mismatch with the upstream sequencer.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Transaction hash mismatch: computed {local:?}, gateway returned {gateway:?}")]
+pub struct TransactionHashMismatch {
+    /// Hash computed locally.
+    pub local: Felt252Wrapper,
+    /// Hash returned by the gateway.
+    pub gateway: Felt252Wrapper,
+}
+
+impl AddTransactionResponse {
+    /// Checks that the gateway computed the same hash as the one of `transaction`.
+    pub fn verify_hash(&self, transaction: &Transaction) -> Result<(), TransactionHashMismatch> {
+        if self.transaction_hash == transaction.hash {
+            Ok(())
+        } else {
+            Err(TransactionHashMismatch { local: transaction.hash, gateway: self.transaction_hash })
+        }
+    }
+}
@@ -10,11 +10,21 @@ use sp_core::U256;
 use thiserror_no_std::Error;
 
 use crate::execution::types::{
-    CallEntryPointWrapper, ContractClassWrapper, EntryPointTypeWrapper, Felt252Wrapper, Felt252WrapperError,
-    MaxCalldataSize,
+    CallEntryPointWrapper, ContractClassWrapper, EntryPointSelector, EntryPointTypeWrapper, Felt252Wrapper,
+    Felt252WrapperError, MaxCalldataSize,
 };
 use crate::transaction::types::{EventWrapper, MaxArraySize, Transaction};
 
+/// Feeder gateway transactions.
+#[cfg(feature = "std")]
+pub mod feeder_gateway;
+/// Gateway responses.
+#[cfg(feature = "std")]
+pub mod gateway;
+/// Hex serialization of [U256].
+#[cfg(feature = "std")]
+pub mod u256_hex;
+
 /// Removes the "0x" prefix from a given hexadecimal string
 fn remove_prefix(input: &str) -> &str {
     input.strip_prefix("0x").unwrap_or(input)
@@ -225,11 +235,11 @@ impl TryFrom<DeserializeCallEntrypoint> for CallEntryPointWrapper {
             _ => return Err(DeserializeCallEntrypointError::InvalidEntryPointType),
         };
 
-        // Convert entrypoint_selector to Option<Felt252Wrapper> if present
+        // Convert entrypoint_selector to Option<EntryPointSelector> if present
         let entrypoint_selector = match d.entrypoint_selector {
-            Some(selector) => {
-                Some(string_to_felt(&selector).map_err(DeserializeCallEntrypointError::InvalidEntrypointSelector)?)
-            }
+            Some(selector) => Some(EntryPointSelector(
+                string_to_felt(&selector).map_err(DeserializeCallEntrypointError::InvalidEntrypointSelector)?,
+            )),
             None => None,
         };
 
@@ -0,0 +1,33 @@
+//! Serialization of a [U256] as a `0x` prefixed big-endian hex string of 64 nibbles.
+//!
+//! Used with `#[serde(with = "crate::starknet_serde::u256_hex")]`. Deserialization also accepts
+//! strings with fewer nibbles, as the RPC doesn't pad its felts.
+use alloc::format;
+use alloc::string::String;
+
+use serde::de::Error as DeserializationError;
+use serde::{Deserialize, Deserializer, Serializer};
+use sp_core::U256;
+
+/// Number of nibbles of a serialized [U256].
+const NIBBLES: usize = 64;
+
+/// Serializes `value` as a `0x` prefixed hex string of 64 nibbles.
+pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut bytes = [0_u8; 32];
+    value.to_big_endian(&mut bytes);
+    serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+}
+
+/// Deserializes a `0x` prefixed hex string of at most 64 nibbles.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    let nibbles = value.strip_prefix("0x").ok_or_else(|| DeserializationError::custom("missing 0x prefix"))?;
+    if nibbles.is_empty() || nibbles.len() > NIBBLES {
+        return Err(DeserializationError::custom(format!("expected 1 to {NIBBLES} nibbles, got {}", nibbles.len())));
+    }
+
+    let mut bytes = [0_u8; 32];
+    hex::decode_to_slice(format!("{nibbles:0>NIBBLES$}"), &mut bytes).map_err(DeserializationError::custom)?;
+    Ok(U256::from_big_endian(&bytes))
+}
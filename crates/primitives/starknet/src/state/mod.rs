@@ -9,6 +9,8 @@ use starknet_api::hash::StarkFelt;
 use starknet_api::state::StorageKey;
 use starknet_api::stdlib::collections::HashMap;
 
+use crate::transaction::types::{StateDiffError, StateDiffWrapper};
+
 type ContractClassMapping = HashMap<ClassHash, ContractClass>;
 
 /// This trait allows to get the state changes of a starknet tx and therefore enables computing the
@@ -23,6 +25,25 @@ pub trait StateChanges {
     /// * `usize` - The number of modified storage vars in the transaction.
     /// * `usize` -  The number of newly declared classes.
     fn count_state_changes(&self) -> (usize, usize, usize);
+
+    /// Returns the state changes made by the transaction: storage updates, nonce updates, newly
+    /// deployed contracts and newly declared classes.
+    ///
+    /// # Errors
+    ///
+    /// A [StateDiffError] if the changes don't fit in a [StateDiffWrapper].
+    fn state_diff(&self) -> Result<StateDiffWrapper, StateDiffError>;
+}
+
+/// This trait allows to discard the writes made by a part of a transaction, so that a reverted
+/// execution doesn't leave partial effects behind.
+pub trait StateRollback {
+    /// Runs `f` on the state, keeping its writes if it succeeds and discarding them if it fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The part of the transaction to run.
+    fn with_rollback<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E>;
 }
 
 /// A simple implementation of `StateReader` using `HashMap`s as storage.
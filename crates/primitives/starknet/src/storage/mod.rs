@@ -31,6 +31,17 @@ pub enum StarknetStorageSchemaVersion {
     Undefined,
     /// Schema V1.
     V1,
+    /// Schema V2: transaction receipts carry the fee unit, the revert reason, the block timestamp,
+    /// the sequencer address, the declared class hash and the deployed contract address,
+    /// transactions store their contract address salt as a felt.
+    V2,
+    /// Schema V3: the block number and block hash of the transaction receipts are optional, unset
+    /// for pending receipts.
+    V3,
+    /// Schema V4: transactions carry the fee paid on L1 of L1 handler transactions.
+    V4,
+    /// Schema V5: transaction receipts record whether events were dropped because they didn't fit.
+    V5,
 }
 
 impl Default for StarknetStorageSchemaVersion {
@@ -4,21 +4,24 @@ use std::str::FromStr;
 
 use frame_support::bounded_vec;
 use sp_core::{H256, U256};
-use starknet_crypto::FieldElement;
+use starknet_crypto::{poseidon_hash, poseidon_hash_many, FieldElement};
 
 use crate::crypto::commitment::{
-    calculate_declare_tx_hash, calculate_deploy_account_tx_hash, calculate_event_commitment, calculate_event_hash,
-    calculate_invoke_tx_hash, calculate_transaction_commitment,
+    calculate_contract_address, calculate_declare_tx_hash, calculate_deploy_account_tx_hash, calculate_deploy_tx_hash,
+    calculate_event_commitment, calculate_event_hash, calculate_invoke_tx_hash, calculate_l1_handler_tx_hash,
+    calculate_transaction_commitment, calculate_transaction_hash, calculate_versioned_commitments,
+    hash_transactions_parallel, pack_da_modes, CommitmentVersion, InvalidStarknetVersion, StarknetV0,
 };
 use crate::crypto::hash::pedersen::PedersenHasher;
 use crate::crypto::hash::{hash, Hasher};
 use crate::crypto::merkle_patricia_tree::merkle_node::{BinaryNode, Direction, Node};
 use crate::execution::call_entrypoint_wrapper::CallEntryPointWrapper;
 use crate::execution::contract_class_wrapper::ContractClassWrapper;
-use crate::execution::types::Felt252Wrapper;
-use crate::traits::hash::{CryptoHasherT, HasherT};
+use crate::execution::types::{ChainId, ContractAddressWrapper, EntryPointSelector, Felt252Wrapper};
+use crate::traits::hash::{CryptoHasherT, HasherT, TransactionHasher};
 use crate::transaction::types::{
-    DeclareTransaction, DeployAccountTransaction, EventWrapper, InvokeTransaction, Transaction, TxType,
+    DataAvailabilityMode, DeclareTransaction, DeployAccountTransaction, DeployTransaction, EventWrapper,
+    InvokeTransaction, Transaction, TxType,
 };
 
 #[test]
@@ -27,7 +30,7 @@ fn test_deploy_account_tx_hash() {
     let expected_tx_hash =
         Felt252Wrapper::from_hex_be("0x050a9c8ed9d8053fc3cf6704b95c1b368cf9a110ff72b87b760db832155b7022").unwrap();
 
-    let chain_id = "SN_GOERLI";
+    let chain_id = ChainId::SN_GOERLI;
 
     let transaction = DeployAccountTransaction {
         version: 1,
@@ -42,13 +45,52 @@ fn test_deploy_account_tx_hash() {
     assert_eq!(calculate_deploy_account_tx_hash(transaction, chain_id), expected_tx_hash);
 }
 
+#[test]
+fn test_legacy_deploy_tx_hash() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let transaction = DeployTransaction {
+        version: 0,
+        class_hash: Felt252Wrapper::THREE,
+        contract_address_salt: Felt252Wrapper::TWO,
+        constructor_calldata: bounded_vec!(Felt252Wrapper::ONE, Felt252Wrapper::TWO),
+    };
+    let contract_address = calculate_contract_address(
+        Felt252Wrapper::TWO,
+        Felt252Wrapper::THREE,
+        &[Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        ContractAddressWrapper::default(),
+    );
+    // `calculate_deploy_transaction_hash` of the cairo lang package.
+    let expected_tx_hash: Felt252Wrapper = PedersenHasher::compute_hash_on_elements(&[
+        FieldElement::from_byte_slice_be(b"deploy").unwrap(),
+        FieldElement::ZERO,
+        contract_address.into(),
+        EntryPointSelector::CONSTRUCTOR.0.into(),
+        PedersenHasher::compute_hash_on_elements(&[FieldElement::ONE, FieldElement::TWO]),
+        FieldElement::ZERO,
+        chain_id.into(),
+    ])
+    .into();
+
+    // When
+    let tx = transaction.clone().from_deploy_legacy(chain_id);
+
+    // Then
+    pretty_assertions::assert_eq!(calculate_deploy_tx_hash(&transaction, chain_id), expected_tx_hash);
+    pretty_assertions::assert_eq!(tx.hash, expected_tx_hash);
+    pretty_assertions::assert_eq!(tx.tx_type, TxType::Deploy);
+    pretty_assertions::assert_eq!(tx.sender_address, contract_address);
+    pretty_assertions::assert_eq!(calculate_transaction_hash(&tx, chain_id), expected_tx_hash);
+}
+
 #[test]
 fn test_declare_tx_hash() {
     // Computed with `calculate_declare_transaction_hash` from the cairo lang package
     let expected_tx_hash =
         Felt252Wrapper::from_hex_be("0x077f205d4855199564663dc9810c1edfcf97573393033dedc3f12dac740aac13").unwrap();
 
-    let chain_id = "SN_GOERLI";
+    let chain_id = ChainId::SN_GOERLI;
 
     let transaction = DeclareTransaction {
         version: 1,
@@ -68,7 +110,7 @@ fn test_invoke_tx_hash() {
     let expected_tx_hash =
         Felt252Wrapper::from_hex_be("0x062633b1f3d64708df3d0d44706b388f841ed4534346be6ad60336c8eb2f4b3e").unwrap();
 
-    let chain_id = "SN_GOERLI";
+    let chain_id = ChainId::SN_GOERLI;
 
     let transaction = InvokeTransaction {
         version: 1,
@@ -77,13 +119,226 @@ fn test_invoke_tx_hash() {
         nonce: Felt252Wrapper::ZERO,
         signature: bounded_vec!(),
         max_fee: Felt252Wrapper::ONE,
+        entry_point_selector: None,
     };
     assert_eq!(calculate_invoke_tx_hash(transaction, chain_id), expected_tx_hash);
 }
 
+/// Hashes every transaction to its version, whatever its content.
+struct VersionHasher;
+
+impl TransactionHasher for VersionHasher {
+    fn invoke_tx_hash(transaction: &InvokeTransaction, _chain_id: ChainId) -> Felt252Wrapper {
+        Felt252Wrapper::from(u64::from(transaction.version))
+    }
+
+    fn declare_tx_hash(transaction: &DeclareTransaction, _chain_id: ChainId) -> Felt252Wrapper {
+        Felt252Wrapper::from(u64::from(transaction.version))
+    }
+
+    fn deploy_account_tx_hash(transaction: &DeployAccountTransaction, _chain_id: ChainId) -> Felt252Wrapper {
+        Felt252Wrapper::from(u64::from(transaction.version))
+    }
+
+    fn deploy_tx_hash(transaction: &DeployTransaction, _chain_id: ChainId) -> Felt252Wrapper {
+        Felt252Wrapper::from(u64::from(transaction.version))
+    }
+}
+
 #[test]
-fn test_merkle_tree() {
+fn test_from_conversions_use_the_given_transaction_hasher() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let invoke = InvokeTransaction { version: 1, ..InvokeTransaction::default() };
+    let declare = DeclareTransaction { version: 2, ..DeclareTransaction::default() };
+    let deploy_account = DeployAccountTransaction { version: 3, ..DeployAccountTransaction::default() };
+    let deploy = DeployTransaction { version: 4, ..DeployTransaction::default() };
+
+    // When
+    let hashes = [
+        invoke.from_invoke_with_hasher::<VersionHasher>(chain_id).unwrap().hash,
+        declare.from_declare_with_hasher::<VersionHasher>(chain_id).hash,
+        deploy_account.from_deploy_with_hasher::<VersionHasher>(chain_id).unwrap().hash,
+        deploy.from_deploy_legacy_with_hasher::<VersionHasher>(chain_id).hash,
+    ];
+
+    // Then
+    pretty_assertions::assert_eq!(hashes, [1_u64, 2, 3, 4].map(Felt252Wrapper::from));
+}
+
+#[test]
+fn test_to_transaction_conversions_use_the_given_transaction_hasher() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let invoke = InvokeTransaction { version: 1, ..InvokeTransaction::default() };
+    let declare = DeclareTransaction { version: 2, ..DeclareTransaction::default() };
+    let deploy_account = DeployAccountTransaction { version: 3, ..DeployAccountTransaction::default() };
+
+    // When
+    let hashes = [
+        invoke.to_transaction_with_hasher::<VersionHasher>(chain_id).unwrap().hash,
+        declare.to_transaction_with_hasher::<VersionHasher>(chain_id).hash,
+        deploy_account.to_transaction_with_hasher::<VersionHasher>(chain_id).unwrap().hash,
+    ];
+
+    // Then
+    pretty_assertions::assert_eq!(hashes, [1_u64, 2, 3].map(Felt252Wrapper::from));
+}
+
+#[test]
+fn test_starknet_v0_hasher_matches_the_default_conversions() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let invoke = InvokeTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(19911991_u128),
+        calldata: bounded_vec!(Felt252Wrapper::ONE, Felt252Wrapper::TWO, Felt252Wrapper::THREE),
+        max_fee: Felt252Wrapper::ONE,
+        ..InvokeTransaction::default()
+    };
+    let declare =
+        DeclareTransaction { version: 1, compiled_class_hash: Felt252Wrapper::THREE, ..DeclareTransaction::default() };
+    let deploy_account = DeployAccountTransaction {
+        version: 1,
+        calldata: bounded_vec!(Felt252Wrapper::ONE),
+        ..DeployAccountTransaction::default()
+    };
+    let deploy = DeployTransaction { class_hash: Felt252Wrapper::TWO, ..DeployTransaction::default() };
+
+    // Then
+    pretty_assertions::assert_eq!(
+        invoke.clone().from_invoke(chain_id).unwrap().hash,
+        StarknetV0::invoke_tx_hash(&invoke, chain_id)
+    );
+    pretty_assertions::assert_eq!(
+        StarknetV0::invoke_tx_hash(&invoke, chain_id),
+        Felt252Wrapper::from_hex_be("0x062633b1f3d64708df3d0d44706b388f841ed4534346be6ad60336c8eb2f4b3e").unwrap()
+    );
+    pretty_assertions::assert_eq!(
+        declare.clone().from_declare(chain_id).hash,
+        calculate_declare_tx_hash(&declare, chain_id)
+    );
+    pretty_assertions::assert_eq!(
+        deploy_account.clone().from_deploy(chain_id).unwrap().hash,
+        calculate_deploy_account_tx_hash(&deploy_account, chain_id)
+    );
+    pretty_assertions::assert_eq!(
+        deploy.clone().from_deploy_legacy(chain_id).hash,
+        calculate_deploy_tx_hash(&deploy, chain_id)
+    );
+}
+
+#[test]
+fn test_pack_da_modes() {
+    let modes = [
+        (DataAvailabilityMode::L1, DataAvailabilityMode::L1, "0x0"),
+        (DataAvailabilityMode::L1, DataAvailabilityMode::L2, "0x1"),
+        (DataAvailabilityMode::L2, DataAvailabilityMode::L1, "0x100000000"),
+        (DataAvailabilityMode::L2, DataAvailabilityMode::L2, "0x100000001"),
+    ];
+
+    for (nonce_mode, fee_mode, expected) in modes {
+        pretty_assertions::assert_eq!(
+            pack_da_modes(nonce_mode, fee_mode),
+            Felt252Wrapper::from_hex_be(expected).unwrap(),
+            "{nonce_mode:?} {fee_mode:?}"
+        );
+    }
+}
+
+#[test]
+fn test_hash_transactions_parallel() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let invoke = InvokeTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(19911991_u128),
+        calldata: bounded_vec!(Felt252Wrapper::ONE, Felt252Wrapper::TWO, Felt252Wrapper::THREE),
+        nonce: Felt252Wrapper::ZERO,
+        signature: bounded_vec!(),
+        max_fee: Felt252Wrapper::ONE,
+        entry_point_selector: None,
+    };
+    let declare = DeclareTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(19911991_u128),
+        nonce: Felt252Wrapper::ZERO,
+        signature: bounded_vec!(),
+        max_fee: Felt252Wrapper::ONE,
+        compiled_class_hash: Felt252Wrapper::THREE,
+        contract_class: ContractClassWrapper::default(),
+    };
+    let deploy_account = DeployAccountTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(19911991_u128),
+        calldata: bounded_vec!(Felt252Wrapper::ONE, Felt252Wrapper::TWO, Felt252Wrapper::THREE),
+        nonce: Felt252Wrapper::ZERO,
+        salt: U256::zero(),
+        signature: bounded_vec!(),
+        account_class_hash: Felt252Wrapper::THREE,
+        max_fee: Felt252Wrapper::ONE,
+    };
+    let l1_handler = Transaction::from_l1_message(
+        Felt252Wrapper::ONE,
+        Felt252Wrapper::TWO,
+        EntryPointSelector::default(),
+        &[Felt252Wrapper::THREE],
+        Felt252Wrapper::ZERO,
+        Felt252Wrapper::ONE,
+        chain_id,
+    )
+    .unwrap();
     let txs = vec![
+        invoke.from_invoke(chain_id).unwrap(),
+        declare.from_declare(chain_id),
+        deploy_account.from_deploy(chain_id).unwrap(),
+        l1_handler,
+    ];
+
+    // When
+    let hashes = hash_transactions_parallel(&txs, chain_id);
+
+    // Then
+    let expected = vec![
+        Felt252Wrapper::from_hex_be("0x062633b1f3d64708df3d0d44706b388f841ed4534346be6ad60336c8eb2f4b3e").unwrap(),
+        Felt252Wrapper::from_hex_be("0x077f205d4855199564663dc9810c1edfcf97573393033dedc3f12dac740aac13").unwrap(),
+        Felt252Wrapper::from_hex_be("0x050a9c8ed9d8053fc3cf6704b95c1b368cf9a110ff72b87b760db832155b7022").unwrap(),
+        calculate_l1_handler_tx_hash(
+            Felt252Wrapper::TWO,
+            Felt252Wrapper::ZERO,
+            &[Felt252Wrapper::ONE, Felt252Wrapper::THREE],
+            Felt252Wrapper::ZERO,
+            chain_id,
+        ),
+    ];
+    pretty_assertions::assert_eq!(hashes, expected);
+    pretty_assertions::assert_eq!(hashes, txs.iter().map(|tx| tx.hash).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_hash_transactions_parallel_matches_sequential() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let txs: Vec<Transaction> = (0..500_u64)
+        .map(|i| Transaction {
+            tx_type: TxType::Invoke,
+            version: 1,
+            sender_address: Felt252Wrapper::from(i),
+            nonce: Felt252Wrapper::from(i),
+            ..Transaction::default()
+        })
+        .collect();
+
+    // When
+    let hashes = hash_transactions_parallel(&txs, chain_id);
+
+    // Then
+    let sequential: Vec<Felt252Wrapper> = txs.iter().map(|tx| calculate_transaction_hash(tx, chain_id)).collect();
+    pretty_assertions::assert_eq!(hashes, sequential);
+}
+
+fn merkle_tree_transactions() -> Vec<Transaction> {
+    vec![
         Transaction {
             tx_type: TxType::Invoke,
             version: 0_u8,
@@ -99,6 +354,7 @@ fn test_merkle_tree() {
             contract_class: None,
             contract_address_salt: None,
             max_fee: Felt252Wrapper::from(u128::MAX),
+            paid_fee_on_l1: None,
         },
         Transaction {
             tx_type: TxType::Invoke,
@@ -111,8 +367,14 @@ fn test_merkle_tree() {
             contract_class: None,
             contract_address_salt: None,
             max_fee: Felt252Wrapper::from(u128::MAX),
+            paid_fee_on_l1: None,
         },
-    ];
+    ]
+}
+
+#[test]
+fn test_merkle_tree() {
+    let txs = merkle_tree_transactions();
     let tx_com = calculate_transaction_commitment::<PedersenHasher>(&txs);
     let events = vec![EventWrapper::default(), EventWrapper::default()];
     let event_com = calculate_event_commitment::<PedersenHasher>(&events);
@@ -124,13 +386,74 @@ fn test_merkle_tree() {
     assert_eq!(H256::from_str("0x054c0fddf3aaf1ca03271712b323822647b66042ccc418ba1d7fb852aebfd2da").unwrap(), tx_com);
 }
 
+#[test]
+fn test_commitment_version_from_starknet_version() {
+    assert_eq!(CommitmentVersion::from_starknet_version(None), Ok(CommitmentVersion::Pedersen));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("0.11.1")), Ok(CommitmentVersion::Pedersen));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("0.13.1.1")), Ok(CommitmentVersion::Pedersen));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("0.13.2")), Ok(CommitmentVersion::Poseidon));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("0.13.2.1")), Ok(CommitmentVersion::Poseidon));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("0.14")), Ok(CommitmentVersion::Poseidon));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("v0.13.2")), Err(InvalidStarknetVersion));
+    assert_eq!(CommitmentVersion::from_starknet_version(Some("0.13.2.1.0")), Err(InvalidStarknetVersion));
+}
+
+#[test]
+fn test_versioned_commitments_pedersen_era() {
+    let txs = merkle_tree_transactions();
+    let events = vec![EventWrapper::default(), EventWrapper::default()];
+
+    let version = CommitmentVersion::from_starknet_version(Some("0.13.1")).unwrap();
+    let (tx_com, event_com) = calculate_versioned_commitments(&txs, &events, version);
+
+    assert_eq!(version, CommitmentVersion::Pedersen);
+    // Same values as `test_merkle_tree`, computed from the sequencer test.
+    assert_eq!(
+        H256::from_str("0x03ebee479332edbeecca7dee501cb507c69d51e0df116d28ae84cd2671dfef02").unwrap(),
+        event_com
+    );
+    assert_eq!(H256::from_str("0x054c0fddf3aaf1ca03271712b323822647b66042ccc418ba1d7fb852aebfd2da").unwrap(), tx_com);
+}
+
+#[test]
+fn test_versioned_commitments_poseidon_era() {
+    let version = CommitmentVersion::from_starknet_version(Some("0.13.2")).unwrap();
+    assert_eq!(version, CommitmentVersion::Poseidon);
+
+    let mut txs = merkle_tree_transactions();
+    txs[0].signature = Default::default();
+    let event = EventWrapper {
+        keys: bounded_vec![Felt252Wrapper::from(2_u128)],
+        data: bounded_vec![Felt252Wrapper::from(4_u128), Felt252Wrapper::from(5_u128)],
+        from_address: Felt252Wrapper::from(10_u128),
+        transaction_hash: Felt252Wrapper::from(28_u128),
+    };
+    let (tx_com, event_com) = calculate_versioned_commitments(&txs, &[event], version);
+
+    // The trees are checked node by node against the 0.13.2 rules: a binary node is
+    // `poseidon(left, right)` and an edge node is `poseidon(child, path) + length`.
+    let felt = |value: u64| FieldElement::from(value);
+    let edge = |child: FieldElement, path: u64, length: u64| poseidon_hash(child, felt(path)) + felt(length);
+
+    // The first transaction has no signature, the second one is signed with `40`.
+    let leaf_0 = poseidon_hash_many(&[txs[0].hash.into(), FieldElement::ZERO]);
+    let leaf_1 = poseidon_hash_many(&[txs[1].hash.into(), felt(40)]);
+    // The keys 0 and 1 only differ on their last bit, their binary node hangs from a 63 bits long
+    // edge from the root.
+    let expected_tx_com = edge(poseidon_hash(leaf_0, leaf_1), 0, 63);
+    assert_eq!(H256::from_slice(&expected_tx_com.to_bytes_be()), tx_com);
+
+    let event_leaf = poseidon_hash_many(&[felt(10), felt(28), felt(1), felt(2), felt(2), felt(4), felt(5)]);
+    assert_eq!(H256::from_slice(&edge(event_leaf, 0, 64).to_bytes_be()), event_com);
+}
+
 #[test]
 fn test_event_hash() {
-    let keys = bounded_vec![Felt252Wrapper::from(2_u128), Felt252Wrapper::from(3_u128),];
-    let data = bounded_vec![Felt252Wrapper::from(4_u128), Felt252Wrapper::from(5_u128), Felt252Wrapper::from(6_u128)];
+    let keys = vec![Felt252Wrapper::from(2_u128), Felt252Wrapper::from(3_u128)];
+    let data = vec![Felt252Wrapper::from(4_u128), Felt252Wrapper::from(5_u128), Felt252Wrapper::from(6_u128)];
     let from_address = Felt252Wrapper::from(10_u128);
     let transaction_hash = Felt252Wrapper::from(0_u128);
-    let event = EventWrapper::new(keys, data, from_address, transaction_hash);
+    let event = EventWrapper::new(keys, data, from_address, transaction_hash).unwrap();
     assert_eq!(
         calculate_event_hash::<PedersenHasher>(&event),
         FieldElement::from_str("0x3f44fb0516121d225664058ecc7e415c4725d6a7a11fd7d515c55c34ef8270b").unwrap()
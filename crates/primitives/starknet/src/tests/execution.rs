@@ -4,6 +4,7 @@ use alloc::sync::Arc;
 use blockifier::abi::abi_utils::selector_from_name;
 use blockifier::execution::contract_class::ContractClass;
 use blockifier::execution::entry_point::{CallEntryPoint, CallType};
+use cairo_vm::types::program::Program;
 use frame_support::{assert_ok, bounded_vec};
 use sp_runtime::BoundedBTreeMap;
 use starknet_api::api_core::{ChainId, ClassHash, ContractAddress, EntryPointSelector, PatriciaKey};
@@ -11,14 +12,19 @@ use starknet_api::deprecated_contract_class::EntryPointType;
 use starknet_api::hash::{StarkFelt, StarkHash};
 use starknet_api::transaction::Calldata;
 use starknet_api::{patricia_key, stark_felt};
+use starknet_core::types::ContractClass as RPCContractClass;
 
 use crate::block::Block;
 use crate::execution::call_entrypoint_wrapper::CallEntryPointWrapper;
+use crate::execution::chain_id::ChainId as ChainIdWrapper;
 use crate::execution::contract_class_wrapper::ContractClassWrapper;
+use crate::execution::entrypoint_selector::EntryPointSelector as EntryPointSelectorWrapper;
 use crate::execution::entrypoint_wrapper::{EntryPointTypeWrapper, EntryPointWrapper};
 use crate::execution::program_wrapper::ProgramWrapper;
-use crate::execution::types::{ContractAddressWrapper, Felt252Wrapper};
-use crate::tests::utils::{create_test_state, TEST_CLASS_HASH, TEST_CONTRACT_ADDRESS};
+use crate::execution::types::{ContractAddressWrapper, Felt252Wrapper, Felt252WrapperError};
+use crate::tests::utils::{
+    create_test_state, get_contract_class, TEST_CLASS_HASH, TEST_CONTRACT_ADDRESS, TEST_CONTRACT_PATH,
+};
 
 #[test]
 fn test_call_entry_point_execute_works() {
@@ -26,7 +32,7 @@ fn test_call_entry_point_execute_works() {
 
     let class_hash = Felt252Wrapper::from_hex_be(TEST_CLASS_HASH).unwrap();
     let address = Felt252Wrapper::from_hex_be(TEST_CONTRACT_ADDRESS).unwrap();
-    let selector = selector_from_name("return_result").0.into();
+    let selector = selector_from_name("return_result").into();
     let calldata = bounded_vec![42_u128.into()];
 
     let chain_id = ChainId("0x1".to_string());
@@ -50,7 +56,7 @@ fn test_call_entry_point_execute_fails_undeclared_class_hash() {
     let mut test_state = create_test_state();
 
     let address = Felt252Wrapper::from_hex_be(TEST_CONTRACT_ADDRESS).unwrap();
-    let selector = selector_from_name("return_result").0.into();
+    let selector = selector_from_name("return_result").into();
     let calldata = bounded_vec![42_u128.into()];
 
     let entrypoint = CallEntryPointWrapper::new(
@@ -176,3 +182,101 @@ fn test_contract_class_wrapper_try_from_contract_class() {
 
     pretty_assertions::assert_eq!(contract_class_wrapper, expected_contract_class_wrapper);
 }
+
+#[test]
+fn test_contract_class_wrapper_to_rpc_contract_class_round_trips() {
+    // Given
+    let contract_class = ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap();
+
+    // When
+    let rpc_contract_class = contract_class.to_rpc_contract_class().unwrap();
+
+    // Then
+    let rpc_contract_class = match rpc_contract_class {
+        RPCContractClass::Legacy(rpc_contract_class) => rpc_contract_class,
+        RPCContractClass::Sierra(_) => panic!("expected a legacy contract class"),
+    };
+    let mut program = Vec::new();
+    std::io::Read::read_to_end(&mut flate2::read::GzDecoder::new(rpc_contract_class.program.as_slice()), &mut program)
+        .unwrap();
+    let decompressed = ProgramWrapper::try_from(Program::from_bytes(&program, None).unwrap()).unwrap();
+    pretty_assertions::assert_eq!(decompressed, contract_class.program);
+    pretty_assertions::assert_eq!(rpc_contract_class.abi, None);
+
+    let entry_points = rpc_contract_class.entry_points_by_type;
+    for (entry_point_type, rpc_entry_points) in [
+        (EntryPointTypeWrapper::Constructor, entry_points.constructor),
+        (EntryPointTypeWrapper::External, entry_points.external),
+        (EntryPointTypeWrapper::L1Handler, entry_points.l1_handler),
+    ] {
+        pretty_assertions::assert_eq!(
+            rpc_entry_points.into_iter().map(EntryPointWrapper::from).collect::<Vec<_>>(),
+            contract_class.entry_points_by_type.get(&entry_point_type).map(|e| e.to_vec()).unwrap_or_default()
+        );
+    }
+}
+
+#[test]
+fn test_program_wrapper_to_cairo_lang_json_matches_the_compiled_program() {
+    // Given
+    let compiled: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(TEST_CONTRACT_PATH).unwrap()).unwrap();
+    let compiled = &compiled["program"];
+    let program = ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap().program;
+
+    // When
+    let json = program.to_cairo_lang_json().unwrap();
+
+    // Then
+    for field in ["builtins", "data", "hints", "main_scope", "prime", "reference_manager"] {
+        pretty_assertions::assert_eq!(json[field], compiled[field], "{field}");
+    }
+    let program_bytes = serde_json::to_vec(&json).unwrap();
+    pretty_assertions::assert_eq!(
+        ProgramWrapper::try_from(Program::from_bytes(&program_bytes, None).unwrap()).unwrap(),
+        program
+    );
+}
+
+#[test]
+fn test_entrypoint_selector_constants_match_hashed_names() {
+    let selectors = [
+        (EntryPointSelectorWrapper::EXECUTE, "__execute__"),
+        (EntryPointSelectorWrapper::VALIDATE, "__validate__"),
+        (EntryPointSelectorWrapper::VALIDATE_DECLARE, "__validate_declare__"),
+        (EntryPointSelectorWrapper::VALIDATE_DEPLOY, "__validate_deploy__"),
+        (EntryPointSelectorWrapper::CONSTRUCTOR, "constructor"),
+        (EntryPointSelectorWrapper::L1_HANDLER, "__l1_default__"),
+    ];
+
+    for (selector, name) in selectors {
+        pretty_assertions::assert_eq!(selector, EntryPointSelectorWrapper::from(selector_from_name(name)), "{name}");
+    }
+}
+
+#[test]
+fn test_entrypoint_selector_predicates() {
+    assert!(EntryPointSelectorWrapper::EXECUTE.is_execute());
+    assert!(!EntryPointSelectorWrapper::EXECUTE.is_validate());
+    assert!(EntryPointSelectorWrapper::VALIDATE.is_validate());
+    assert!(EntryPointSelectorWrapper::VALIDATE_DECLARE.is_validate());
+    assert!(EntryPointSelectorWrapper::VALIDATE_DEPLOY.is_validate());
+    assert!(!EntryPointSelectorWrapper::VALIDATE.is_execute());
+    assert!(!EntryPointSelectorWrapper::CONSTRUCTOR.is_execute());
+    assert!(!EntryPointSelectorWrapper::CONSTRUCTOR.is_validate());
+}
+
+#[test]
+fn test_chain_id_constants_match_encoded_names() {
+    pretty_assertions::assert_eq!(ChainIdWrapper::SN_MAIN, ChainIdWrapper::from_bytes(b"SN_MAIN").unwrap());
+    pretty_assertions::assert_eq!(ChainIdWrapper::SN_GOERLI, ChainIdWrapper::from_bytes(b"SN_GOERLI").unwrap());
+}
+
+#[test]
+fn test_chain_id_from_str() {
+    pretty_assertions::assert_eq!("SN_MAIN".parse(), Ok(ChainIdWrapper::SN_MAIN));
+    pretty_assertions::assert_eq!("SN_GOERLI".parse(), Ok(ChainIdWrapper::SN_GOERLI));
+    pretty_assertions::assert_eq!("0x534e5f474f45524c49".parse(), Ok(ChainIdWrapper::SN_GOERLI));
+    pretty_assertions::assert_eq!("0x1".parse(), Ok(ChainIdWrapper(Felt252Wrapper::ONE)));
+    pretty_assertions::assert_eq!("SN_UNKNOWN".parse::<ChainIdWrapper>(), Err(Felt252WrapperError::InvalidCharacter));
+}
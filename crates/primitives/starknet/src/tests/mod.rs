@@ -1,5 +1,6 @@
 pub mod crypto;
 pub mod execution;
 pub mod starknet_serde;
+pub mod traits;
 pub mod transaction;
 pub mod utils;
@@ -1,4 +1,12 @@
+use sp_core::U256;
+
+use crate::execution::types::{
+    ChainId, EntryPointSelector, EntryPointTypeWrapper, Felt252Wrapper, Felt252WrapperError,
+};
+use crate::starknet_serde::feeder_gateway::{transaction_from_feeder_gateway_json, FeederGatewayConversionError};
+use crate::starknet_serde::gateway::{AddTransactionResponse, TransactionHashMismatch};
 use crate::starknet_serde::{transaction_from_json, DeserializeCallEntrypointError, DeserializeTransactionError};
+use crate::transaction::types::{DeployAccountTransaction, InvokeTransaction, Transaction, TxType};
 
 #[test]
 fn test_missing_not_optional_field() {
@@ -171,3 +179,346 @@ fn test_wrong_entrypoint_type() {
         Err(DeserializeTransactionError::InvalidCallEntryPoint(DeserializeCallEntrypointError::InvalidEntryPointType))
     ));
 }
+
+#[test]
+fn test_feeder_gateway_invoke_v1_without_max_fee() {
+    let json_content: &str = r#"{
+      "type": "INVOKE_FUNCTION",
+      "transaction_hash": "0x1",
+      "version": "0x1",
+      "signature": ["0x2", "0x3"],
+      "nonce": "0x4",
+      "sender_address": "0x5",
+      "calldata": ["0x6", "0x7"]
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content).unwrap();
+
+    pretty_assertions::assert_eq!(transaction.tx_type, TxType::Invoke);
+    pretty_assertions::assert_eq!(transaction.version, 1);
+    pretty_assertions::assert_eq!(transaction.hash, Felt252Wrapper::from(1_u64));
+    pretty_assertions::assert_eq!(transaction.signature.to_vec(), vec![Felt252Wrapper::TWO, Felt252Wrapper::THREE]);
+    pretty_assertions::assert_eq!(transaction.nonce, Felt252Wrapper::from(4_u64));
+    pretty_assertions::assert_eq!(transaction.sender_address, Felt252Wrapper::from(5_u64));
+    pretty_assertions::assert_eq!(
+        transaction.call_entrypoint.calldata.to_vec(),
+        vec![Felt252Wrapper::from(6_u64), Felt252Wrapper::from(7_u64)]
+    );
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.entrypoint_selector, None);
+    pretty_assertions::assert_eq!(transaction.max_fee, Felt252Wrapper::ZERO);
+}
+
+#[test]
+fn test_feeder_gateway_invoke_v0_with_decimal_values() {
+    let json_content: &str = r#"{
+      "type": "INVOKE_FUNCTION",
+      "transaction_hash": "1",
+      "max_fee": "10",
+      "contract_address": "2",
+      "entry_point_selector": "3",
+      "calldata": ["4"]
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content).unwrap();
+
+    pretty_assertions::assert_eq!(transaction.version, 0);
+    pretty_assertions::assert_eq!(transaction.sender_address, Felt252Wrapper::TWO);
+    pretty_assertions::assert_eq!(
+        transaction.call_entrypoint.entrypoint_selector,
+        Some(EntryPointSelector(Felt252Wrapper::THREE))
+    );
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.calldata.to_vec(), vec![Felt252Wrapper::from(4_u64)]);
+    pretty_assertions::assert_eq!(transaction.max_fee, Felt252Wrapper::from(10_u64));
+}
+
+#[test]
+fn test_feeder_gateway_rejects_decimal_values_over_the_modulus() {
+    let json_content: &str = r#"{
+      "type": "INVOKE_FUNCTION",
+      "transaction_hash": "1",
+      "contract_address": "2",
+      "entry_point_selector": "3",
+      "calldata": ["3618502788666131213697322783095070105623107215331596699973092056135872020481"]
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content);
+
+    assert!(matches!(
+        transaction,
+        Err(FeederGatewayConversionError::InvalidFelt { field: "calldata", error: Felt252WrapperError::OutOfRange })
+    ));
+}
+
+#[test]
+fn test_feeder_gateway_declare_v2() {
+    let json_content: &str = r#"{
+      "type": "DECLARE",
+      "transaction_hash": "0x1",
+      "version": "0x2",
+      "max_fee": "0x10",
+      "signature": [],
+      "nonce": "0x2",
+      "class_hash": "0x3",
+      "compiled_class_hash": "0x4",
+      "sender_address": "0x5"
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content).unwrap();
+
+    pretty_assertions::assert_eq!(transaction.tx_type, TxType::Declare);
+    pretty_assertions::assert_eq!(transaction.version, 2);
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.class_hash, Some(Felt252Wrapper::THREE));
+    pretty_assertions::assert_eq!(transaction.contract_class, None);
+}
+
+#[test]
+fn test_feeder_gateway_declare_v2_requires_compiled_class_hash() {
+    let json_content: &str = r#"{
+      "type": "DECLARE",
+      "transaction_hash": "0x1",
+      "version": "0x2",
+      "nonce": "0x2",
+      "class_hash": "0x3",
+      "sender_address": "0x5"
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content);
+
+    assert!(matches!(transaction, Err(FeederGatewayConversionError::MissingField("compiled_class_hash"))));
+}
+
+#[test]
+fn test_feeder_gateway_deploy_account() {
+    let json_content: &str = r#"{
+      "type": "DEPLOY_ACCOUNT",
+      "transaction_hash": "0x1",
+      "version": "0x1",
+      "max_fee": "0x10",
+      "signature": ["0x2"],
+      "nonce": "0x0",
+      "class_hash": "0x3",
+      "contract_address": "0x4",
+      "contract_address_salt": "0x5",
+      "constructor_calldata": ["0x6"]
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content).unwrap();
+
+    pretty_assertions::assert_eq!(transaction.tx_type, TxType::DeployAccount);
+    pretty_assertions::assert_eq!(transaction.sender_address, Felt252Wrapper::from(4_u64));
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.class_hash, Some(Felt252Wrapper::THREE));
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.calldata.to_vec(), vec![Felt252Wrapper::from(6_u64)]);
+    pretty_assertions::assert_eq!(transaction.contract_address_salt, Some(Felt252Wrapper::from(5_u64)));
+}
+
+#[test]
+fn test_feeder_gateway_legacy_deploy() {
+    let json_content: &str = r#"{
+      "type": "DEPLOY",
+      "transaction_hash": "0x1",
+      "version": "0x0",
+      "class_hash": "0x3",
+      "contract_address": "0x4",
+      "contract_address_salt": "0x5",
+      "constructor_calldata": ["0x6"]
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content).unwrap();
+
+    pretty_assertions::assert_eq!(transaction.tx_type, TxType::Deploy);
+    pretty_assertions::assert_eq!(transaction.hash, Felt252Wrapper::ONE);
+    pretty_assertions::assert_eq!(transaction.sender_address, Felt252Wrapper::from(4_u64));
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.entrypoint_type, EntryPointTypeWrapper::Constructor);
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.class_hash, Some(Felt252Wrapper::THREE));
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.calldata.to_vec(), vec![Felt252Wrapper::from(6_u64)]);
+    pretty_assertions::assert_eq!(transaction.contract_address_salt, Some(Felt252Wrapper::from(5_u64)));
+    pretty_assertions::assert_eq!(transaction.max_fee, Felt252Wrapper::ZERO);
+}
+
+#[test]
+fn test_feeder_gateway_l1_handler() {
+    let json_content: &str = r#"{
+      "type": "L1_HANDLER",
+      "transaction_hash": "0x1",
+      "version": "0x0",
+      "nonce": "0x2",
+      "contract_address": "0x3",
+      "entry_point_selector": "0x4",
+      "calldata": ["0x5", "0x6"]
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content).unwrap();
+
+    pretty_assertions::assert_eq!(transaction.tx_type, TxType::L1Handler);
+    pretty_assertions::assert_eq!(transaction.nonce, Felt252Wrapper::TWO);
+    pretty_assertions::assert_eq!(transaction.sender_address, Felt252Wrapper::THREE);
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.entrypoint_type, EntryPointTypeWrapper::L1Handler);
+    pretty_assertions::assert_eq!(
+        transaction.call_entrypoint.entrypoint_selector,
+        Some(EntryPointSelector(Felt252Wrapper::from(4_u64)))
+    );
+    assert!(transaction.signature.is_empty());
+}
+
+#[test]
+fn test_feeder_gateway_unsupported_version() {
+    let json_content: &str = r#"{
+      "type": "DEPLOY_ACCOUNT",
+      "transaction_hash": "0x1",
+      "version": "0x3",
+      "nonce": "0x0",
+      "class_hash": "0x3",
+      "contract_address": "0x4",
+      "contract_address_salt": "0x5",
+      "constructor_calldata": []
+    }"#;
+
+    let transaction = transaction_from_feeder_gateway_json(json_content);
+
+    assert!(matches!(
+        transaction,
+        Err(FeederGatewayConversionError::UnsupportedVersion { tx_type: TxType::DeployAccount, version: 3 })
+    ));
+}
+
+#[test]
+fn test_invoke_transaction_serializes_to_spec_field_names() {
+    // Given
+    let transaction = InvokeTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::THREE,
+        calldata: frame_support::bounded_vec![Felt252Wrapper::ONE],
+        nonce: Felt252Wrapper::ZERO,
+        signature: frame_support::bounded_vec![Felt252Wrapper::TWO],
+        max_fee: Felt252Wrapper::ONE,
+        entry_point_selector: None,
+    }
+    .from_invoke(ChainId::SN_GOERLI)
+    .unwrap();
+
+    // When
+    let json = serde_json::to_value(transaction).unwrap();
+
+    // Then
+    let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    pretty_assertions::assert_eq!(
+        keys,
+        vec!["calldata", "max_fee", "nonce", "sender_address", "signature", "transaction_hash", "type", "version"]
+    );
+    pretty_assertions::assert_eq!(json["type"], "INVOKE");
+}
+
+#[test]
+fn test_gateway_add_deploy_account_transaction_response() {
+    // Given
+    let json_content: &str = r#"{
+      "code": "TRANSACTION_RECEIVED",
+      "transaction_hash": "0x32b272b6d0d584305a460197aa849b5c7a9a85903b66e9d3e1afa2427ef093e",
+      "address": "0x1b1d4f9bd0c1e9b8a8c8a9ba5e4f5cb7b8cf4d3ad3f8d1e4d3c2b1a0f9e8d7c"
+    }"#;
+
+    // When
+    let response: AddTransactionResponse = serde_json::from_str(json_content).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        response,
+        AddTransactionResponse {
+            transaction_hash: Felt252Wrapper::from_hex_be(
+                "0x32b272b6d0d584305a460197aa849b5c7a9a85903b66e9d3e1afa2427ef093e"
+            )
+            .unwrap(),
+            contract_address: Some(
+                Felt252Wrapper::from_hex_be("0x1b1d4f9bd0c1e9b8a8c8a9ba5e4f5cb7b8cf4d3ad3f8d1e4d3c2b1a0f9e8d7c")
+                    .unwrap()
+            ),
+            class_hash: None,
+        }
+    );
+}
+
+#[test]
+fn test_gateway_add_declare_transaction_response() {
+    // Given
+    let json_content: &str = r#"{
+      "code": "TRANSACTION_RECEIVED",
+      "transaction_hash": "0x1",
+      "class_hash": "0x2"
+    }"#;
+
+    // When
+    let response: AddTransactionResponse = serde_json::from_str(json_content).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(response.transaction_hash, Felt252Wrapper::ONE);
+    pretty_assertions::assert_eq!(response.class_hash, Some(Felt252Wrapper::TWO));
+    pretty_assertions::assert_eq!(response.contract_address, None);
+}
+
+#[test]
+fn test_gateway_add_transaction_response_verifies_the_hash() {
+    // Given
+    let response =
+        AddTransactionResponse { transaction_hash: Felt252Wrapper::ONE, contract_address: None, class_hash: None };
+    let matching = Transaction { hash: Felt252Wrapper::ONE, ..Transaction::default() };
+    let mismatching = Transaction { hash: Felt252Wrapper::TWO, ..Transaction::default() };
+
+    // When
+    let ok = response.verify_hash(&matching);
+    let err = response.verify_hash(&mismatching);
+
+    // Then
+    pretty_assertions::assert_eq!(ok, Ok(()));
+    pretty_assertions::assert_eq!(
+        err,
+        Err(TransactionHashMismatch { local: Felt252Wrapper::TWO, gateway: Felt252Wrapper::ONE })
+    );
+}
+
+#[test]
+fn test_deploy_account_salt_serializes_as_padded_hex() {
+    // Given
+    let transaction = DeployAccountTransaction { salt: U256::from(0x1a2b_u64), ..DeployAccountTransaction::default() };
+
+    // When
+    let json = serde_json::to_value(&transaction).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        json["salt"],
+        serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000001a2b")
+    );
+}
+
+#[test]
+fn test_deploy_account_salt_round_trips() {
+    // Given
+    let transaction =
+        DeployAccountTransaction { salt: U256::from_big_endian(&[0xab; 31]), ..DeployAccountTransaction::default() };
+
+    // When
+    let json = serde_json::to_string(&transaction).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(serde_json::from_str::<DeployAccountTransaction>(&json).unwrap(), transaction);
+}
+
+#[test]
+fn test_deploy_account_salt_deserializes_unpadded_hex() {
+    // Given
+    let mut json = serde_json::to_value(DeployAccountTransaction::default()).unwrap();
+
+    // When
+    json["salt"] = serde_json::json!("0x1a2b");
+    let unpadded = serde_json::from_value::<DeployAccountTransaction>(json.clone());
+    json["salt"] = serde_json::json!("1a2b");
+    let unprefixed = serde_json::from_value::<DeployAccountTransaction>(json.clone());
+    json["salt"] = serde_json::json!(format!("0x{}", "1".repeat(65)));
+    let too_long = serde_json::from_value::<DeployAccountTransaction>(json);
+
+    // Then
+    pretty_assertions::assert_eq!(unpadded.unwrap().salt, U256::from(0x1a2b_u64));
+    assert!(unprefixed.is_err());
+    assert!(too_long.is_err());
+}
@@ -1,11 +1,15 @@
 use blockifier::execution::contract_class::ContractClass;
 use blockifier::state::errors::StateError;
 use blockifier::state::state_api::StateReader;
-use crate::state::*;
+use frame_support::traits::Get;
 use starknet_api::api_core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::hash::StarkFelt;
 use starknet_api::state::StorageKey;
 
+use crate::execution::types::Felt252Wrapper;
+use crate::state::*;
+use crate::transaction::types::{MaxArraySize, StateDiffError, StateDiffWrapper};
+
 #[test]
 fn test_get_storage_at() {
     let mut state = DictStateReader::default();
@@ -75,3 +79,37 @@ fn test_get_contract_class_undeclared_class_hash() {
         panic!("Unexpected error");
     }
 }
+
+#[test]
+fn test_state_diff_groups_storage_updates_by_contract() {
+    let mut state_diff = StateDiffWrapper::default();
+
+    state_diff.register_storage_update(Felt252Wrapper::ONE, Felt252Wrapper::ONE, Felt252Wrapper::TWO).unwrap();
+    state_diff.register_storage_update(Felt252Wrapper::ONE, Felt252Wrapper::TWO, Felt252Wrapper::THREE).unwrap();
+    state_diff.register_storage_update(Felt252Wrapper::TWO, Felt252Wrapper::ONE, Felt252Wrapper::ONE).unwrap();
+    state_diff.register_nonce_update(Felt252Wrapper::ONE, Felt252Wrapper::TWO).unwrap();
+    state_diff.register_deployed_contract(Felt252Wrapper::THREE, Felt252Wrapper::ONE).unwrap();
+    state_diff.register_declared_class(Felt252Wrapper::ONE).unwrap();
+
+    assert_eq!(state_diff.storage_diffs.len(), 2);
+    assert_eq!(
+        state_diff.storage_diffs[0].1.to_vec(),
+        vec![(Felt252Wrapper::ONE, Felt252Wrapper::TWO), (Felt252Wrapper::TWO, Felt252Wrapper::THREE)]
+    );
+    assert_eq!(state_diff.storage_diffs[1].1.to_vec(), vec![(Felt252Wrapper::ONE, Felt252Wrapper::ONE)]);
+    assert_eq!(state_diff.nonces.to_vec(), vec![(Felt252Wrapper::ONE, Felt252Wrapper::TWO)]);
+    assert_eq!(state_diff.deployed_contracts.to_vec(), vec![(Felt252Wrapper::THREE, Felt252Wrapper::ONE)]);
+    assert_eq!(state_diff.declared_classes.to_vec(), vec![Felt252Wrapper::ONE]);
+}
+
+#[test]
+fn test_state_diff_fails_when_too_many_classes_are_declared() {
+    let mut state_diff = StateDiffWrapper::default();
+    let max_classes: u32 = MaxArraySize::get();
+
+    for _ in 0..max_classes {
+        state_diff.register_declared_class(Felt252Wrapper::ONE).unwrap();
+    }
+
+    assert_eq!(state_diff.register_declared_class(Felt252Wrapper::ONE), Err(StateDiffError::DeclaredClassError));
+}
@@ -0,0 +1,56 @@
+use frame_support::traits::{ConstU32, Get};
+use frame_support::BoundedVec;
+
+use crate::execution::types::Felt252Wrapper;
+use crate::traits::bounded::BoundedCapacity;
+use crate::transaction::types::{EventAccumulator, EventWrapper, MaxArraySize};
+
+#[test]
+fn test_remaining_capacity_of_a_partially_filled_bounded_vec() {
+    // Given
+    let signature: BoundedVec<Felt252Wrapper, ConstU32<3>> = BoundedVec::truncate_from(vec![Felt252Wrapper::ONE]);
+
+    // When
+    let remaining = signature.remaining_capacity();
+
+    // Then
+    pretty_assertions::assert_eq!(remaining, 2);
+    assert!(signature.can_fit(2));
+    assert!(!signature.can_fit(3));
+}
+
+#[test]
+fn test_full_bounded_vec_has_no_remaining_capacity() {
+    // Given
+    let signature: BoundedVec<Felt252Wrapper, MaxArraySize> =
+        BoundedVec::truncate_from(vec![Felt252Wrapper::ONE; MaxArraySize::get() as usize]);
+
+    // When
+    let remaining = signature.remaining_capacity();
+
+    // Then
+    pretty_assertions::assert_eq!(remaining, 0);
+    assert!(signature.can_fit(0));
+    assert!(!signature.can_fit(1));
+}
+
+#[test]
+fn test_event_accumulator_can_fit_up_to_its_bound() {
+    // Given
+    let max_events = MaxArraySize::get() as usize;
+    let mut accumulator = EventAccumulator::new();
+    for _ in 0..max_events - 1 {
+        accumulator.push(EventWrapper::default()).unwrap();
+    }
+
+    // When
+    let can_fit_one = accumulator.can_fit(1);
+    let can_fit_two = accumulator.can_fit(2);
+    accumulator.push(EventWrapper::default()).unwrap();
+
+    // Then
+    assert!(can_fit_one);
+    assert!(!can_fit_two);
+    pretty_assertions::assert_eq!(accumulator.remaining_capacity(), 0);
+    assert!(accumulator.push(EventWrapper::default()).is_err());
+}
@@ -1,23 +1,53 @@
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
 use core::str::FromStr;
 
 use blockifier::abi::abi_utils::selector_from_name;
+use blockifier::execution::entry_point::{CallEntryPoint, CallExecution, CallInfo, OrderedEvent, Retdata};
+use blockifier::transaction::objects::{ResourcesMapping, TransactionExecutionInfo};
+use blockifier::transaction::transaction_types::TransactionType;
+use frame_support::traits::Get;
 use frame_support::{bounded_vec, BoundedVec};
-use sp_core::U256;
-use starknet_api::api_core::{ContractAddress, PatriciaKey};
+use sp_core::{ConstU32, U256};
+use starknet_api::api_core::{ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::hash::{StarkFelt, StarkHash};
 use starknet_api::patricia_key;
 use starknet_api::transaction::{
-    Event, EventContent, EventData, EventKey, Fee, InvokeTransactionOutput, TransactionHash, TransactionOutput,
-    TransactionReceipt,
+    Calldata, DeployTransactionOutput, Event, EventContent, EventData, EventKey, Fee, InvokeTransactionOutput,
+    TransactionHash, TransactionOutput, TransactionReceipt, TransactionSignature,
+};
+use starknet_core::types::{
+    BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV1, BroadcastedDeployAccountTransaction,
+    BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV0, BroadcastedInvokeTransactionV1,
+    BroadcastedTransaction, CompressedLegacyContractClass, EmittedEvent, FieldElement, LegacyEntryPointsByType,
+    MaybePendingTransactionReceipt, PendingTransactionReceipt, TransactionStatus,
 };
 
+use crate::block::{enumerate_block_events, group_events_by_address, total_events, total_fees};
+use crate::crypto::commitment::calculate_transaction_hash;
+use crate::crypto::hash::pedersen::PedersenHasher;
 use crate::execution::call_entrypoint_wrapper::{CallEntryPointWrapper, MaxCalldataSize};
-use crate::execution::types::{ContractAddressWrapper, Felt252Wrapper};
-use crate::transaction::constants;
+use crate::execution::types::{
+    ChainId, ContractAddressWrapper, ContractClassWrapper, EntryPointSelector as EntryPointSelectorWrapper,
+    EntryPointTypeWrapper, Felt252Wrapper,
+};
+use crate::fees::FeeTokenConfig;
+use crate::tests::utils::{get_contract_class, TEST_CONTRACT_PATH};
+use crate::traits::hash::CryptoHasherT;
+use crate::traits::limits::{DefaultTxLimits, TxLimits};
+use crate::transaction::bloom::Bloom;
+use crate::transaction::compact::{CompactDecodingError, COMPACT_FORMAT_VERSION};
 use crate::transaction::types::{
-    EventError, EventWrapper, MaxArraySize, Transaction, TransactionReceiptWrapper, TxType,
+    try_into_rpc_transactions, AccountTxType, CallTraceEvent, CallTraceWrapper, DeclareTransaction,
+    DeployAccountTransaction, DeployTransaction, EventAccumulator, EventError, EventOverflow, EventWrapper,
+    ExecutionStatus, FeeUnit, FieldDiff, IndexedEvent, IntoRpcTransactions, InvokeTransaction, MaxArraySize,
+    RPCTransactionConversionError, SimulationFlags, Transaction, TransactionConversionError,
+    TransactionExecutionErrorWrapper, TransactionExecutionInfoWrapper, TransactionReceiptWrapper, TransactionSummary,
+    TransactionTraceWrapper, TransactionValidationErrorWrapper, TxType, ValidationContext,
 };
+use crate::transaction::validation::VALIDATE_RETURN_MAGIC;
+use crate::transaction::{constants, dedup_by_hash, total_execution_time, validate_events, validate_nonce_sequence};
 
 #[test]
 fn test_validate_entry_point_selector_is_declare() {
@@ -82,6 +112,24 @@ fn get_test_contract_address_salt() -> U256 {
     U256::from_str("0x000000000000000000000000000000000000000000000000000000000000dead").unwrap()
 }
 
+#[test]
+fn test_signature_matches() {
+    // Given
+    let tx = Transaction::default();
+
+    // When
+    let same = tx.signature_matches(&[Felt252Wrapper::ONE, Felt252Wrapper::ONE]);
+    let different = tx.signature_matches(&[Felt252Wrapper::ONE, Felt252Wrapper::TWO]);
+    let shorter = tx.signature_matches(&[Felt252Wrapper::ONE]);
+    let longer = tx.signature_matches(&[Felt252Wrapper::ONE, Felt252Wrapper::ONE, Felt252Wrapper::ONE]);
+
+    // Then
+    assert!(same);
+    assert!(!different);
+    assert!(!shorter);
+    assert!(!longer);
+}
+
 #[test]
 fn test_validate_entrypoint_calldata_declare() {
     // Given
@@ -108,7 +156,7 @@ fn test_validate_entrypoint_calldata_declare() {
 fn test_validate_entrypoint_calldata_deploy_account() {
     // Given
     let tx = Transaction {
-        contract_address_salt: Some(get_test_contract_address_salt()),
+        contract_address_salt: Some(Felt252Wrapper::try_from(get_test_contract_address_salt()).unwrap()),
         call_entrypoint: CallEntryPointWrapper {
             class_hash: Some(get_test_class_hash()),
             calldata: get_test_calldata(),
@@ -242,6 +290,7 @@ fn test_try_into_transaction_receipt_wrapper() {
     // Check if the transaction hash, actual fee, and tx type are correctly converted
     assert_eq!(transaction_receipt_wrapper.transaction_hash, Felt252Wrapper::try_from(&[1; 32]).unwrap());
     assert_eq!(transaction_receipt_wrapper.actual_fee, Felt252Wrapper::ZERO);
+    assert_eq!(transaction_receipt_wrapper.fee_unit, FeeUnit::Wei);
     assert_eq!(transaction_receipt_wrapper.tx_type, TxType::Invoke);
 
     // Check if the events are correctly converted
@@ -265,6 +314,27 @@ fn test_try_into_transaction_receipt_wrapper() {
     assert_eq!(events.get(1).unwrap().from_address, event_wrapper2.from_address);
 }
 
+#[test]
+fn test_try_into_transaction_receipt_wrapper_keeps_the_deploy_type() {
+    // Given
+    let transaction_receipt = &TransactionReceipt {
+        transaction_hash: TransactionHash(StarkFelt::new([1; 32]).unwrap()),
+        output: TransactionOutput::Deploy(DeployTransactionOutput {
+            actual_fee: Fee(0),
+            messages_sent: vec![],
+            events: vec![],
+        }),
+        block_hash: BlockHash(StarkFelt::new([0; 32]).unwrap()),
+        block_number: BlockNumber(0),
+    };
+
+    // When
+    let result: Result<TransactionReceiptWrapper, EventError> = transaction_receipt.try_into();
+
+    // Then
+    assert_eq!(result.unwrap().tx_type, TxType::Deploy);
+}
+
 #[test]
 fn test_try_into_transaction_receipt_wrapper_with_too_many_events() {
     let events: Vec<Event> = (0..=10001)
@@ -297,21 +367,360 @@ fn test_try_into_transaction_receipt_wrapper_with_too_many_events() {
     assert_eq!(result.unwrap_err(), EventError::TooManyEvents);
 }
 
+fn ordered_event(order: usize, key: u64) -> OrderedEvent {
+    OrderedEvent { order, event: EventContent { keys: vec![EventKey(StarkFelt::from(key))], data: EventData(vec![]) } }
+}
+
+fn call_info(address: u64, events: Vec<OrderedEvent>, inner_calls: Vec<CallInfo>) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint {
+            storage_address: ContractAddress(PatriciaKey::try_from(StarkFelt::from(address)).unwrap()),
+            ..CallEntryPoint::default()
+        },
+        execution: CallExecution { events, ..CallExecution::default() },
+        inner_calls,
+        ..CallInfo::default()
+    }
+}
+
+fn execution_info(
+    validate_call_info: Option<CallInfo>,
+    execute_call_info: Option<CallInfo>,
+    fee_transfer_call_info: Option<CallInfo>,
+) -> TransactionExecutionInfoWrapper {
+    TransactionExecutionInfoWrapper {
+        validate_call_info,
+        execute_call_info,
+        fee_transfer_call_info,
+        actual_fee: Fee(42),
+        actual_resources: BTreeMap::new(),
+        revert_error: None,
+        state_diff: None,
+        execution_duration_micros: None,
+    }
+}
+
+#[test]
+fn test_execution_info_wrapper_from_blockifier_execution_info() {
+    // Given
+    let call_info = |address: &str| CallInfo {
+        call: CallEntryPoint { storage_address: ContractAddress(patricia_key!(address)), ..CallEntryPoint::default() },
+        ..CallInfo::default()
+    };
+    let info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info("0x1")),
+        execute_call_info: Some(call_info("0x2")),
+        fee_transfer_call_info: Some(call_info("0x3")),
+        actual_fee: Fee(42),
+        actual_resources: ResourcesMapping(
+            [(String::from("l1_gas_usage"), 1), (String::from("n_steps"), 2)].into_iter().collect(),
+        ),
+    };
+
+    // When
+    let wrapper = TransactionExecutionInfoWrapper::from(info);
+
+    // Then
+    pretty_assertions::assert_eq!(wrapper.validate_call_info, Some(call_info("0x1")));
+    pretty_assertions::assert_eq!(wrapper.execute_call_info, Some(call_info("0x2")));
+    pretty_assertions::assert_eq!(wrapper.fee_transfer_call_info, Some(call_info("0x3")));
+    pretty_assertions::assert_eq!(wrapper.actual_fee, Fee(42));
+    pretty_assertions::assert_eq!(
+        wrapper.actual_resources,
+        BTreeMap::from([(String::from("l1_gas_usage"), 1), (String::from("n_steps"), 2)])
+    );
+    pretty_assertions::assert_eq!(wrapper.revert_error, None);
+    pretty_assertions::assert_eq!(wrapper.state_diff, None);
+    pretty_assertions::assert_eq!(wrapper.execution_duration_micros(), None);
+}
+
+#[test]
+fn total_execution_time_sums_the_measured_durations() {
+    // Given
+    let infos = [
+        TransactionExecutionInfoWrapper { execution_duration_micros: Some(10), ..execution_info(None, None, None) },
+        execution_info(None, None, None),
+        TransactionExecutionInfoWrapper { execution_duration_micros: Some(32), ..execution_info(None, None, None) },
+    ];
+
+    // When
+    let total = total_execution_time(&infos);
+
+    // Then
+    pretty_assertions::assert_eq!(total, 42);
+    pretty_assertions::assert_eq!(infos[1].execution_duration_micros(), None);
+    pretty_assertions::assert_eq!(total_execution_time(&[]), 0);
+}
+
+#[test]
+fn into_receipt_collects_events_in_call_order_and_pre_order() {
+    // Given
+    let execute = call_info(
+        2,
+        vec![ordered_event(1, 2), ordered_event(0, 1)],
+        vec![call_info(3, vec![ordered_event(0, 3)], vec![call_info(4, vec![ordered_event(0, 4)], vec![])])],
+    );
+    let info = execution_info(
+        Some(call_info(1, vec![ordered_event(0, 0)], vec![])),
+        Some(execute),
+        Some(call_info(5, vec![ordered_event(0, 5)], vec![])),
+    );
+    let tx_hash = Felt252Wrapper::from(7_u64);
+
+    // When
+    let receipt = info.into_receipt(tx_hash, TxType::Invoke).unwrap();
+
+    // Then
+    let emitted: Vec<(Felt252Wrapper, Felt252Wrapper)> =
+        receipt.events.iter().map(|event| (event.keys[0], event.from_address)).collect();
+    let expected: Vec<(Felt252Wrapper, Felt252Wrapper)> = [(0_u64, 1_u64), (1, 2), (2, 2), (3, 3), (4, 4), (5, 5)]
+        .into_iter()
+        .map(|(key, address)| (Felt252Wrapper::from(key), Felt252Wrapper::from(address)))
+        .collect();
+    pretty_assertions::assert_eq!(emitted, expected);
+    assert!(receipt.events.iter().all(|event| event.transaction_hash == tx_hash));
+    assert_eq!(receipt.transaction_hash, tx_hash);
+    assert_eq!(receipt.tx_type, TxType::Invoke);
+    assert_eq!(receipt.block_number, None);
+    assert_eq!(receipt.block_hash, None);
+    assert_eq!(receipt.actual_fee, Felt252Wrapper::from(42_u64));
+    assert_eq!(receipt.revert_reason, None);
+}
+
+#[test]
+fn into_receipt_keeps_the_revert_reason() {
+    // Given
+    let mut info = execution_info(None, None, None);
+    info.revert_error = Some("out of gas".into());
+
+    // When
+    let receipt = info.into_receipt(Felt252Wrapper::ONE, TxType::Invoke).unwrap();
+
+    // Then
+    assert!(receipt.events.is_empty());
+    assert_eq!(receipt.revert_reason.unwrap().into_inner(), b"out of gas".to_vec());
+}
+
+#[test]
+fn into_receipt_of_a_succeeded_invoke_has_no_revert_reason() {
+    // Given
+    let info = execution_info(None, Some(call_info(1, vec![], vec![])), None);
+
+    // When
+    let receipt = info.into_receipt(Felt252Wrapper::ONE, TxType::Invoke).unwrap();
+
+    // Then
+    assert_eq!(receipt.execution_status, ExecutionStatus::Succeeded);
+    assert_eq!(receipt.revert_reason, None);
+    assert_eq!(serde_json::to_value(&receipt).unwrap()["execution_status"], "SUCCEEDED");
+}
+
+#[test]
+fn into_receipt_of_a_reverted_invoke_has_a_revert_reason() {
+    // Given
+    let mut info = execution_info(None, Some(call_info(1, vec![], vec![])), None);
+    info.revert_error = Some("assertion failed".into());
+
+    // When
+    let receipt = info.into_receipt(Felt252Wrapper::ONE, TxType::Invoke).unwrap();
+
+    // Then
+    assert_eq!(receipt.execution_status, ExecutionStatus::Reverted);
+    assert_eq!(receipt.revert_reason.as_ref().unwrap().to_vec(), b"assertion failed".to_vec());
+    assert_eq!(serde_json::to_value(&receipt).unwrap()["execution_status"], "REVERTED");
+}
+
+#[test]
+fn into_receipt_fails_with_too_many_events() {
+    // Given
+    let max_events = MaxArraySize::get() as usize;
+    let execute = call_info(1, (0..max_events).map(|order| ordered_event(order, 0)).collect(), vec![]);
+    let info = execution_info(None, Some(execute), Some(call_info(2, vec![ordered_event(0, 0)], vec![])));
+
+    // When
+    let result = info.into_receipt(Felt252Wrapper::ONE, TxType::Invoke);
+
+    // Then
+    assert_eq!(result.unwrap_err(), EventError::TooManyEvents);
+}
+
+#[test]
+fn validate_events_accepts_events_that_fit_in_the_receipt() {
+    // Given
+    let max_size = MaxArraySize::get() as usize;
+    let execute = call_info(1, (0..max_size - 1).map(|order| ordered_event(order, 0)).collect(), vec![]);
+    let info = execution_info(None, Some(execute), Some(call_info(2, vec![ordered_event(0, 0)], vec![])));
+
+    // When
+    let result = validate_events(&info.events());
+
+    // Then
+    assert_eq!(result, Ok(()));
+    let receipt = info.into_receipt(Felt252Wrapper::ONE, TxType::Invoke).unwrap();
+    assert_eq!(receipt.events.len(), max_size);
+    assert!(!receipt.events_truncated);
+    assert!(serde_json::to_value(&receipt).unwrap().get("events_truncated").is_none());
+}
+
+#[test]
+fn validate_events_rejects_events_that_dont_fit_in_the_receipt() {
+    // Given
+    let max_size = MaxArraySize::get() as usize;
+    let event = |keys: usize, data: usize| EventContent {
+        keys: vec![EventKey(StarkFelt::from(1_u64)); keys],
+        data: EventData(vec![StarkFelt::from(2_u64); data]),
+    };
+
+    // When
+    let too_many_events = validate_events(&vec![event(1, 0); max_size + 1]);
+    let too_many_keys = validate_events(&[event(1, 0), event(max_size + 1, 0)]);
+    let too_much_data = validate_events(&[event(1, max_size + 1)]);
+
+    // Then
+    assert_eq!(too_many_events, Err(EventError::TooManyEvents));
+    assert_eq!(too_many_keys, Err(EventError::InvalidKeys));
+    assert_eq!(too_much_data, Err(EventError::InvalidData));
+}
+
+#[test]
+fn into_truncated_receipt_drops_the_events_that_dont_fit() {
+    // Given
+    let max_size = MaxArraySize::get() as usize;
+    let mut oversized = ordered_event(0, 1);
+    oversized.event.data = EventData(vec![StarkFelt::from(2_u64); max_size + 1]);
+    let validate = call_info(1, vec![oversized], vec![]);
+    let execute = call_info(2, (0..max_size).map(|order| ordered_event(order, 2)).collect(), vec![]);
+    let info = execution_info(Some(validate), Some(execute), Some(call_info(3, vec![ordered_event(0, 3)], vec![])));
+    assert_eq!(validate_events(&info.events()), Err(EventError::TooManyEvents));
+
+    // When
+    let receipt = info.into_truncated_receipt(Felt252Wrapper::ONE, TxType::Invoke);
+
+    // Then
+    assert!(receipt.events_truncated);
+    assert_eq!(receipt.events.len(), max_size);
+    assert!(receipt.events.iter().all(|event| event.from_address == Felt252Wrapper::from(2_u64)));
+    assert_eq!(serde_json::to_value(&receipt).unwrap()["events_truncated"], true);
+}
+
+#[test]
+fn trace_nests_the_calls_and_sorts_their_events() {
+    // Given
+    let mut root = call_info(
+        1,
+        vec![ordered_event(1, 1), ordered_event(0, 0)],
+        vec![call_info(2, vec![ordered_event(2, 2)], vec![call_info(3, vec![ordered_event(3, 3)], vec![])])],
+    );
+    root.call.entry_point_selector = EntryPointSelector(StarkFelt::from(7_u64));
+    root.call.calldata = Calldata(Arc::new(vec![StarkFelt::from(1_u64), StarkFelt::from(2_u64)]));
+    root.execution.retdata = Retdata(vec![StarkFelt::from(3_u64)]);
+    let execution_info = execution_info(None, Some(root), None);
+
+    // When
+    let trace = execution_info.trace();
+
+    // Then
+    let event = |order: u64| CallTraceEvent { order, keys: vec![Felt252Wrapper::from(order)], data: vec![] };
+    let call = |address: u64, events: Vec<CallTraceEvent>, calls: Vec<CallTraceWrapper>| CallTraceWrapper {
+        contract_address: Felt252Wrapper::from(address),
+        entry_point_selector: EntryPointSelectorWrapper::default(),
+        calldata: vec![],
+        result: vec![],
+        events,
+        calls,
+    };
+    let expected = TransactionTraceWrapper {
+        validate_invocation: None,
+        execute_invocation: Some(CallTraceWrapper {
+            entry_point_selector: EntryPointSelectorWrapper(Felt252Wrapper::from(7_u64)),
+            calldata: vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+            result: vec![Felt252Wrapper::THREE],
+            ..call(1, vec![event(0), event(1)], vec![call(2, vec![event(2)], vec![call(3, vec![event(3)], vec![])])])
+        }),
+        fee_transfer_invocation: None,
+    };
+    pretty_assertions::assert_eq!(trace, expected);
+}
+
+#[test]
+fn test_event_accumulator_reports_the_overflowing_event() {
+    // Given
+    let max_events = MaxArraySize::get() as usize;
+    let mut accumulator = EventAccumulator::new();
+    for _ in 0..max_events {
+        accumulator.push(EventWrapper::default()).unwrap();
+    }
+    let overflowing = EventWrapper { from_address: Felt252Wrapper::THREE, ..EventWrapper::default() };
+
+    // When
+    let result = accumulator.push(overflowing);
+
+    // Then
+    pretty_assertions::assert_eq!(
+        result,
+        Err(EventOverflow { index: max_events, from_address: Felt252Wrapper::THREE })
+    );
+    assert_eq!(accumulator.len(), max_events);
+    assert_eq!(EventError::from(result.unwrap_err()), EventError::TooManyEvents);
+}
+
 #[test]
 fn test_event_wrapper_new() {
-    let keys: BoundedVec<Felt252Wrapper, MaxArraySize> =
-        bounded_vec![Felt252Wrapper::ZERO, Felt252Wrapper::try_from(&[1; 32]).unwrap()];
-    let data: BoundedVec<Felt252Wrapper, MaxArraySize> =
-        bounded_vec![Felt252Wrapper::try_from(&[1; 32]).unwrap(), Felt252Wrapper::try_from(&[2; 32]).unwrap()];
+    let keys = vec![Felt252Wrapper::ZERO, Felt252Wrapper::try_from(&[1; 32]).unwrap()];
+    let data = vec![Felt252Wrapper::try_from(&[1; 32]).unwrap(), Felt252Wrapper::try_from(&[2; 32]).unwrap()];
     let from_address = Felt252Wrapper::try_from(&[3; 32]).unwrap();
     let transaction_hash = Felt252Wrapper::try_from(&[4; 32]).unwrap();
 
-    let event_wrapper = EventWrapper::new(keys.clone(), data.clone(), from_address, transaction_hash);
-    let expected_event = EventWrapper { keys, data, from_address, transaction_hash };
+    let event_wrapper = EventWrapper::new(keys.clone(), data.clone(), from_address, transaction_hash).unwrap();
+    let expected_event = EventWrapper {
+        keys: BoundedVec::try_from(keys).unwrap(),
+        data: BoundedVec::try_from(data).unwrap(),
+        from_address,
+        transaction_hash,
+    };
 
     pretty_assertions::assert_eq!(event_wrapper, expected_event);
 }
 
+#[test]
+fn test_event_wrapper_new_without_keys_fails() {
+    let event_wrapper = EventWrapper::new(vec![], vec![Felt252Wrapper::ONE], Felt252Wrapper::ONE, Felt252Wrapper::ONE);
+
+    pretty_assertions::assert_eq!(event_wrapper, Err(EventError::InvalidKeys));
+}
+
+#[test]
+fn test_event_wrapper_new_with_too_many_keys_or_data_fails() {
+    let too_many = vec![Felt252Wrapper::ONE; MaxArraySize::get() as usize + 1];
+
+    let too_many_keys = EventWrapper::new(too_many.clone(), vec![], Felt252Wrapper::ONE, Felt252Wrapper::ONE);
+    let too_much_data =
+        EventWrapper::new(vec![Felt252Wrapper::ONE], too_many, Felt252Wrapper::ONE, Felt252Wrapper::ONE);
+
+    pretty_assertions::assert_eq!(too_many_keys, Err(EventError::InvalidKeys));
+    pretty_assertions::assert_eq!(too_much_data, Err(EventError::InvalidData));
+}
+
+#[test]
+fn test_event_wrapper_builder_without_keys_keeps_the_event() {
+    let event_wrapper = EventWrapper::builder().with_data(vec![Felt252Wrapper::ONE]).build().unwrap();
+
+    pretty_assertions::assert_eq!(event_wrapper.keys, bounded_vec![]);
+    pretty_assertions::assert_eq!(event_wrapper.data, bounded_vec![Felt252Wrapper::ONE]);
+}
+
+#[test]
+fn validate_events_accepts_events_without_keys() {
+    // Given
+    let event = EventContent { keys: vec![], data: EventData(vec![StarkFelt::from(2_u64)]) };
+
+    // When
+    let result = validate_events(&[event]);
+
+    // Then
+    assert_eq!(result, Ok(()));
+}
+
 #[test]
 fn test_event_wrapper_empty() {
     let event_wrapper = EventWrapper::empty();
@@ -373,3 +782,2501 @@ fn test_event_wrapper_builder_with_event_content() {
 
     pretty_assertions::assert_eq!(event_wrapper, expected_event);
 }
+
+#[test]
+fn test_event_wrapper_with_index_into_emitted_event() {
+    // Given
+    let event = EventWrapper::new(
+        vec![Felt252Wrapper::ONE],
+        vec![Felt252Wrapper::TWO, Felt252Wrapper::THREE],
+        Felt252Wrapper::from(4_u64),
+        Felt252Wrapper::from(5_u64),
+    )
+    .unwrap();
+
+    // When
+    let indexed = event.clone().with_index(6, Felt252Wrapper::from(7_u64), 8, 9);
+    let emitted = EmittedEvent::from(indexed.clone());
+
+    // Then
+    pretty_assertions::assert_eq!(
+        indexed,
+        IndexedEvent {
+            event,
+            block_number: 6,
+            block_hash: Felt252Wrapper::from(7_u64),
+            transaction_index: 8,
+            event_index: 9
+        }
+    );
+    pretty_assertions::assert_eq!(
+        emitted,
+        EmittedEvent {
+            from_address: FieldElement::from(4_u64),
+            keys: vec![FieldElement::ONE],
+            data: vec![FieldElement::TWO, FieldElement::THREE],
+            block_hash: FieldElement::from(7_u64),
+            block_number: 6,
+            transaction_hash: FieldElement::from(5_u64),
+        }
+    );
+}
+
+#[test]
+fn test_enumerate_block_events() {
+    // Given
+    let event = |n: u128| EventWrapper { data: bounded_vec![Felt252Wrapper::from(n)], ..EventWrapper::empty() };
+    let receipts = vec![
+        TransactionReceiptWrapper { events: bounded_vec![event(0), event(1)], ..TransactionReceiptWrapper::default() },
+        TransactionReceiptWrapper { events: bounded_vec![], ..TransactionReceiptWrapper::default() },
+        TransactionReceiptWrapper { events: bounded_vec![event(2)], ..TransactionReceiptWrapper::default() },
+    ];
+
+    // When
+    let events: Vec<(u64, &EventWrapper)> = enumerate_block_events(&receipts).collect();
+
+    // Then
+    pretty_assertions::assert_eq!(events.len(), 3);
+    for (expected_index, (index, event)) in events.into_iter().enumerate() {
+        assert_eq!(index, expected_index as u64);
+        assert_eq!(event.data[0], Felt252Wrapper::from(expected_index as u128));
+    }
+}
+
+#[test]
+fn test_group_events_by_address() {
+    // Given
+    let event = |from: u64, n: u128| EventWrapper {
+        from_address: Felt252Wrapper::from(from),
+        data: bounded_vec![Felt252Wrapper::from(n)],
+        ..EventWrapper::empty()
+    };
+    let receipt = |events| TransactionReceiptWrapper { events, ..TransactionReceiptWrapper::default() };
+    let receipts =
+        vec![receipt(bounded_vec![event(2, 0), event(1, 1)]), receipt(bounded_vec![event(2, 2), event(1, 3)])];
+
+    // When
+    let grouped = group_events_by_address(&receipts);
+
+    // Then
+    let data = |address: u64| -> Vec<Felt252Wrapper> {
+        grouped[&Felt252Wrapper::from(address)].iter().map(|event| event.data[0]).collect()
+    };
+    pretty_assertions::assert_eq!(grouped.len(), 2);
+    pretty_assertions::assert_eq!(data(1), vec![Felt252Wrapper::from(1_u128), Felt252Wrapper::from(3_u128)]);
+    pretty_assertions::assert_eq!(data(2), vec![Felt252Wrapper::ZERO, Felt252Wrapper::TWO]);
+}
+
+#[test]
+fn test_event_bloom_has_no_false_negatives() {
+    // Given
+    let event = |from: u64, keys: [u64; 2]| EventWrapper {
+        from_address: Felt252Wrapper::from(from),
+        keys: keys.map(Felt252Wrapper::from).to_vec().try_into().unwrap(),
+        ..EventWrapper::empty()
+    };
+    let events = (0..10).map(|i| event(i + 1, [100 + 2 * i, 101 + 2 * i])).collect::<Vec<_>>();
+    let receipt =
+        TransactionReceiptWrapper { events: events.try_into().unwrap(), ..TransactionReceiptWrapper::default() };
+
+    // When
+    let bloom = receipt.event_bloom();
+
+    // Then
+    for value in (1..=10_u64).chain(100..120) {
+        assert!(bloom.might_contain(Felt252Wrapper::from(value)), "missing {value}");
+    }
+    assert!(!bloom.might_contain(Felt252Wrapper::from(999_u64)));
+    assert!(!Bloom::default().might_contain(Felt252Wrapper::ONE));
+    pretty_assertions::assert_eq!(TransactionReceiptWrapper::default().event_bloom(), Bloom::default());
+}
+
+#[test]
+fn test_compact_bytes_roundtrip_invoke() {
+    // Given
+    let tx = Transaction {
+        nonce: Felt252Wrapper::from(3_u128),
+        sender_address: Felt252Wrapper::from_hex_be(
+            "0x02356b628d108863baf8644c945d97bad70190af5957031f4852d00d0f690a77",
+        )
+        .unwrap(),
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::from(25_u128)],
+            entrypoint_selector: None,
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let compact = tx.to_compact_bytes();
+
+    // Then
+    assert_eq!(compact[0], COMPACT_FORMAT_VERSION);
+    assert!(compact.len() < scale_codec::Encode::encode(&tx).len());
+    pretty_assertions::assert_eq!(Transaction::from_compact_bytes(&compact).unwrap(), tx);
+}
+
+#[test]
+fn test_compact_bytes_roundtrip_with_optional_fields() {
+    // Given
+    let tx = Transaction {
+        tx_type: TxType::DeployAccount,
+        version: 200,
+        contract_address_salt: Some(Felt252Wrapper::from(42_u64)),
+        paid_fee_on_l1: Some(Felt252Wrapper::from(43_u64)),
+        ..Transaction::default()
+    };
+
+    // When
+    let compact = tx.to_compact_bytes();
+
+    // Then
+    pretty_assertions::assert_eq!(Transaction::from_compact_bytes(&compact).unwrap(), tx);
+}
+
+#[test]
+fn test_compact_bytes_roundtrip_legacy_deploy() {
+    // Given
+    let tx = Transaction {
+        tx_type: TxType::Deploy,
+        version: 0,
+        contract_address_salt: Some(Felt252Wrapper::from(42_u64)),
+        ..Transaction::default()
+    };
+
+    // When
+    let compact = tx.to_compact_bytes();
+
+    // Then
+    pretty_assertions::assert_eq!(Transaction::from_compact_bytes(&compact).unwrap(), tx);
+}
+
+#[test]
+fn test_compact_bytes_fails_for_unknown_format_version() {
+    // Given
+    let mut compact = Transaction::default().to_compact_bytes();
+    compact[0] = COMPACT_FORMAT_VERSION + 1;
+
+    // When
+    let result = Transaction::from_compact_bytes(&compact);
+
+    // Then
+    assert_eq!(result, Err(CompactDecodingError::UnsupportedFormatVersion(COMPACT_FORMAT_VERSION + 1)));
+}
+
+#[test]
+fn test_compact_bytes_fails_for_truncated_input() {
+    // Given
+    let compact = Transaction::default().to_compact_bytes();
+
+    // When
+    let result = Transaction::from_compact_bytes(&compact[..compact.len() - 1]);
+
+    // Then
+    assert_eq!(result, Err(CompactDecodingError::UnexpectedEnd));
+}
+
+#[test]
+fn test_compact_bytes_size_of_an_erc20_transfer() {
+    // Given
+    let felt = |hex: &str| Felt252Wrapper::from_hex_be(hex).unwrap();
+    let sender = felt("0x02356b628d108863baf8644c945d97bad70190af5957031f4852d00d0f690a77");
+    let tx = Transaction {
+        version: 1,
+        hash: felt("0x0554f9443c06ce406badc7159f2c0da29eac095f8571fe1a6ce44a2076829a52"),
+        signature: bounded_vec![
+            felt("0x03a3d2d1a1f8c9e0b7a6958473625140fedcba98765432100123456789abcdef"),
+            felt("0x06b1c2d3e4f5a6978879a6b5c4d3e2f1001122334455667788990aabbccddeef"),
+        ],
+        sender_address: sender,
+        nonce: Felt252Wrapper::from(3_u128),
+        max_fee: Felt252Wrapper::from(1_000_000_000_000_000_u128),
+        call_entrypoint: CallEntryPointWrapper {
+            entrypoint_selector: None,
+            calldata: bounded_vec![
+                Felt252Wrapper::ONE,
+                felt("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+                felt("0x0083afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"),
+                Felt252Wrapper::ZERO,
+                Felt252Wrapper::from(3_u128),
+                Felt252Wrapper::from(3_u128),
+                felt("0x01176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8"),
+                Felt252Wrapper::from(100_u128),
+                Felt252Wrapper::ZERO,
+            ],
+            storage_address: sender,
+            caller_address: sender,
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let compact = tx.to_compact_bytes();
+
+    // Then
+    assert_eq!(compact.len(), 322);
+    assert_eq!(scale_codec::Encode::encode(&tx).len(), 554);
+    pretty_assertions::assert_eq!(Transaction::from_compact_bytes(&compact).unwrap(), tx);
+}
+
+#[test]
+fn test_compact_bytes_fails_for_felt_with_leading_zero() {
+    // Given
+    let tx = Transaction { nonce: Felt252Wrapper::from(3_u128), ..Transaction::default() };
+    let mut compact = tx.to_compact_bytes();
+    // The nonce is written as [1, 3] right after the empty hash, signature and sender.
+    assert_eq!(compact[6..8], [1, 3]);
+    compact.splice(6..8, [2, 0, 3]);
+
+    // When
+    let result = Transaction::from_compact_bytes(&compact);
+
+    // Then
+    assert_eq!(result, Err(CompactDecodingError::NonCanonical));
+}
+
+#[test]
+fn test_compact_bytes_fails_for_overlong_varint() {
+    // Given
+    let mut compact = Transaction::default().to_compact_bytes();
+    // The empty signature length is written as a single zero byte.
+    assert_eq!(compact[4], 0);
+    compact.splice(4..5, [0x80, 0x00]);
+
+    // When
+    let result = Transaction::from_compact_bytes(&compact);
+
+    // Then
+    assert_eq!(result, Err(CompactDecodingError::NonCanonical));
+}
+
+#[test]
+fn test_total_fees_and_events() {
+    // Given
+    let receipts = vec![
+        TransactionReceiptWrapper { actual_fee: Felt252Wrapper::from(10_u128), ..TransactionReceiptWrapper::default() },
+        TransactionReceiptWrapper {
+            actual_fee: Felt252Wrapper::from(32_u128),
+            events: bounded_vec![],
+            ..TransactionReceiptWrapper::default()
+        },
+    ];
+
+    // When
+    let fees = total_fees(&receipts);
+    let events = total_events(&receipts);
+
+    // Then
+    assert_eq!(fees, Felt252Wrapper::from(42_u128));
+    assert_eq!(events, 2);
+    assert_eq!(total_fees(&[]), Felt252Wrapper::ZERO);
+}
+
+#[test]
+fn test_total_fees_wraps_modulo_the_field() {
+    // Given
+    let receipts = vec![
+        TransactionReceiptWrapper { actual_fee: Felt252Wrapper::MAX, ..TransactionReceiptWrapper::default() },
+        TransactionReceiptWrapper { actual_fee: Felt252Wrapper::TWO, ..TransactionReceiptWrapper::default() },
+    ];
+
+    // When
+    let fees = total_fees(&receipts);
+
+    // Then
+    assert_eq!(fees, Felt252Wrapper::ONE);
+}
+
+#[test]
+fn test_to_transaction_matches_consuming_conversions() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let invoke = InvokeTransaction {
+        version: 1,
+        calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        signature: bounded_vec![Felt252Wrapper::THREE],
+        ..InvokeTransaction::default()
+    };
+    let declare = DeclareTransaction { version: 1, ..DeclareTransaction::default() };
+    let deploy_account = DeployAccountTransaction {
+        version: 1,
+        salt: U256::from(7),
+        calldata: bounded_vec![Felt252Wrapper::ONE],
+        ..DeployAccountTransaction::default()
+    };
+
+    // When
+    let borrowed = [
+        invoke.to_transaction(chain_id).unwrap(),
+        declare.to_transaction(chain_id),
+        deploy_account.to_transaction(chain_id).unwrap(),
+    ];
+    let consumed = [
+        invoke.from_invoke(chain_id).unwrap(),
+        declare.from_declare(chain_id),
+        deploy_account.from_deploy(chain_id).unwrap(),
+    ];
+
+    // Then
+    pretty_assertions::assert_eq!(borrowed, consumed);
+}
+
+#[test]
+fn test_try_from_deploy_fails_for_salt_out_of_range() {
+    // Given
+    let deploy_account =
+        DeployAccountTransaction { version: 1, salt: U256::MAX, ..DeployAccountTransaction::default() };
+
+    // When
+    let result = deploy_account.try_from_deploy::<DefaultTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::OutOfRange)));
+}
+
+#[test]
+fn test_from_deploy_and_to_transaction_fail_for_salt_out_of_range() {
+    // Given
+    let deploy_account =
+        DeployAccountTransaction { version: 1, salt: U256::MAX, ..DeployAccountTransaction::default() };
+
+    // When
+    let borrowed = deploy_account.to_transaction(ChainId::SN_GOERLI);
+    let consumed = deploy_account.from_deploy(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(matches!(borrowed, Err(RPCTransactionConversionError::OutOfRange)));
+    assert!(matches!(consumed, Err(RPCTransactionConversionError::OutOfRange)));
+}
+
+#[test]
+fn test_try_from_deploy_accepts_max_felt_salt() {
+    // Given
+    let deploy_account = DeployAccountTransaction {
+        version: 1,
+        salt: U256::from(Felt252Wrapper::MAX),
+        ..DeployAccountTransaction::default()
+    };
+
+    // When
+    let transaction = deploy_account.clone().try_from_deploy::<DefaultTxLimits>(ChainId::SN_GOERLI).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(transaction, deploy_account.from_deploy(ChainId::SN_GOERLI).unwrap());
+}
+
+#[test]
+fn test_deploy_account_contract_address() {
+    // Computed with `calculate_contract_address_from_hash` from the cairo lang package
+    let expected_address =
+        Felt252Wrapper::from_hex_be("0x06e2616a2dceff4355997369246c25a78e95093df7a49e5ca6a06ce1544ffd50").unwrap();
+
+    // Given
+    let public_key =
+        Felt252Wrapper::from_hex_be("0x03603a2692a2ae60abb343e832ee53b55d6b25f02a3ef1565ec691edc7a209b2").unwrap();
+    let deploy_account = DeployAccountTransaction {
+        version: 1,
+        calldata: bounded_vec![public_key],
+        salt: U256::from_str("0x0780f72e33c1508df24d8f00a96ecc6e08a850ecb09f7e6dff6a81624c0ef46a").unwrap(),
+        account_class_hash: Felt252Wrapper::from_hex_be(
+            "0x006280083f8c2a2db9f737320d5e3029b380e0e820fe24b8d312a6a34fdba0cd",
+        )
+        .unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+
+    // When
+    let address = deploy_account.contract_address().unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(address, expected_address);
+}
+
+#[test]
+fn test_fee_unit_from_tx_version() {
+    pretty_assertions::assert_eq!(FeeUnit::from_tx_version(0), FeeUnit::Wei);
+    pretty_assertions::assert_eq!(FeeUnit::from_tx_version(1), FeeUnit::Wei);
+    pretty_assertions::assert_eq!(FeeUnit::from_tx_version(2), FeeUnit::Wei);
+    pretty_assertions::assert_eq!(FeeUnit::from_tx_version(3), FeeUnit::Fri);
+}
+
+#[test]
+fn test_require_signature() {
+    // Given
+    let signed = Transaction::default();
+    let unsigned = Transaction { signature: bounded_vec![], ..Transaction::default() };
+    let l1_handler = Transaction { tx_type: TxType::L1Handler, signature: bounded_vec![], ..Transaction::default() };
+
+    // Then
+    assert!(signed.is_signed());
+    assert!(!unsigned.is_signed());
+    assert!(signed.require_signature().is_ok());
+    assert!(matches!(unsigned.require_signature(), Err(TransactionValidationErrorWrapper::MissingSignature)));
+    assert!(!l1_handler.is_signed());
+    assert!(l1_handler.require_signature().is_ok());
+}
+
+#[test]
+fn test_felt_iter_order() {
+    // Given
+    let transaction = Transaction {
+        version: 1,
+        hash: Felt252Wrapper::from(10_u64),
+        signature: bounded_vec![Felt252Wrapper::from(20_u64), Felt252Wrapper::from(21_u64)],
+        sender_address: Felt252Wrapper::from(30_u64),
+        nonce: Felt252Wrapper::from(40_u64),
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::from(50_u64), Felt252Wrapper::from(51_u64)],
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let felts: Vec<Felt252Wrapper> = transaction.felt_iter().collect();
+
+    // Then
+    let expected: Vec<Felt252Wrapper> =
+        [10_u64, 1, 40, 30, 50, 51, 20, 21].into_iter().map(Felt252Wrapper::from).collect();
+    pretty_assertions::assert_eq!(felts, expected);
+}
+
+#[test]
+fn test_into_maybe_pending_transaction_receipt_for_accepted_invoke() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        transaction_hash: Felt252Wrapper::ONE,
+        actual_fee: Felt252Wrapper::TWO,
+        block_hash: Some(Felt252Wrapper::THREE),
+        block_number: Some(4),
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let rpc_receipt = receipt.into_maybe_pending_transaction_receipt(TransactionStatus::AcceptedOnL2);
+
+    // Then
+    match rpc_receipt {
+        MaybePendingTransactionReceipt::Receipt(starknet_core::types::TransactionReceipt::Invoke(invoke)) => {
+            pretty_assertions::assert_eq!(invoke.transaction_hash, Felt252Wrapper::ONE.0);
+            pretty_assertions::assert_eq!(invoke.actual_fee, Felt252Wrapper::TWO.0);
+            pretty_assertions::assert_eq!(invoke.block_hash, Felt252Wrapper::THREE.0);
+            pretty_assertions::assert_eq!(invoke.block_number, 4);
+            pretty_assertions::assert_eq!(invoke.events.len(), 2);
+        }
+        _ => panic!("Expected an accepted invoke receipt"),
+    }
+}
+
+#[test]
+fn test_into_maybe_pending_transaction_receipt_for_reverted_invoke_drops_the_revert_reason() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        block_hash: Some(Felt252Wrapper::THREE),
+        block_number: Some(4),
+        execution_status: ExecutionStatus::Reverted,
+        revert_reason: Some(BoundedVec::truncate_from(b"assertion failed".to_vec())),
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let rpc_receipt = receipt.into_maybe_pending_transaction_receipt(TransactionStatus::AcceptedOnL2);
+
+    // Then
+    // The receipts of the spec we implement can't tell a reverted transaction apart, see
+    // `into_maybe_pending_transaction_receipt`.
+    let rpc_receipt = serde_json::to_value(rpc_receipt).unwrap();
+    pretty_assertions::assert_eq!(rpc_receipt["status"], "ACCEPTED_ON_L2");
+    assert!(rpc_receipt.get("execution_status").is_none());
+    assert!(rpc_receipt.get("revert_reason").is_none());
+}
+
+#[test]
+fn test_into_maybe_pending_transaction_receipt_for_deploy_account_has_contract_address() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        tx_type: TxType::DeployAccount,
+        block_hash: Some(Felt252Wrapper::ONE),
+        block_number: Some(1),
+        contract_address: Some(Felt252Wrapper::THREE),
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let rpc_receipt = receipt.into_maybe_pending_transaction_receipt(TransactionStatus::AcceptedOnL2);
+
+    // Then
+    match rpc_receipt {
+        MaybePendingTransactionReceipt::Receipt(starknet_core::types::TransactionReceipt::DeployAccount(deploy)) => {
+            pretty_assertions::assert_eq!(deploy.contract_address, Felt252Wrapper::THREE.0);
+        }
+        _ => panic!("Expected an accepted deploy account receipt"),
+    }
+}
+
+#[test]
+fn test_receipt_event_commitment() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        events: bounded_vec![EventWrapper::default(), EventWrapper::default()],
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let commitment = receipt.event_commitment();
+
+    // Then
+    // Same value as the event commitment of `test_merkle_tree`, computed from the sequencer test.
+    pretty_assertions::assert_eq!(
+        commitment,
+        Felt252Wrapper::from_hex_be("0x03ebee479332edbeecca7dee501cb507c69d51e0df116d28ae84cd2671dfef02").unwrap()
+    );
+}
+
+#[test]
+fn test_receipt_event_commitment_depends_on_the_event_order() {
+    // Given
+    let first = EventWrapper { from_address: Felt252Wrapper::ONE, ..EventWrapper::default() };
+    let second = EventWrapper { keys: bounded_vec![Felt252Wrapper::TWO], ..EventWrapper::default() };
+    let receipt = TransactionReceiptWrapper {
+        events: bounded_vec![first.clone(), second.clone()],
+        ..TransactionReceiptWrapper::default()
+    };
+    let swapped =
+        TransactionReceiptWrapper { events: bounded_vec![second, first], ..TransactionReceiptWrapper::default() };
+
+    // When
+    let commitment = receipt.event_commitment();
+
+    // Then
+    assert_ne!(commitment, swapped.event_commitment());
+    assert_ne!(commitment, TransactionReceiptWrapper::default().event_commitment());
+}
+
+#[test]
+fn test_into_maybe_pending_transaction_receipt_for_declare() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        tx_type: TxType::Declare,
+        transaction_hash: Felt252Wrapper::ONE,
+        block_hash: Some(Felt252Wrapper::ONE),
+        block_number: Some(1),
+        class_hash: Some(Felt252Wrapper::TWO),
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let rpc_receipt = receipt.into_maybe_pending_transaction_receipt(TransactionStatus::AcceptedOnL2);
+
+    // Then
+    match rpc_receipt {
+        MaybePendingTransactionReceipt::Receipt(starknet_core::types::TransactionReceipt::Declare(declare)) => {
+            pretty_assertions::assert_eq!(declare.transaction_hash, Felt252Wrapper::ONE.0);
+        }
+        _ => panic!("Expected an accepted declare receipt"),
+    }
+}
+
+#[test]
+fn test_into_maybe_pending_transaction_receipt_for_pending_invoke() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        transaction_hash: Felt252Wrapper::ONE,
+        block_hash: None,
+        block_number: None,
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let rpc_receipt = receipt.into_maybe_pending_transaction_receipt(TransactionStatus::AcceptedOnL2);
+
+    // Then
+    match rpc_receipt {
+        MaybePendingTransactionReceipt::PendingReceipt(PendingTransactionReceipt::Invoke(invoke)) => {
+            pretty_assertions::assert_eq!(invoke.transaction_hash, Felt252Wrapper::ONE.0);
+        }
+        _ => panic!("Expected a pending invoke receipt"),
+    }
+}
+
+#[test]
+fn test_into_pending_receipt_for_pending_invoke() {
+    // Given
+    let receipt = TransactionReceiptWrapper {
+        transaction_hash: Felt252Wrapper::ONE,
+        actual_fee: Felt252Wrapper::TWO,
+        ..TransactionReceiptWrapper::default()
+    };
+
+    // When
+    let rpc_receipt = receipt.into_pending_receipt();
+
+    // Then
+    match rpc_receipt {
+        MaybePendingTransactionReceipt::PendingReceipt(PendingTransactionReceipt::Invoke(invoke)) => {
+            pretty_assertions::assert_eq!(invoke.transaction_hash, Felt252Wrapper::ONE.0);
+            pretty_assertions::assert_eq!(invoke.actual_fee, Felt252Wrapper::TWO.0);
+            pretty_assertions::assert_eq!(invoke.events.len(), 2);
+        }
+        _ => panic!("Expected a pending invoke receipt"),
+    }
+}
+
+#[test]
+fn test_fee_transfer_error_overage() {
+    // Given
+    let error = TransactionExecutionErrorWrapper::fee_transfer_error(Fee(10), Fee(25));
+    let no_overage = TransactionExecutionErrorWrapper::FeeTransferError { max_fee: Fee(25), actual_fee: Fee(10) };
+
+    // Then
+    pretty_assertions::assert_eq!(error.overage(), Some(Fee(15)));
+    pretty_assertions::assert_eq!(no_overage.overage(), None);
+    pretty_assertions::assert_eq!(TransactionExecutionErrorWrapper::FeeComputationError.overage(), None);
+}
+
+#[test]
+#[should_panic]
+#[cfg(debug_assertions)]
+fn test_fee_transfer_error_requires_actual_fee_above_max_fee() {
+    TransactionExecutionErrorWrapper::fee_transfer_error(Fee(25), Fee(10));
+}
+
+#[test]
+fn test_to_log_string() {
+    // Given
+    let transaction = Transaction {
+        version: 1,
+        hash: Felt252Wrapper::from(0xabc_u64),
+        sender_address: Felt252Wrapper::from(0x123_u64),
+        nonce: Felt252Wrapper::from(7_u64),
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let log = transaction.to_log_string();
+
+    // Then
+    pretty_assertions::assert_eq!(log, "type=INVOKE v1 hash=0xabc sender=0x123 nonce=7 calldata_len=2");
+}
+
+#[test]
+fn test_redacted_keeps_the_hash_and_drops_the_signature() {
+    // Given
+    let transaction = Transaction {
+        hash: Felt252Wrapper::from(0xabc_u64),
+        signature: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO, Felt252Wrapper::THREE],
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let redacted = transaction.redacted();
+    let truncated = transaction.redacted().with_truncated_calldata(1);
+
+    // Then
+    pretty_assertions::assert_eq!(redacted.hash, transaction.hash);
+    assert!(redacted.signature.is_empty());
+    pretty_assertions::assert_eq!(redacted.call_entrypoint.calldata, transaction.call_entrypoint.calldata);
+    pretty_assertions::assert_eq!(truncated.hash, transaction.hash);
+    pretty_assertions::assert_eq!(truncated.call_entrypoint.calldata.to_vec(), vec![Felt252Wrapper::ONE]);
+    pretty_assertions::assert_eq!(transaction.redacted_log_string(), redacted.to_log_string());
+}
+
+#[test]
+fn test_validate_entrypoint_declare() {
+    // Given
+    let tx = Transaction {
+        tx_type: TxType::Declare,
+        sender_address: Felt252Wrapper::THREE,
+        call_entrypoint: CallEntryPointWrapper {
+            class_hash: Some(get_test_class_hash()),
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let entrypoint = tx.validate_entrypoint().unwrap();
+
+    // Then
+    let expected_selector = selector_from_name(constants::VALIDATE_DECLARE_ENTRY_POINT_NAME);
+    pretty_assertions::assert_eq!(entrypoint.entrypoint_selector, Some(expected_selector.into()));
+    pretty_assertions::assert_eq!(entrypoint.calldata.to_vec(), vec![get_test_class_hash()]);
+    pretty_assertions::assert_eq!(entrypoint.storage_address, Felt252Wrapper::THREE);
+    pretty_assertions::assert_eq!(entrypoint.class_hash, None);
+}
+
+#[test]
+fn test_validate_entrypoint_invoke() {
+    // Given
+    let tx = Transaction {
+        call_entrypoint: CallEntryPointWrapper { calldata: get_test_calldata(), ..CallEntryPointWrapper::default() },
+        ..Transaction::default()
+    };
+
+    // When
+    let entrypoint = tx.validate_entrypoint().unwrap();
+
+    // Then
+    let expected_selector = selector_from_name(constants::VALIDATE_ENTRY_POINT_NAME);
+    pretty_assertions::assert_eq!(entrypoint.entrypoint_selector, Some(expected_selector.into()));
+    pretty_assertions::assert_eq!(entrypoint.calldata, get_test_calldata());
+}
+
+#[test]
+fn test_validate_entrypoint_is_none_for_l1_handler() {
+    // Given
+    let tx = Transaction { tx_type: TxType::L1Handler, ..Transaction::default() };
+
+    // Then
+    assert!(tx.validate_entrypoint().is_none());
+}
+
+#[test]
+fn test_starknet_api_getters() {
+    // Given
+    let tx = Transaction {
+        sender_address: Felt252Wrapper::from(10_u64),
+        nonce: Felt252Wrapper::TWO,
+        signature: bounded_vec![Felt252Wrapper::from(20_u64), Felt252Wrapper::from(21_u64)],
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::from(30_u64)],
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // Then
+    pretty_assertions::assert_eq!(tx.sender_address_api().unwrap(), ContractAddress(patricia_key!("0xa")));
+    pretty_assertions::assert_eq!(tx.nonce_api(), Nonce(StarkFelt::from(2_u64)));
+    pretty_assertions::assert_eq!(
+        tx.signature_api(),
+        TransactionSignature(vec![StarkFelt::from(20_u64), StarkFelt::from(21_u64)])
+    );
+    pretty_assertions::assert_eq!(tx.calldata_api(), Calldata(Arc::new(vec![StarkFelt::from(30_u64)])));
+}
+
+#[test]
+fn test_sender_address_api_fails_for_out_of_range_address() {
+    // Given
+    let tx = Transaction { sender_address: Felt252Wrapper::MAX, ..Transaction::default() };
+
+    // When
+    let result = tx.sender_address_api();
+
+    // Then
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_check_nonce() {
+    // Given
+    let tx = Transaction { nonce: Felt252Wrapper::TWO, ..Transaction::default() };
+    let l1_handler = Transaction { tx_type: TxType::L1Handler, ..tx.clone() };
+
+    // Then
+    assert!(tx.check_nonce(Felt252Wrapper::TWO).is_ok());
+    assert!(matches!(
+        tx.check_nonce(Felt252Wrapper::THREE),
+        Err(TransactionValidationErrorWrapper::InvalidNonce { expected, got })
+            if expected == Felt252Wrapper::THREE && got == Felt252Wrapper::TWO
+    ));
+    assert!(l1_handler.check_nonce(Felt252Wrapper::THREE).is_ok());
+}
+
+#[test]
+fn test_transaction_summary_from_transaction() {
+    // Given
+    let tx = Transaction {
+        tx_type: TxType::DeployAccount,
+        hash: Felt252Wrapper::from(0x1234_u64),
+        sender_address: Felt252Wrapper::from(0x101_u64),
+        nonce: Felt252Wrapper::TWO,
+        max_fee: Felt252Wrapper::from(100_u64),
+        ..Transaction::default()
+    };
+
+    // When
+    let summary = TransactionSummary::from(&tx);
+
+    // Then
+    pretty_assertions::assert_eq!(
+        summary,
+        TransactionSummary {
+            hash: Felt252Wrapper::from(0x1234_u64),
+            tx_type: TxType::DeployAccount,
+            version: 1,
+            sender_address: Felt252Wrapper::from(0x101_u64),
+            nonce: Felt252Wrapper::TWO,
+            max_fee: Felt252Wrapper::from(100_u64),
+        }
+    );
+}
+
+#[test]
+fn test_rpc_transaction_conversion_errors_have_an_rpc_error_code() {
+    // Given
+    let unexpected = "An unexpected error occurred";
+    let cases = [
+        (RPCTransactionConversionError::UnknownVersion, 61, "The transaction version is not supported"),
+        (RPCTransactionConversionError::UnsupportedVersion(2), 61, "The transaction version is not supported"),
+        (RPCTransactionConversionError::InvalidContractClass, 50, "Invalid contract class"),
+        (RPCTransactionConversionError::CalldataTooLong, 63, "Invalid transaction: calldata is too long"),
+        (RPCTransactionConversionError::SignatureTooLong, 63, "Invalid transaction: signature is too long"),
+        (RPCTransactionConversionError::ArrayTooLong, 63, "Invalid transaction: array is too long"),
+        (
+            RPCTransactionConversionError::InvalidEntryPointSelector,
+            63,
+            "Invalid transaction: only v0 invoke transactions have an entry point selector",
+        ),
+        (RPCTransactionConversionError::MissingInformation, 63, unexpected),
+        (RPCTransactionConversionError::NotBroadcastable, 63, unexpected),
+        (RPCTransactionConversionError::FromArrayError, 63, unexpected),
+        (RPCTransactionConversionError::InvalidLength, 63, unexpected),
+        (RPCTransactionConversionError::InvalidCharacter, 63, unexpected),
+        (RPCTransactionConversionError::OutOfRange, 63, unexpected),
+        (RPCTransactionConversionError::ValueTooLarge, 63, unexpected),
+    ];
+
+    for (error, expected_code, expected_message) in cases {
+        // When
+        let (code, message) = (error.rpc_error_code(), error.rpc_error_message());
+
+        // Then
+        pretty_assertions::assert_eq!(code, expected_code, "{error:?}");
+        pretty_assertions::assert_eq!(message, expected_message, "{error:?}");
+    }
+}
+
+#[test]
+fn test_validate_nonce_sequence_accepts_contiguous_nonces() {
+    // Given
+    let txs: Vec<Transaction> =
+        (3_u64..6).map(|nonce| Transaction { nonce: nonce.into(), ..Transaction::default() }).collect();
+
+    // When
+    let result = validate_nonce_sequence(Felt252Wrapper::THREE, &txs.iter().collect::<Vec<_>>());
+
+    // Then
+    assert!(result.is_ok());
+    assert!(validate_nonce_sequence(Felt252Wrapper::THREE, &[]).is_ok());
+}
+
+#[test]
+fn test_validate_nonce_sequence_rejects_a_gap() {
+    // Given
+    let txs: Vec<Transaction> =
+        [3_u64, 4, 6].into_iter().map(|nonce| Transaction { nonce: nonce.into(), ..Transaction::default() }).collect();
+
+    // When
+    let result = validate_nonce_sequence(Felt252Wrapper::THREE, &txs.iter().collect::<Vec<_>>());
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(TransactionValidationErrorWrapper::InvalidNonce { expected, got })
+            if expected == Felt252Wrapper::from(5_u64) && got == Felt252Wrapper::from(6_u64)
+    ));
+}
+
+#[test]
+fn test_validate_nonce_sequence_rejects_a_duplicate_nonce() {
+    // Given
+    let txs: Vec<Transaction> =
+        [3_u64, 3, 4].into_iter().map(|nonce| Transaction { nonce: nonce.into(), ..Transaction::default() }).collect();
+
+    // When
+    let result = validate_nonce_sequence(Felt252Wrapper::THREE, &txs.iter().collect::<Vec<_>>());
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(TransactionValidationErrorWrapper::InvalidNonce { expected, got })
+            if expected == Felt252Wrapper::from(4_u64) && got == Felt252Wrapper::THREE
+    ));
+}
+
+#[test]
+fn test_check_nonce_gap_at_the_boundary() {
+    // Given
+    let current_nonce = Felt252Wrapper::from(10_u64);
+    let at_max_gap = Transaction { nonce: Felt252Wrapper::from(15_u64), ..Transaction::default() };
+    let over_max_gap = Transaction { nonce: Felt252Wrapper::from(16_u64), ..Transaction::default() };
+
+    // When
+    let at_max_gap = at_max_gap.check_nonce_gap(current_nonce, 5);
+    let over_max_gap = over_max_gap.check_nonce_gap(current_nonce, 5);
+
+    // Then
+    assert!(at_max_gap.is_ok());
+    assert!(matches!(
+        over_max_gap,
+        Err(TransactionValidationErrorWrapper::NonceGapTooLarge { current, got, max_gap: 5 })
+            if current == current_nonce && got == Felt252Wrapper::from(16_u64)
+    ));
+}
+
+#[test]
+fn test_check_nonce_gap_rejects_past_nonce() {
+    // Given
+    let current_nonce = Felt252Wrapper::from(10_u64);
+    let tx = Transaction { nonce: Felt252Wrapper::from(9_u64), ..Transaction::default() };
+    let l1_handler = Transaction { tx_type: TxType::L1Handler, ..tx.clone() };
+
+    // When
+    let result = tx.check_nonce_gap(current_nonce, u64::MAX);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(TransactionValidationErrorWrapper::InvalidNonce { expected, got })
+            if expected == current_nonce && got == Felt252Wrapper::from(9_u64)
+    ));
+    assert!(l1_handler.check_nonce_gap(current_nonce, 0).is_ok());
+}
+
+#[test]
+fn test_check_max_fee_rejects_zero_max_fee_account_transactions() {
+    for tx_type in [TxType::Invoke, TxType::Declare, TxType::DeployAccount] {
+        // Given
+        let tx = Transaction { tx_type, max_fee: Felt252Wrapper::ZERO, ..Transaction::default() };
+
+        // When
+        let result = tx.check_max_fee();
+
+        // Then
+        assert!(matches!(result, Err(TransactionValidationErrorWrapper::ZeroMaxFee)));
+        assert!(Transaction { max_fee: Felt252Wrapper::ONE, ..tx }.check_max_fee().is_ok());
+    }
+}
+
+#[test]
+fn test_check_max_fee_exempts_l1_handler() {
+    // Given
+    let tx = Transaction { tx_type: TxType::L1Handler, max_fee: Felt252Wrapper::ZERO, ..Transaction::default() };
+
+    // When
+    let result = tx.check_max_fee();
+
+    // Then
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_check_l1_fee_accepts_l1_handler_with_paid_fee() {
+    // Given
+    let tx =
+        Transaction { tx_type: TxType::L1Handler, paid_fee_on_l1: Some(Felt252Wrapper::ONE), ..Transaction::default() };
+
+    // When
+    let result = tx.check_l1_fee();
+
+    // Then
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_check_l1_fee_rejects_l1_handler_without_paid_fee() {
+    // Given
+    let l1_handler = Transaction { tx_type: TxType::L1Handler, paid_fee_on_l1: None, ..Transaction::default() };
+    let invoke = Transaction { tx_type: TxType::Invoke, paid_fee_on_l1: None, ..Transaction::default() };
+
+    // When
+    let result = l1_handler.check_l1_fee();
+
+    // Then
+    assert!(matches!(result, Err(TransactionValidationErrorWrapper::MissingL1Fee)));
+    assert!(invoke.check_l1_fee().is_ok());
+}
+
+fn validation_context() -> ValidationContext {
+    ValidationContext {
+        current_nonce: Felt252Wrapper::ONE,
+        account_balance: Felt252Wrapper::from(1000_u64),
+        chain_id: ChainId::SN_GOERLI,
+        max_nonce_gap: 5,
+        block_timestamp: 0,
+    }
+}
+
+fn admissible_invoke(nonce: Felt252Wrapper, max_fee: Felt252Wrapper) -> Transaction {
+    InvokeTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(0x101_u64),
+        nonce,
+        max_fee,
+        ..InvokeTransaction::default()
+    }
+    .from_invoke(ChainId::SN_GOERLI)
+    .unwrap()
+}
+
+#[test]
+fn test_validate_against_accepts_an_admissible_transaction() {
+    // Given
+    let tx = admissible_invoke(Felt252Wrapper::THREE, Felt252Wrapper::from(100_u64));
+
+    // When
+    let result = tx.validate_against(&validation_context());
+
+    // Then
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_validate_against_runs_the_checks_in_order() {
+    // Given
+    let ctx = validation_context();
+    let zero_fee_and_gap = admissible_invoke(Felt252Wrapper::from(100_u64), Felt252Wrapper::ZERO);
+    let bad_hash = Transaction { hash: Felt252Wrapper::ONE, ..zero_fee_and_gap.clone() };
+    let gap_and_balance = admissible_invoke(Felt252Wrapper::from(100_u64), Felt252Wrapper::from(2000_u64));
+    let balance = admissible_invoke(Felt252Wrapper::ONE, Felt252Wrapper::from(2000_u64));
+
+    // When
+    let results = [
+        bad_hash.validate_against(&ctx),
+        zero_fee_and_gap.validate_against(&ctx),
+        gap_and_balance.validate_against(&ctx),
+        balance.validate_against(&ctx),
+    ];
+
+    // Then
+    assert!(matches!(results[0], Err(TransactionValidationErrorWrapper::InvalidTransactionHash)));
+    assert!(matches!(results[1], Err(TransactionValidationErrorWrapper::ZeroMaxFee)));
+    assert!(matches!(results[2], Err(TransactionValidationErrorWrapper::NonceGapTooLarge { max_gap: 5, .. })));
+    assert!(matches!(
+        results[3],
+        Err(TransactionValidationErrorWrapper::InsufficientBalance { balance, max_fee })
+            if balance == Felt252Wrapper::from(1000_u64) && max_fee == Felt252Wrapper::from(2000_u64)
+    ));
+}
+
+#[test]
+fn test_validate_against_only_checks_the_l1_fee_of_l1_handlers() {
+    // Given
+    let ctx = ValidationContext { account_balance: Felt252Wrapper::ZERO, ..validation_context() };
+    let paid = Transaction {
+        tx_type: TxType::L1Handler,
+        nonce: Felt252Wrapper::from(100_u64),
+        paid_fee_on_l1: Some(Felt252Wrapper::ONE),
+        ..Transaction::default()
+    };
+    let unpaid = Transaction { paid_fee_on_l1: None, ..paid.clone() };
+    let legacy_deploy = DeployTransaction {
+        version: 0,
+        class_hash: Felt252Wrapper::TWO,
+        contract_address_salt: Felt252Wrapper::THREE,
+        constructor_calldata: bounded_vec!(),
+    }
+    .from_deploy_legacy(ChainId::SN_GOERLI);
+
+    // When
+    let results = [paid.validate_against(&ctx), unpaid.validate_against(&ctx), legacy_deploy.validate_against(&ctx)];
+
+    // Then
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(TransactionValidationErrorWrapper::MissingL1Fee)));
+    assert!(matches!(results[2], Err(TransactionValidationErrorWrapper::LegacyDeploy)));
+}
+
+#[test]
+fn test_charges_fee() {
+    for tx_type in TxType::all() {
+        let expected = tx_type != TxType::L1Handler;
+        let tx = Transaction { tx_type, ..Transaction::default() };
+        pretty_assertions::assert_eq!(tx.charges_fee(), expected);
+    }
+}
+
+#[test]
+fn test_charges_fee_with_truth_table() {
+    let cases = [
+        (TxType::Invoke, false, true),
+        (TxType::Invoke, true, false),
+        (TxType::Declare, false, true),
+        (TxType::Declare, true, false),
+        (TxType::DeployAccount, false, true),
+        (TxType::DeployAccount, true, false),
+        (TxType::L1Handler, false, false),
+        (TxType::L1Handler, true, false),
+    ];
+    for (tx_type, is_query, expected) in cases {
+        // Given
+        let tx = Transaction { tx_type: tx_type.clone(), ..Transaction::default() };
+        let flags = SimulationFlags { skip_fee_charge: is_query, ..SimulationFlags::default() };
+
+        // When
+        let charges_fee = tx.charges_fee_with(flags);
+
+        // Then
+        pretty_assertions::assert_eq!(charges_fee, expected, "tx_type: {tx_type:?}, is_query: {is_query}");
+    }
+}
+
+#[test]
+fn test_validate_return_magic_is_valid_short_string() {
+    // Given
+    let valid = Felt252Wrapper::from_bytes_be(b"VALID").unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(VALIDATE_RETURN_MAGIC, valid);
+    pretty_assertions::assert_eq!(VALIDATE_RETURN_MAGIC, Felt252Wrapper::from_hex_be("0x56414c4944").unwrap());
+}
+
+#[test]
+fn test_check_validate_result_requires_magic_value() {
+    for tx_type in [TxType::Invoke, TxType::Declare, TxType::DeployAccount] {
+        // Given
+        let tx = Transaction { tx_type, ..Transaction::default() };
+
+        // When
+        let valid = tx.check_validate_result(&[VALIDATE_RETURN_MAGIC]);
+        let empty = tx.check_validate_result(&[]);
+        let wrong = tx.check_validate_result(&[Felt252Wrapper::ONE]);
+
+        // Then
+        assert!(valid.is_ok());
+        assert!(matches!(empty, Err(TransactionValidationErrorWrapper::InvalidValidateReturn)));
+        assert!(matches!(wrong, Err(TransactionValidationErrorWrapper::InvalidValidateReturn)));
+    }
+}
+
+#[test]
+fn test_check_validate_result_exempts_legacy_versions_and_l1_handler() {
+    // Given
+    let declare_v0 = Transaction { tx_type: TxType::Declare, version: 0, ..Transaction::default() };
+    let l1_handler = Transaction { tx_type: TxType::L1Handler, ..Transaction::default() };
+
+    // Then
+    assert!(!declare_v0.requires_validate_magic());
+    assert!(declare_v0.check_validate_result(&[]).is_ok());
+    assert!(!l1_handler.requires_validate_magic());
+    assert!(l1_handler.check_validate_result(&[]).is_ok());
+}
+
+#[test]
+fn test_try_into_rpc_transactions_reports_failures_by_index() {
+    // Given
+    let valid = Transaction::default();
+    let invalid = Transaction { version: 5, ..Transaction::default() };
+
+    // When
+    let (converted, errors) = try_into_rpc_transactions(vec![valid, invalid]);
+
+    // Then
+    pretty_assertions::assert_eq!(converted.len(), 1);
+    assert!(matches!(converted[0], starknet_core::types::Transaction::Invoke(_)));
+    pretty_assertions::assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], (1, RPCTransactionConversionError::UnknownVersion)));
+}
+
+#[test]
+fn test_into_rpc_converts_each_transaction() {
+    // Given
+    let txs = vec![Transaction::default(), Transaction { version: 5, ..Transaction::default() }];
+
+    // When
+    let converted: Vec<_> = txs.into_rpc().collect();
+
+    // Then
+    pretty_assertions::assert_eq!(converted.len(), 2);
+    assert!(matches!(converted[0], Ok(starknet_core::types::Transaction::Invoke(_))));
+    assert!(matches!(converted[1], Err(RPCTransactionConversionError::UnknownVersion)));
+}
+
+#[test]
+fn test_into_rpc_converts_lazily() {
+    // Given
+    let pulled = core::cell::Cell::new(0);
+    let txs = vec![Transaction::default(); 3].into_iter().inspect(|_| pulled.set(pulled.get() + 1));
+
+    // When
+    let first = txs.into_rpc().next();
+
+    // Then
+    assert!(matches!(first, Some(Ok(_))));
+    pretty_assertions::assert_eq!(pulled.get(), 1);
+}
+
+#[test]
+fn test_tx_type_round_trips_through_blockifier_transaction_type() {
+    for tx_type in TxType::all() {
+        pretty_assertions::assert_eq!(TxType::from(TransactionType::try_from(tx_type.clone()).unwrap()), tx_type);
+    }
+    assert!(matches!(TransactionType::try_from(TxType::Deploy), Err(TransactionConversionError::LegacyDeploy)));
+}
+
+#[test]
+fn test_requires_deployed_sender() {
+    for tx_type in TxType::all() {
+        let expected = tx_type != TxType::DeployAccount;
+        let tx = Transaction { tx_type, ..Transaction::default() };
+        pretty_assertions::assert_eq!(tx.requires_deployed_sender(), expected);
+    }
+}
+
+#[test]
+fn test_diff_of_identical_transactions_is_empty() {
+    // Given
+    let transaction = Transaction::default();
+
+    // When
+    let diff = transaction.diff(&transaction.clone());
+
+    // Then
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_diff_reports_changed_nonce() {
+    // Given
+    let transaction = Transaction { nonce: Felt252Wrapper::ONE, ..Transaction::default() };
+    let other = Transaction { nonce: Felt252Wrapper::TWO, ..Transaction::default() };
+
+    // When
+    let diff = transaction.diff(&other);
+
+    // Then
+    pretty_assertions::assert_eq!(
+        diff,
+        vec![FieldDiff { field: "nonce", left: String::from("0x1"), right: String::from("0x2") }]
+    );
+}
+
+#[test]
+fn test_diff_renders_calldata_by_first_difference() {
+    // Given
+    let transaction = Transaction {
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+    let other = Transaction {
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::THREE, Felt252Wrapper::ONE],
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let diff = transaction.diff(&other);
+
+    // Then
+    pretty_assertions::assert_eq!(
+        diff,
+        vec![FieldDiff {
+            field: "call_entrypoint.calldata",
+            left: String::from("len=2 [1]=0x2"),
+            right: String::from("len=3 [1]=0x3"),
+        }]
+    );
+}
+
+#[test]
+fn test_dedup_by_hash_keeps_first_seen_transaction() {
+    // Given
+    let first = Transaction { hash: Felt252Wrapper::ONE, nonce: Felt252Wrapper::ZERO, ..Transaction::default() };
+    let second = Transaction { hash: Felt252Wrapper::TWO, ..Transaction::default() };
+    let duplicate = Transaction { hash: Felt252Wrapper::ONE, nonce: Felt252Wrapper::ONE, ..Transaction::default() };
+
+    // When
+    let (unique, removed) = dedup_by_hash(vec![first.clone(), second.clone(), duplicate]);
+
+    // Then
+    pretty_assertions::assert_eq!(unique, vec![first, second]);
+    assert_eq!(removed, 1);
+}
+
+#[test]
+fn test_dedup_by_hash_keeps_transactions_of_different_versions() {
+    // Given
+    let invoke = InvokeTransaction {
+        sender_address: Felt252Wrapper::THREE,
+        nonce: Felt252Wrapper::ONE,
+        ..InvokeTransaction::default()
+    };
+    let v0 = InvokeTransaction {
+        version: 0,
+        entry_point_selector: Some(EntryPointSelectorWrapper::default()),
+        ..invoke.clone()
+    }
+    .from_invoke(ChainId::SN_GOERLI)
+    .unwrap();
+    let v1 = InvokeTransaction { version: 1, ..invoke }.from_invoke(ChainId::SN_GOERLI).unwrap();
+    let same_hash = Transaction { version: 1, ..v0.clone() };
+
+    // When
+    let (unique, removed) = dedup_by_hash(vec![v0.clone(), v1.clone(), same_hash.clone()]);
+
+    // Then
+    assert_ne!(v0.identity(), v1.identity());
+    pretty_assertions::assert_eq!(unique, vec![v0, v1, same_hash]);
+    assert_eq!(removed, 0);
+}
+
+#[test]
+fn test_transaction_builder_builds_invoke_by_default() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize> = bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO];
+    let invoke = InvokeTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::THREE,
+        calldata: calldata.clone(),
+        nonce: Felt252Wrapper::ONE,
+        signature: bounded_vec![Felt252Wrapper::TWO],
+        max_fee: Felt252Wrapper::from(u128::MAX),
+        entry_point_selector: None,
+    };
+
+    // When
+    let transaction = Transaction::builder()
+        .sender(Felt252Wrapper::THREE)
+        .calldata(calldata)
+        .nonce(Felt252Wrapper::ONE)
+        .signature(bounded_vec![Felt252Wrapper::TWO])
+        .max_fee(Felt252Wrapper::from(u128::MAX))
+        .build(chain_id)
+        .unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(transaction, invoke.from_invoke(chain_id).unwrap());
+}
+
+#[test]
+fn test_transaction_builder_builds_declare() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let declare = DeclareTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::THREE,
+        nonce: Felt252Wrapper::ONE,
+        signature: bounded_vec![],
+        max_fee: Felt252Wrapper::ONE,
+        compiled_class_hash: Felt252Wrapper::TWO,
+        contract_class: ContractClassWrapper::default(),
+    };
+
+    // When
+    let transaction = Transaction::builder()
+        .declare()
+        .sender(Felt252Wrapper::THREE)
+        .nonce(Felt252Wrapper::ONE)
+        .max_fee(Felt252Wrapper::ONE)
+        .class_hash(Felt252Wrapper::TWO)
+        .contract_class(ContractClassWrapper::default())
+        .build(chain_id)
+        .unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(transaction, declare.from_declare(chain_id));
+}
+
+#[test]
+fn test_transaction_builder_rejects_declare_without_class() {
+    // When
+    let result = Transaction::builder().declare().class_hash(Felt252Wrapper::TWO).build(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(matches!(result, Err(TransactionConversionError::MissingClass)));
+}
+
+#[test]
+fn test_check_class_present_rejects_an_empty_class() {
+    // Given
+    let empty = DeclareTransaction { contract_class: ContractClassWrapper::default(), ..DeclareTransaction::default() };
+    let declare = DeclareTransaction {
+        contract_class: ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap(),
+        ..DeclareTransaction::default()
+    };
+
+    // When
+    let empty_result = empty.check_class_present();
+    let result = declare.check_class_present();
+
+    // Then
+    assert!(matches!(empty_result, Err(TransactionValidationErrorWrapper::EmptyContractClass)));
+    assert!(result.is_ok());
+    assert!(matches!(
+        TransactionExecutionErrorWrapper::from(empty_result.unwrap_err()),
+        TransactionExecutionErrorWrapper::EmptyContractClass
+    ));
+}
+
+#[test]
+fn test_transaction_builder_rejects_deploy_account_without_class_hash() {
+    // When
+    let result =
+        Transaction::builder().deploy_account().contract_address_salt(Felt252Wrapper::ONE).build(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(matches!(result, Err(TransactionConversionError::MissingClassHash)));
+}
+
+#[test]
+fn test_verify_hash_accepts_untouched_transaction() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let transaction = Transaction::builder()
+        .sender(Felt252Wrapper::THREE)
+        .calldata(bounded_vec![Felt252Wrapper::ONE])
+        .nonce(Felt252Wrapper::ONE)
+        .build(chain_id)
+        .unwrap();
+
+    // When
+    let valid = transaction.verify_hash(chain_id);
+
+    // Then
+    assert!(valid);
+}
+
+#[test]
+fn test_hash_preimage_hashes_to_the_transaction_hash() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let transaction = Transaction::builder()
+        .sender(Felt252Wrapper::THREE)
+        .calldata(bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO])
+        .nonce(Felt252Wrapper::ONE)
+        .max_fee(Felt252Wrapper::TWO)
+        .build(chain_id)
+        .unwrap();
+
+    // When
+    let preimage = transaction.hash_preimage(chain_id);
+
+    // Then
+    let calldata_hash = PedersenHasher::compute_hash_on_elements(&[FieldElement::ONE, FieldElement::TWO]);
+    let expected = vec![
+        Felt252Wrapper::from_bytes_be(b"invoke").unwrap(),
+        Felt252Wrapper::from(transaction.version as u64),
+        Felt252Wrapper::THREE,
+        Felt252Wrapper::ZERO,
+        Felt252Wrapper::from(calldata_hash),
+        Felt252Wrapper::TWO,
+        chain_id.into(),
+        Felt252Wrapper::ONE,
+    ];
+    pretty_assertions::assert_eq!(preimage, expected);
+    let preimage: Vec<FieldElement> = preimage.into_iter().map(FieldElement::from).collect();
+    pretty_assertions::assert_eq!(
+        Felt252Wrapper::from(PedersenHasher::compute_hash_on_elements(&preimage)),
+        transaction.hash
+    );
+}
+
+#[test]
+fn test_hash_preimage_of_an_l1_handler_transaction_hashes_to_its_hash() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let transaction = Transaction::from_l1_message(
+        Felt252Wrapper::ONE,
+        Felt252Wrapper::TWO,
+        EntryPointSelectorWrapper(Felt252Wrapper::THREE),
+        &[Felt252Wrapper::from(4_u64)],
+        Felt252Wrapper::from(5_u64),
+        Felt252Wrapper::ZERO,
+        chain_id,
+    )
+    .unwrap();
+
+    // When
+    let preimage = transaction.hash_preimage(chain_id);
+
+    // Then
+    let calldata_hash = PedersenHasher::compute_hash_on_elements(&[FieldElement::ONE, FieldElement::from(4_u64)]);
+    let expected = vec![
+        Felt252Wrapper::from_bytes_be(b"l1_handler").unwrap(),
+        Felt252Wrapper::ZERO,
+        Felt252Wrapper::TWO,
+        Felt252Wrapper::THREE,
+        Felt252Wrapper::from(calldata_hash),
+        Felt252Wrapper::ZERO,
+        chain_id.into(),
+        Felt252Wrapper::from(5_u64),
+    ];
+    pretty_assertions::assert_eq!(preimage, expected);
+    pretty_assertions::assert_eq!(calculate_transaction_hash(&transaction, chain_id), transaction.hash);
+}
+
+#[test]
+fn test_verify_hash_rejects_tampered_nonce() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let mut transaction = Transaction::builder()
+        .sender(Felt252Wrapper::THREE)
+        .calldata(bounded_vec![Felt252Wrapper::ONE])
+        .nonce(Felt252Wrapper::ONE)
+        .build(chain_id)
+        .unwrap();
+    transaction.nonce = Felt252Wrapper::TWO;
+
+    // When
+    let valid = transaction.verify_hash(chain_id);
+
+    // Then
+    assert!(!valid);
+}
+
+#[test]
+fn test_verify_hash_rejects_tampered_l1_handler_calldata() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let mut transaction = Transaction::from_l1_message(
+        Felt252Wrapper::ONE,
+        Felt252Wrapper::TWO,
+        EntryPointSelectorWrapper(Felt252Wrapper::THREE),
+        &[Felt252Wrapper::from(4_u64)],
+        Felt252Wrapper::ZERO,
+        Felt252Wrapper::ZERO,
+        chain_id,
+    )
+    .unwrap();
+    assert!(transaction.verify_hash(chain_id));
+    transaction.call_entrypoint.calldata = bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::from(5_u64)];
+
+    // When
+    let valid = transaction.verify_hash(chain_id);
+
+    // Then
+    assert!(!valid);
+}
+
+#[test]
+fn test_with_next_nonce_bumps_the_nonce_and_resets_the_hash() {
+    // Given
+    let transaction = Transaction { nonce: Felt252Wrapper::ONE, hash: Felt252Wrapper::THREE, ..Transaction::default() };
+
+    // When
+    let next = transaction.with_next_nonce().unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        next,
+        Transaction { nonce: Felt252Wrapper::TWO, hash: Felt252Wrapper::ZERO, ..transaction.clone() }
+    );
+}
+
+#[test]
+fn test_with_next_nonce_fails_at_the_max_nonce() {
+    // Given
+    let transaction = Transaction { nonce: Felt252Wrapper::MAX, ..Transaction::default() };
+
+    // When
+    let next = transaction.with_next_nonce();
+
+    // Then
+    assert_eq!(next, None);
+}
+
+fn fee_token_config() -> FeeTokenConfig {
+    FeeTokenConfig { eth: Felt252Wrapper::from(0xe7_u64), strk: Felt252Wrapper::from(0x57_u64) }
+}
+
+#[test]
+fn test_fee_token_address_of_a_v1_transaction_is_eth() {
+    // Given
+    let transaction = Transaction { version: 1, ..Transaction::default() };
+
+    // When
+    let fee_token_address = transaction.fee_token_address(&fee_token_config());
+
+    // Then
+    pretty_assertions::assert_eq!(fee_token_address, fee_token_config().eth);
+}
+
+#[test]
+fn test_fee_token_address_of_a_v3_transaction_is_strk() {
+    // Given
+    let transaction = Transaction { version: 3, ..Transaction::default() };
+
+    // When
+    let fee_token_address = transaction.fee_token_address(&fee_token_config());
+
+    // Then
+    pretty_assertions::assert_eq!(fee_token_address, fee_token_config().strk);
+}
+
+#[test]
+fn test_felt_count_of_an_invoke_counts_its_fields_calldata_and_signature() {
+    // Given
+    let transaction = Transaction {
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+            ..CallEntryPointWrapper::default()
+        },
+        signature: bounded_vec![Felt252Wrapper::THREE],
+        ..Transaction::default()
+    };
+
+    // When
+    let felt_count = transaction.felt_count();
+
+    // Then
+    // Fixed fields, the default entry point selector, calldata and signature.
+    pretty_assertions::assert_eq!(felt_count, 5 + 1 + 2 + 1);
+}
+
+#[test]
+fn test_felt_count_of_a_declare_counts_its_contract_class() {
+    // Given
+    let contract_class = ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap();
+    let declare = Transaction::builder()
+        .declare()
+        .class_hash(Felt252Wrapper::TWO)
+        .contract_class(contract_class.clone())
+        .build(ChainId::SN_GOERLI)
+        .unwrap();
+    let invoke = Transaction::builder().calldata(bounded_vec![Felt252Wrapper::ONE]).build(ChainId::SN_GOERLI).unwrap();
+
+    // When
+    let declare_count = declare.felt_count();
+    let invoke_count = invoke.felt_count();
+
+    // Then
+    assert!(contract_class.felt_count() > 0);
+    pretty_assertions::assert_eq!(declare_count, 5 + 1 + contract_class.felt_count());
+    assert!(declare_count > 10 * invoke_count);
+}
+
+#[test]
+fn test_total_weight_of_a_declare_outweighs_an_invoke_of_the_same_size() {
+    // Given
+    let contract_class = ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap();
+    let declare = Transaction::builder()
+        .declare()
+        .class_hash(Felt252Wrapper::TWO)
+        .contract_class(contract_class.clone())
+        .build(ChainId::SN_GOERLI)
+        .unwrap();
+    let padding = declare.felt_count() - Transaction::default().felt_count();
+    let invoke = Transaction {
+        call_entrypoint: CallEntryPointWrapper {
+            calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; padding]).unwrap(),
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+
+    // When
+    let declare_weight = declare.total_weight();
+    let invoke_weight = invoke.total_weight();
+
+    // Then
+    pretty_assertions::assert_eq!(declare.felt_count(), invoke.felt_count());
+    pretty_assertions::assert_eq!(
+        declare_weight - invoke_weight,
+        constants::DECLARE_BASE_WEIGHT - constants::INVOKE_BASE_WEIGHT
+            + contract_class.felt_count() as u64 * constants::WEIGHT_PER_CLASS_FELT
+    );
+}
+
+#[test]
+fn test_base_weight_orders_the_transaction_types() {
+    assert!(TxType::Declare.base_weight() > TxType::DeployAccount.base_weight());
+    assert!(TxType::DeployAccount.base_weight() > TxType::Invoke.base_weight());
+    assert!(TxType::Invoke.base_weight() > TxType::L1Handler.base_weight());
+    pretty_assertions::assert_eq!(
+        Transaction { tx_type: TxType::L1Handler, ..Transaction::default() }.total_weight(),
+        constants::L1_HANDLER_BASE_WEIGHT + Transaction::default().felt_count() as u64 * constants::WEIGHT_PER_FELT
+    );
+}
+
+#[test]
+fn test_encode_to_writer_matches_encode() {
+    // Given
+    let contract_class = ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap();
+    let declare = Transaction::builder()
+        .declare()
+        .class_hash(Felt252Wrapper::TWO)
+        .contract_class(contract_class)
+        .build(ChainId::SN_GOERLI)
+        .unwrap();
+    let deploy_account = Transaction { contract_address_salt: Some(Felt252Wrapper::THREE), ..Transaction::default() };
+
+    for tx in [Transaction::default(), declare, deploy_account] {
+        // When
+        let mut streamed = Vec::new();
+        tx.encode_to_writer(&mut streamed);
+
+        // Then
+        pretty_assertions::assert_eq!(streamed, scale_codec::Encode::encode(&tx));
+    }
+}
+
+#[test]
+fn test_try_from_deploy_rejects_constructor_calldata_too_long() {
+    // Given
+    let max_calldata = <DefaultTxLimits as TxLimits>::DeployConstructorMaxCalldata::get() as usize;
+    let at_limit = DeployAccountTransaction {
+        calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata]).unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+    let too_long = DeployAccountTransaction {
+        calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata + 1]).unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+
+    // When
+    let at_limit = at_limit.try_from_deploy::<DefaultTxLimits>(ChainId::SN_GOERLI);
+    let too_long = too_long.try_from_deploy::<DefaultTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(at_limit.is_ok());
+    assert!(matches!(too_long, Err(RPCTransactionConversionError::CalldataTooLong)));
+}
+
+#[test]
+fn test_try_from_invoke_rejects_signature_too_long() {
+    // Given
+    let max_signature = <DefaultTxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+    let at_limit = InvokeTransaction {
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature]).unwrap(),
+        ..InvokeTransaction::default()
+    };
+    let too_long = InvokeTransaction {
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature + 1]).unwrap(),
+        ..InvokeTransaction::default()
+    };
+
+    // When
+    let at_limit = at_limit.try_from_invoke::<DefaultTxLimits>(ChainId::SN_GOERLI);
+    let too_long = too_long.try_from_invoke::<DefaultTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(at_limit.is_ok());
+    assert!(matches!(too_long, Err(RPCTransactionConversionError::SignatureTooLong)));
+}
+
+#[test]
+fn test_try_from_deploy_rejects_signature_too_long() {
+    // Given
+    let max_signature = <DefaultTxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+    let transaction = DeployAccountTransaction {
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature + 1]).unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+
+    // When
+    let result = transaction.try_from_deploy::<DefaultTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::SignatureTooLong)));
+}
+
+#[test]
+fn test_try_from_invoke_accepts_calldata_within_limit() {
+    // Given
+    let invoke = InvokeTransaction { calldata: bounded_vec![Felt252Wrapper::ONE], ..InvokeTransaction::default() };
+
+    // When
+    let transaction = invoke.clone().try_from_invoke::<DefaultTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    pretty_assertions::assert_eq!(transaction.unwrap(), invoke.from_invoke(ChainId::SN_GOERLI).unwrap());
+}
+
+#[test]
+fn test_account_tx_type_excludes_l1_handler() {
+    // Given
+    let tx_types = TxType::all();
+
+    // When
+    let account_tx_types: Vec<Option<AccountTxType>> = tx_types
+        .iter()
+        .map(|tx_type| Transaction { tx_type: tx_type.clone(), ..Transaction::default() }.account_tx_type())
+        .collect();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        account_tx_types,
+        vec![Some(AccountTxType::Invoke), Some(AccountTxType::Declare), Some(AccountTxType::DeployAccount), None]
+    );
+    for (tx_type, account_tx_type) in tx_types.into_iter().zip(account_tx_types) {
+        if let Some(account_tx_type) = account_tx_type {
+            assert_eq!(TxType::from(account_tx_type), tx_type);
+        }
+    }
+}
+
+#[test]
+fn test_try_from_l1_handler_with_a_nonce_above_u64_fails() {
+    // Given
+    let tx = Transaction {
+        tx_type: TxType::L1Handler,
+        nonce: Felt252Wrapper::from(u128::from(u64::MAX) + 1),
+        ..Transaction::default()
+    };
+
+    // When
+    let result = starknet_core::types::Transaction::try_from(tx);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::ValueTooLarge)));
+}
+
+#[test]
+fn test_from_l1_message_prepends_the_sender_and_hashes_the_l1_handler() {
+    // Given
+    let from_address = Felt252Wrapper::from_hex_be("0xae0ee0a63a2ce6baeeffe56e7714fb4efe48d419").unwrap();
+    let to_address =
+        Felt252Wrapper::from_hex_be("0x073314940630fd6dcda0d772d4c972c4e0a9946bef9dabf4ef84eda8ef542b82").unwrap();
+    let selector = EntryPointSelectorWrapper(
+        Felt252Wrapper::from_hex_be("0x02d757788a8d8d6f21d1cd40bce38a8222d70654214e96ff95d8086e684fbee5").unwrap(),
+    );
+    let payload = [
+        Felt252Wrapper::from_hex_be("0x0218559e75713ee564d7e2f2a8c2ed08e3da3a8ce4c1f0f3a7f5d5e4f2a3c1b0").unwrap(),
+        Felt252Wrapper::from(1_000_000_000_000_000_u64),
+        Felt252Wrapper::ZERO,
+    ];
+
+    // When
+    let transaction = Transaction::from_l1_message(
+        from_address,
+        to_address,
+        selector,
+        &payload,
+        Felt252Wrapper::ONE,
+        Felt252Wrapper::TWO,
+        ChainId::SN_MAIN,
+    )
+    .unwrap();
+
+    // Then
+    // Computed outside of Madara, with the `l1_handler` hash formula of the cairo lang package.
+    let expected_hash =
+        Felt252Wrapper::from_hex_be("0x00faa6364505cec421ec6457003fb3280c14e182c4a61edc2e0217ef91b948f9").unwrap();
+    pretty_assertions::assert_eq!(transaction.hash, expected_hash);
+    pretty_assertions::assert_eq!(transaction.tx_type, TxType::L1Handler);
+    pretty_assertions::assert_eq!(transaction.sender_address, to_address);
+    pretty_assertions::assert_eq!(transaction.nonce, Felt252Wrapper::ONE);
+    pretty_assertions::assert_eq!(transaction.paid_fee_on_l1, Some(Felt252Wrapper::TWO));
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.entrypoint_type, EntryPointTypeWrapper::L1Handler);
+    pretty_assertions::assert_eq!(transaction.call_entrypoint.entrypoint_selector, Some(selector));
+    pretty_assertions::assert_eq!(
+        transaction.call_entrypoint.calldata.to_vec(),
+        [&[from_address][..], &payload[..]].concat()
+    );
+}
+
+#[test]
+fn test_from_l1_message_with_too_long_payload_fails() {
+    // Given
+    // The sender address prepended to the payload makes it one element too long.
+    let payload = vec![Felt252Wrapper::ONE; <TestTxLimits as TxLimits>::MaxCalldataSize::get() as usize];
+
+    // When
+    let result = Transaction::from_l1_message_with_limits::<TestTxLimits>(
+        Felt252Wrapper::ONE,
+        Felt252Wrapper::TWO,
+        EntryPointSelectorWrapper::default(),
+        &payload,
+        Felt252Wrapper::ZERO,
+        Felt252Wrapper::ONE,
+        ChainId::SN_MAIN,
+    );
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::CalldataTooLong)));
+}
+
+#[test]
+fn test_to_rpc_matches_the_consuming_conversion() {
+    // Given
+    let invoke = Transaction::default();
+    let deploy_account = Transaction {
+        tx_type: TxType::DeployAccount,
+        contract_address_salt: Some(Felt252Wrapper::THREE),
+        call_entrypoint: CallEntryPointWrapper {
+            class_hash: Some(Felt252Wrapper::TWO),
+            ..CallEntryPointWrapper::default()
+        },
+        ..Transaction::default()
+    };
+    let deploy = DeployTransaction {
+        version: 0,
+        class_hash: Felt252Wrapper::TWO,
+        contract_address_salt: Felt252Wrapper::THREE,
+        constructor_calldata: bounded_vec!(Felt252Wrapper::ONE),
+    }
+    .from_deploy_legacy(ChainId::SN_GOERLI);
+    let invalid = Transaction { tx_type: TxType::Declare, ..Transaction::default() };
+
+    for tx in [invoke, deploy_account, deploy] {
+        // When
+        let borrowed = tx.to_rpc().unwrap();
+
+        // Then
+        pretty_assertions::assert_eq!(borrowed, starknet_core::types::Transaction::try_from(tx).unwrap());
+    }
+    assert!(matches!(invalid.to_rpc(), Err(RPCTransactionConversionError::MissingInformation)));
+}
+
+#[test]
+fn test_try_from_invoke_v2_is_unsupported() {
+    // Given
+    let tx = Transaction { tx_type: TxType::Invoke, version: 2, ..Transaction::default() };
+
+    // When
+    let result = starknet_core::types::Transaction::try_from(tx);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::UnsupportedVersion(2))));
+}
+
+#[test]
+fn test_from_invoke_rejects_misplaced_entry_point_selectors() {
+    // Given
+    let v0_without_selector = InvokeTransaction { version: 0, ..InvokeTransaction::default() };
+    let v1_with_selector = InvokeTransaction {
+        version: 1,
+        entry_point_selector: Some(EntryPointSelectorWrapper::default()),
+        ..InvokeTransaction::default()
+    };
+
+    // When
+    let v0_borrowed = v0_without_selector.to_transaction(ChainId::SN_GOERLI);
+    let v1_borrowed = v1_with_selector.to_transaction(ChainId::SN_GOERLI);
+    let v0 = v0_without_selector.from_invoke(ChainId::SN_GOERLI);
+    let v1 = v1_with_selector.from_invoke(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(matches!(v0_borrowed, Err(RPCTransactionConversionError::InvalidEntryPointSelector)));
+    assert!(matches!(v1_borrowed, Err(RPCTransactionConversionError::InvalidEntryPointSelector)));
+    assert!(matches!(v0, Err(RPCTransactionConversionError::InvalidEntryPointSelector)));
+    assert!(matches!(v1, Err(RPCTransactionConversionError::InvalidEntryPointSelector)));
+}
+
+#[test]
+fn test_hash_preimage_of_an_invoke_v0_transaction_commits_to_the_selector() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let transaction = InvokeTransaction {
+        version: 0,
+        sender_address: Felt252Wrapper::THREE,
+        calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        max_fee: Felt252Wrapper::TWO,
+        entry_point_selector: Some(EntryPointSelectorWrapper(Felt252Wrapper::from(0x42_u64))),
+        ..InvokeTransaction::default()
+    }
+    .from_invoke(chain_id)
+    .unwrap();
+
+    // When
+    let preimage = transaction.hash_preimage(chain_id);
+
+    // Then
+    let calldata_hash = PedersenHasher::compute_hash_on_elements(&[FieldElement::ONE, FieldElement::TWO]);
+    let expected = vec![
+        Felt252Wrapper::from_bytes_be(b"invoke").unwrap(),
+        Felt252Wrapper::ZERO,
+        Felt252Wrapper::THREE,
+        Felt252Wrapper::from(0x42_u64),
+        Felt252Wrapper::from(calldata_hash),
+        Felt252Wrapper::TWO,
+        chain_id.into(),
+    ];
+    pretty_assertions::assert_eq!(preimage, expected);
+    let preimage: Vec<FieldElement> = preimage.into_iter().map(FieldElement::from).collect();
+    pretty_assertions::assert_eq!(
+        Felt252Wrapper::from(PedersenHasher::compute_hash_on_elements(&preimage)),
+        transaction.hash
+    );
+}
+
+#[test]
+fn test_invoke_v0_round_trip() {
+    // Given
+    let invoke = InvokeTransaction {
+        version: 0,
+        sender_address: Felt252Wrapper::from(0x101_u64),
+        calldata: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        signature: bounded_vec![Felt252Wrapper::THREE],
+        max_fee: Felt252Wrapper::from(100_u64),
+        entry_point_selector: Some(EntryPointSelectorWrapper(Felt252Wrapper::from(0x42_u64))),
+        ..InvokeTransaction::default()
+    };
+
+    // When
+    let tx = invoke.clone().from_invoke(ChainId::SN_GOERLI).unwrap();
+    let rpc = tx.to_rpc().unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        rpc,
+        starknet_core::types::Transaction::Invoke(starknet_core::types::InvokeTransaction::V0(
+            starknet_core::types::InvokeTransactionV0 {
+                transaction_hash: tx.hash.0,
+                max_fee: FieldElement::from(100_u64),
+                signature: vec![FieldElement::THREE],
+                nonce: FieldElement::ZERO,
+                contract_address: FieldElement::from(0x101_u64),
+                entry_point_selector: FieldElement::from(0x42_u64),
+                calldata: vec![FieldElement::ONE, FieldElement::TWO],
+            }
+        ))
+    );
+    pretty_assertions::assert_eq!(InvokeTransaction::from(tx), invoke);
+}
+
+#[test]
+fn test_invoke_v0_transaction_round_trip_keeps_the_selector_and_hash() {
+    // Given
+    let chain_id = ChainId::SN_GOERLI;
+    let contract_address = Felt252Wrapper::from(0x101_u64);
+    let mut tx = Transaction {
+        tx_type: TxType::Invoke,
+        version: 0,
+        sender_address: contract_address,
+        call_entrypoint: CallEntryPointWrapper::new(
+            None,
+            EntryPointTypeWrapper::External,
+            Some(EntryPointSelectorWrapper(Felt252Wrapper::from(0x42_u64))),
+            bounded_vec![Felt252Wrapper::ONE],
+            contract_address,
+            contract_address,
+        ),
+        max_fee: Felt252Wrapper::from(100_u64),
+        ..Transaction::default()
+    };
+    tx.hash = calculate_transaction_hash(&tx, chain_id);
+
+    let mut other_selector = tx.clone();
+    other_selector.call_entrypoint.entrypoint_selector =
+        Some(EntryPointSelectorWrapper(Felt252Wrapper::from(0x43_u64)));
+
+    // When
+    let round_trip = InvokeTransaction::from(tx.clone()).from_invoke(chain_id).unwrap();
+    let other_selector = InvokeTransaction::from(other_selector).from_invoke(chain_id).unwrap();
+
+    // Then
+    // Computed outside of Madara, with the v0 `invoke` hash formula of the cairo lang package.
+    let expected_hash =
+        Felt252Wrapper::from_hex_be("0x05f85f18c9f2678f7658610ba79e60514e52c2b80ec2575ca10f1df9d9af2ac0").unwrap();
+    pretty_assertions::assert_eq!(tx.hash, expected_hash);
+    pretty_assertions::assert_eq!(round_trip.hash, tx.hash);
+    pretty_assertions::assert_eq!(round_trip, tx);
+    assert_ne!(other_selector.hash, tx.hash);
+}
+
+#[test]
+fn test_to_broadcasted_invoke_omits_the_hash() {
+    // Given
+    let tx = InvokeTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(0x101_u64),
+        calldata: bounded_vec![Felt252Wrapper::ONE],
+        nonce: Felt252Wrapper::THREE,
+        signature: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        max_fee: Felt252Wrapper::from(100_u64),
+        entry_point_selector: None,
+    }
+    .from_invoke(ChainId::SN_GOERLI)
+    .unwrap();
+
+    // When
+    let broadcasted = tx.to_broadcasted().unwrap();
+
+    // Then
+    let broadcasted = match broadcasted {
+        BroadcastedTransaction::Invoke(broadcasted) => broadcasted,
+        _ => panic!("expected an invoke transaction"),
+    };
+    pretty_assertions::assert_eq!(broadcasted, broadcasted_invoke_v1(vec![FieldElement::ONE]));
+    pretty_assertions::assert_eq!(
+        InvokeTransaction::try_from(broadcasted).unwrap().from_invoke(ChainId::SN_GOERLI).unwrap().hash,
+        tx.hash
+    );
+}
+
+#[test]
+fn test_to_broadcasted_declare_includes_the_compressed_class() {
+    // Given
+    let contract_class = ContractClassWrapper::try_from(get_contract_class(TEST_CONTRACT_PATH)).unwrap();
+    let tx = DeclareTransaction {
+        version: 1,
+        sender_address: Felt252Wrapper::from(0x101_u64),
+        compiled_class_hash: Felt252Wrapper::TWO,
+        contract_class: contract_class.clone(),
+        nonce: Felt252Wrapper::ONE,
+        signature: bounded_vec![Felt252Wrapper::THREE],
+        max_fee: Felt252Wrapper::from(100_u64),
+    }
+    .from_declare(ChainId::SN_GOERLI);
+
+    // When
+    let broadcasted = tx.to_broadcasted().unwrap();
+
+    // Then
+    let broadcasted = match broadcasted {
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(broadcasted)) => broadcasted,
+        _ => panic!("expected a v1 declare transaction"),
+    };
+    pretty_assertions::assert_eq!(broadcasted.sender_address, FieldElement::from(0x101_u64));
+    pretty_assertions::assert_eq!(broadcasted.nonce, FieldElement::ONE);
+    pretty_assertions::assert_eq!(broadcasted.signature, vec![FieldElement::THREE]);
+    pretty_assertions::assert_eq!(broadcasted.max_fee, FieldElement::from(100_u64));
+    pretty_assertions::assert_eq!(
+        broadcasted.contract_class.program,
+        contract_class.to_compressed_contract_class().unwrap().program
+    );
+    pretty_assertions::assert_eq!(
+        DeclareTransaction::try_from(BroadcastedDeclareTransaction::V1(broadcasted)).unwrap().contract_class,
+        contract_class
+    );
+}
+
+#[test]
+fn test_to_broadcasted_fails_for_deploy_and_l1_handler_transactions() {
+    // Given
+    let deploy = Transaction { tx_type: TxType::Deploy, ..Transaction::default() };
+    let l1_handler = Transaction { tx_type: TxType::L1Handler, ..Transaction::default() };
+    let declare_without_class = Transaction { tx_type: TxType::Declare, version: 1, ..Transaction::default() };
+
+    // When
+    let results = [deploy.to_broadcasted(), l1_handler.to_broadcasted(), declare_without_class.to_broadcasted()];
+
+    // Then
+    assert!(matches!(results[0], Err(RPCTransactionConversionError::NotBroadcastable)));
+    assert!(matches!(results[1], Err(RPCTransactionConversionError::NotBroadcastable)));
+    assert!(matches!(results[2], Err(RPCTransactionConversionError::MissingInformation)));
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_try_from_arbitrary_transactions_does_not_panic() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut bytes = vec![0_u8; 4096];
+    for _ in 0..5000 {
+        rng.fill_bytes(&mut bytes);
+        let tx = match Transaction::arbitrary(&mut Unstructured::new(&bytes)) {
+            Ok(tx) => tx,
+            Err(_) => continue,
+        };
+
+        // Errors are expected, only a panic fails the test.
+        let _ = starknet_core::types::Transaction::try_from(tx);
+    }
+}
+
+fn broadcasted_invoke_v1(calldata: Vec<FieldElement>) -> BroadcastedInvokeTransaction {
+    BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1 {
+        max_fee: FieldElement::from(100_u64),
+        signature: vec![FieldElement::ONE, FieldElement::TWO],
+        nonce: FieldElement::THREE,
+        sender_address: FieldElement::from(0x101_u64),
+        calldata,
+    })
+}
+
+fn compressed_contract_class(program: Vec<u8>) -> CompressedLegacyContractClass {
+    CompressedLegacyContractClass {
+        program,
+        entry_points_by_type: LegacyEntryPointsByType { constructor: vec![], external: vec![], l1_handler: vec![] },
+        abi: None,
+    }
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    std::io::Write::write_all(&mut encoder, data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_try_from_broadcasted_invoke_v1() {
+    // Given
+    let tx = broadcasted_invoke_v1(vec![FieldElement::ONE]);
+
+    // When
+    let invoke = InvokeTransaction::try_from(tx).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        invoke,
+        InvokeTransaction {
+            version: 1,
+            sender_address: Felt252Wrapper::from(0x101_u64),
+            calldata: bounded_vec![Felt252Wrapper::ONE],
+            nonce: Felt252Wrapper::THREE,
+            signature: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+            max_fee: Felt252Wrapper::from(100_u64),
+            entry_point_selector: None,
+        }
+    );
+}
+
+#[test]
+fn test_try_from_broadcasted_invoke_v0() {
+    // Given
+    let tx = BroadcastedInvokeTransaction::V0(BroadcastedInvokeTransactionV0 {
+        max_fee: FieldElement::ZERO,
+        signature: vec![],
+        nonce: FieldElement::ZERO,
+        contract_address: FieldElement::ONE,
+        entry_point_selector: FieldElement::TWO,
+        calldata: vec![FieldElement::THREE],
+    });
+
+    // When
+    let invoke = InvokeTransaction::try_from(tx).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(
+        invoke,
+        InvokeTransaction {
+            version: 0,
+            sender_address: Felt252Wrapper::ONE,
+            calldata: bounded_vec![Felt252Wrapper::THREE],
+            entry_point_selector: Some(EntryPointSelectorWrapper(Felt252Wrapper::TWO)),
+            ..InvokeTransaction::default()
+        }
+    );
+}
+
+#[test]
+fn test_to_broadcasted_invoke_v0_round_trip() {
+    // Given
+    let tx = InvokeTransaction {
+        version: 0,
+        sender_address: Felt252Wrapper::from(0x101_u64),
+        calldata: bounded_vec![Felt252Wrapper::ONE],
+        nonce: Felt252Wrapper::ZERO,
+        signature: bounded_vec![Felt252Wrapper::ONE, Felt252Wrapper::TWO],
+        max_fee: Felt252Wrapper::from(100_u64),
+        entry_point_selector: Some(EntryPointSelectorWrapper(Felt252Wrapper::THREE)),
+    }
+    .from_invoke(ChainId::SN_GOERLI)
+    .unwrap();
+
+    // When
+    let broadcasted = match tx.to_broadcasted().unwrap() {
+        BroadcastedTransaction::Invoke(broadcasted) => broadcasted,
+        _ => panic!("expected an invoke transaction"),
+    };
+    let round_trip = InvokeTransaction::try_from(broadcasted).unwrap().from_invoke(ChainId::SN_GOERLI).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(round_trip, tx);
+}
+
+/// Limits small enough to be exceeded in tests without allocating the default bounds.
+struct TestTxLimits;
+
+impl TxLimits for TestTxLimits {
+    type MaxArraySize = ConstU32<8>;
+    type MaxSignatureSize = ConstU32<2>;
+    type MaxCalldataSize = ConstU32<4>;
+    type InvokeMaxCalldata = ConstU32<4>;
+    type DeployConstructorMaxCalldata = ConstU32<2>;
+}
+
+#[test]
+fn test_try_from_deploy_checks_the_given_limits() {
+    // Given
+    let max_calldata = <TestTxLimits as TxLimits>::DeployConstructorMaxCalldata::get() as usize;
+    let max_signature = <TestTxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+    let at_limit = DeployAccountTransaction {
+        calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata]).unwrap(),
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature]).unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+    let calldata_too_long = DeployAccountTransaction {
+        calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata + 1]).unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+    let signature_too_long = DeployAccountTransaction {
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature + 1]).unwrap(),
+        ..DeployAccountTransaction::default()
+    };
+
+    // When
+    let at_limit = at_limit.try_from_deploy::<TestTxLimits>(ChainId::SN_GOERLI);
+    let calldata_too_long = calldata_too_long.try_from_deploy::<TestTxLimits>(ChainId::SN_GOERLI);
+    let signature_too_long = signature_too_long.try_from_deploy::<TestTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(at_limit.is_ok());
+    assert!(matches!(calldata_too_long, Err(RPCTransactionConversionError::CalldataTooLong)));
+    assert!(matches!(signature_too_long, Err(RPCTransactionConversionError::SignatureTooLong)));
+}
+
+#[test]
+fn test_try_from_invoke_checks_the_given_limits() {
+    // Given
+    let max_calldata = <TestTxLimits as TxLimits>::InvokeMaxCalldata::get() as usize;
+    let max_signature = <TestTxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+    let at_limit = InvokeTransaction {
+        calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata]).unwrap(),
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature]).unwrap(),
+        ..InvokeTransaction::default()
+    };
+    let calldata_too_long = InvokeTransaction {
+        calldata: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_calldata + 1]).unwrap(),
+        ..InvokeTransaction::default()
+    };
+    let signature_too_long = InvokeTransaction {
+        signature: BoundedVec::try_from(vec![Felt252Wrapper::ONE; max_signature + 1]).unwrap(),
+        ..InvokeTransaction::default()
+    };
+
+    // When
+    let at_limit = at_limit.try_from_invoke::<TestTxLimits>(ChainId::SN_GOERLI);
+    let calldata_too_long = calldata_too_long.try_from_invoke::<TestTxLimits>(ChainId::SN_GOERLI);
+    let signature_too_long = signature_too_long.try_from_invoke::<TestTxLimits>(ChainId::SN_GOERLI);
+
+    // Then
+    assert!(at_limit.is_ok());
+    assert!(matches!(calldata_too_long, Err(RPCTransactionConversionError::CalldataTooLong)));
+    assert!(matches!(signature_too_long, Err(RPCTransactionConversionError::SignatureTooLong)));
+}
+
+#[test]
+fn test_try_from_broadcasted_invoke_within_calldata_limit() {
+    // Given
+    let max_calldata = <TestTxLimits as TxLimits>::InvokeMaxCalldata::get() as usize;
+    let tx = broadcasted_invoke_v1(vec![FieldElement::ONE; max_calldata]);
+
+    // When
+    let result = InvokeTransaction::try_from_broadcasted::<TestTxLimits>(tx);
+
+    // Then
+    pretty_assertions::assert_eq!(result.unwrap().calldata.len(), max_calldata);
+}
+
+#[test]
+fn test_try_from_broadcasted_invoke_with_too_long_calldata_fails() {
+    // Given
+    let max_calldata = <TestTxLimits as TxLimits>::InvokeMaxCalldata::get() as usize;
+    let tx = broadcasted_invoke_v1(vec![FieldElement::ONE; max_calldata + 1]);
+
+    // When
+    let result = InvokeTransaction::try_from_broadcasted::<TestTxLimits>(tx);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::CalldataTooLong)));
+}
+
+#[test]
+fn test_try_from_broadcasted_invoke_with_too_many_signatures_fails() {
+    // Given
+    let max_signature = <TestTxLimits as TxLimits>::MaxSignatureSize::get() as usize;
+    let tx = BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1 {
+        max_fee: FieldElement::from(100_u64),
+        signature: vec![FieldElement::ONE; max_signature + 1],
+        nonce: FieldElement::THREE,
+        sender_address: FieldElement::from(0x101_u64),
+        calldata: vec![],
+    });
+
+    // When
+    let result = InvokeTransaction::try_from_broadcasted::<TestTxLimits>(tx);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::SignatureTooLong)));
+}
+
+#[test]
+fn test_try_from_broadcasted_deploy_account() {
+    // Given
+    let tx = BroadcastedDeployAccountTransaction {
+        max_fee: FieldElement::ONE,
+        signature: vec![FieldElement::TWO],
+        nonce: FieldElement::ZERO,
+        contract_address_salt: FieldElement::THREE,
+        constructor_calldata: vec![FieldElement::ONE],
+        class_hash: FieldElement::from(0x111_u64),
+    };
+
+    // When
+    let deploy = DeployAccountTransaction::try_from(tx).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(deploy.salt, U256::from(3));
+    pretty_assertions::assert_eq!(deploy.account_class_hash, Felt252Wrapper::from(0x111_u64));
+    pretty_assertions::assert_eq!(
+        deploy.calldata,
+        BoundedVec::<_, MaxCalldataSize>::truncate_from(vec![Felt252Wrapper::ONE])
+    );
+    assert_ne!(deploy.sender_address, ContractAddressWrapper::default());
+}
+
+#[test]
+fn test_try_from_broadcasted_deploy_account_with_too_many_signatures_fails() {
+    // Given
+    let tx = BroadcastedDeployAccountTransaction {
+        max_fee: FieldElement::ONE,
+        signature: vec![FieldElement::TWO; MaxArraySize::get() as usize + 1],
+        nonce: FieldElement::ZERO,
+        contract_address_salt: FieldElement::THREE,
+        constructor_calldata: vec![],
+        class_hash: FieldElement::from(0x111_u64),
+    };
+
+    // When
+    let result = DeployAccountTransaction::try_from(tx);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::ArrayTooLong)));
+}
+
+#[test]
+fn test_try_from_broadcasted_declare_v1_decompresses_the_program() {
+    // Given
+    let contract_class: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(TEST_CONTRACT_PATH).unwrap()).unwrap();
+    let program = gzip(&serde_json::to_vec(&contract_class["program"]).unwrap());
+    let tx = BroadcastedDeclareTransaction::V1(BroadcastedDeclareTransactionV1 {
+        max_fee: FieldElement::ONE,
+        signature: vec![],
+        nonce: FieldElement::ZERO,
+        contract_class: compressed_contract_class(program).into(),
+        sender_address: FieldElement::from(0x101_u64),
+    });
+
+    // When
+    let declare = DeclareTransaction::try_from(tx).unwrap();
+
+    // Then
+    pretty_assertions::assert_eq!(declare.sender_address, Felt252Wrapper::from(0x101_u64));
+    assert_ne!(declare.contract_class.program, Default::default());
+    pretty_assertions::assert_eq!(
+        declare.contract_class.entry_points_by_type.keys().cloned().collect::<Vec<_>>(),
+        vec![EntryPointTypeWrapper::Constructor, EntryPointTypeWrapper::External, EntryPointTypeWrapper::L1Handler]
+    );
+}
+
+#[test]
+fn test_try_from_broadcasted_declare_v1_with_an_invalid_program_fails() {
+    // Given
+    let tx = BroadcastedDeclareTransaction::V1(BroadcastedDeclareTransactionV1 {
+        max_fee: FieldElement::ONE,
+        signature: vec![],
+        nonce: FieldElement::ZERO,
+        contract_class: compressed_contract_class(gzip(b"not a program")).into(),
+        sender_address: FieldElement::from(0x101_u64),
+    });
+
+    // When
+    let result = DeclareTransaction::try_from(tx);
+
+    // Then
+    assert!(matches!(result, Err(RPCTransactionConversionError::InvalidContractClass)));
+}
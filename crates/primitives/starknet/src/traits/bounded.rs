@@ -0,0 +1,21 @@
+//! Capacity of the bounded collections.
+
+use frame_support::BoundedVec;
+use sp_core::Get;
+
+/// Space left in a bounded collection, to check that elements fit before pushing them.
+pub trait BoundedCapacity {
+    /// Returns the number of elements that can still be pushed.
+    fn remaining_capacity(&self) -> usize;
+
+    /// Returns `true` if `n` more elements can be pushed.
+    fn can_fit(&self, n: usize) -> bool {
+        n <= self.remaining_capacity()
+    }
+}
+
+impl<T, S: Get<u32>> BoundedCapacity for BoundedVec<T, S> {
+    fn remaining_capacity(&self) -> usize {
+        (S::get() as usize).saturating_sub(self.len())
+    }
+}
@@ -3,6 +3,8 @@
 use starknet_crypto::FieldElement;
 
 use crate::execution::felt252_wrapper::Felt252Wrapper;
+use crate::execution::types::ChainId;
+use crate::transaction::types::{DeclareTransaction, DeployAccountTransaction, DeployTransaction, InvokeTransaction};
 
 /// A trait for hashing.
 pub trait HasherT {
@@ -49,3 +51,43 @@ pub trait CryptoHasherT {
     /// The hash of the array.
     fn compute_hash_on_elements(elements: &[FieldElement]) -> FieldElement;
 }
+
+/// A trait for the hashing rules of the transactions.
+///
+/// The hash of a transaction depends on the Starknet version, and appchains may use their own
+/// rules. The rules of Starknet are implemented by [`crate::crypto::commitment::StarknetV0`],
+/// which is what the `from_*` conversions to [`crate::transaction::types::Transaction`] use
+/// unless another hasher is given to their `from_*_with_hasher` counterpart.
+pub trait TransactionHasher {
+    /// Computes the hash of an invoke transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to hash.
+    /// * `chain_id` - The chain id of the network.
+    fn invoke_tx_hash(transaction: &InvokeTransaction, chain_id: ChainId) -> Felt252Wrapper;
+
+    /// Computes the hash of a declare transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to hash.
+    /// * `chain_id` - The chain id of the network.
+    fn declare_tx_hash(transaction: &DeclareTransaction, chain_id: ChainId) -> Felt252Wrapper;
+
+    /// Computes the hash of a deploy account transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to hash.
+    /// * `chain_id` - The chain id of the network.
+    fn deploy_account_tx_hash(transaction: &DeployAccountTransaction, chain_id: ChainId) -> Felt252Wrapper;
+
+    /// Computes the hash of a legacy deploy transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to hash.
+    /// * `chain_id` - The chain id of the network.
+    fn deploy_tx_hash(transaction: &DeployTransaction, chain_id: ChainId) -> Felt252Wrapper;
+}
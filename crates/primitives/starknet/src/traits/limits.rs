@@ -0,0 +1,47 @@
+//! Traits for the transaction size limits.
+
+use sp_core::{ConstU32, Get};
+
+/// Size limits of the transactions.
+///
+/// The runtime provides its own implementation through the pallet `Config` so the caps can be
+/// tuned per deployment (e.g. a testnet vs a high-throughput appchain) without touching the
+/// primitives.
+///
+/// The bounds of the `BoundedVec`s stored on chain are [`DefaultTxLimits`] values: a runtime
+/// can only lower them at admission. Raising them requires changing [`DefaultTxLimits`], which
+/// changes the `MaxEncodedLen` of the stored types and therefore needs a storage migration;
+/// already encoded values stay decodable since SCALE doesn't encode the bound itself.
+pub trait TxLimits {
+    /// Max number of elements of the transaction arrays (signature, events, ...).
+    type MaxArraySize: Get<u32>;
+    /// Max number of signature elements of a transaction.
+    ///
+    /// Checked at admission, below `MaxArraySize` which stays the storage bound of the signature.
+    /// Chains whose accounts use bigger signatures (e.g. multisig) can raise it up to
+    /// `MaxArraySize`.
+    type MaxSignatureSize: Get<u32>;
+    /// Max number of calldata elements of a transaction.
+    type MaxCalldataSize: Get<u32>;
+    /// Max number of calldata elements of an invoke transaction.
+    type InvokeMaxCalldata: Get<u32>;
+    /// Max number of constructor calldata elements of a deploy account transaction.
+    type DeployConstructorMaxCalldata: Get<u32>;
+}
+
+/// Default transaction size limits.
+pub struct DefaultTxLimits;
+
+impl TxLimits for DefaultTxLimits {
+    // TODO: add real value (#250)
+    type MaxArraySize = ConstU32<10000>;
+    // An ECDSA signature is 2 felts, 64 leaves room for multisig and other schemes without letting
+    // a cheap transaction carry thousands of signature felts.
+    type MaxSignatureSize = ConstU32<64>;
+    type MaxCalldataSize = ConstU32<{ u32::MAX }>;
+    // Same as `MaxCalldataSize`: a multicall invoke can carry any number of calls.
+    type InvokeMaxCalldata = ConstU32<{ u32::MAX }>;
+    // Account constructors only take a few arguments (e.g. a public key, or the implementation,
+    // the initializer selector and its calldata for a proxy), 256 leaves plenty of room.
+    type DeployConstructorMaxCalldata = ConstU32<256>;
+}
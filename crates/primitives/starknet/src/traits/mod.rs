@@ -1,5 +1,9 @@
+/// Define traits related to the capacity of bounded collections.
+pub mod bounded;
 /// Define traits related to hash functions.
 pub mod hash;
+/// Define traits related to the transaction size limits.
+pub mod limits;
 
 /// A trait for types that can be shared between threads + copied.
 pub trait ThreadSafeCopy: Send + Sync + Copy + 'static {}
@@ -0,0 +1,61 @@
+//! Bloom filter over the keys and emitters of the events of a transaction.
+//!
+//! Each felt sets [`BLOOM_HASHES`] bits of a [`BLOOM_BITS`] bits filter, picked from its blake2
+//! hash. A filter never misses a felt that was added to it, but may report felts that weren't,
+//! which makes it suited to skip the transactions that can't match an events query.
+use frame_support::Hashable;
+
+use crate::execution::types::Felt252Wrapper;
+
+/// Size of a [`Bloom`] filter, in bytes.
+pub const BLOOM_BYTES: usize = 256;
+/// Size of a [`Bloom`] filter, in bits.
+pub const BLOOM_BITS: usize = BLOOM_BYTES * 8;
+/// Number of bits set in a [`Bloom`] filter for each felt.
+pub const BLOOM_HASHES: usize = 3;
+
+/// Bloom filter over felts, see the [module](self) documentation.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+pub struct Bloom(pub [u8; BLOOM_BYTES]);
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self([0; BLOOM_BYTES])
+    }
+}
+
+impl Bloom {
+    /// Adds a felt to the filter.
+    pub fn accrue(&mut self, value: Felt252Wrapper) {
+        for bit in Self::bits(value) {
+            self.0[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Returns whether the felt may have been added to the filter.
+    ///
+    /// A `false` is definitive, a `true` may be a false positive.
+    pub fn might_contain(&self, value: Felt252Wrapper) -> bool {
+        Self::bits(value).into_iter().all(|bit| self.0[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Returns the bits of the filter set for a felt, each taken from two bytes of its hash.
+    fn bits(value: Felt252Wrapper) -> [usize; BLOOM_HASHES] {
+        let hash = <[u8; 32]>::from(value).blake2_256();
+        let mut bits = [0; BLOOM_HASHES];
+        for (i, bit) in bits.iter_mut().enumerate() {
+            *bit = usize::from(u16::from_be_bytes([hash[2 * i], hash[2 * i + 1]])) % BLOOM_BITS;
+        }
+        bits
+    }
+}
@@ -1,7 +1,3 @@
-use blockifier::abi::abi_utils::selector_from_name;
-use lazy_static::lazy_static;
-use starknet_api::api_core::EntryPointSelector;
-
 /// validate entry point name
 pub const VALIDATE_ENTRY_POINT_NAME: &str = "__validate__";
 /// validate declare entry point name
@@ -9,11 +5,16 @@ pub const VALIDATE_DECLARE_ENTRY_POINT_NAME: &str = "__validate_declare__";
 /// validate deploy entry point name
 pub const VALIDATE_DEPLOY_ENTRY_POINT_NAME: &str = "__validate_deploy__";
 
-lazy_static! {
-    /// validate entry point selector
-    pub static ref VALIDATE_ENTRY_POINT_SELECTOR: EntryPointSelector = selector_from_name(VALIDATE_ENTRY_POINT_NAME);
-    /// validate declare entry point selector
-    pub static ref VALIDATE_DECLARE_ENTRY_POINT_SELECTOR: EntryPointSelector = selector_from_name(VALIDATE_DECLARE_ENTRY_POINT_NAME);
-    /// validate deploy entry point selector
-    pub static ref VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR: EntryPointSelector = selector_from_name(VALIDATE_DEPLOY_ENTRY_POINT_NAME);
-}
+/// Base weight of an invoke transaction, see [`crate::transaction::types::TxType::base_weight`].
+pub const INVOKE_BASE_WEIGHT: u64 = 2_000;
+/// Base weight of a declare transaction, the heaviest as it adds a class to the state.
+pub const DECLARE_BASE_WEIGHT: u64 = 10_000;
+/// Base weight of a deploy account and of a legacy deploy transaction.
+pub const DEPLOY_ACCOUNT_BASE_WEIGHT: u64 = 4_000;
+/// Base weight of an L1 handler transaction, the lightest as it isn't validated nor charged.
+pub const L1_HANDLER_BASE_WEIGHT: u64 = 1_000;
+/// Weight of each felt of a transaction, see
+/// [`crate::transaction::types::Transaction::felt_count`].
+pub const WEIGHT_PER_FELT: u64 = 10;
+/// Weight added on top of [`WEIGHT_PER_FELT`] for each felt of a declared contract class.
+pub const WEIGHT_PER_CLASS_FELT: u64 = 20;
@@ -1,11 +1,19 @@
 //! Starknet transaction related functionality.
+/// Bloom filter over the keys and emitters of events.
+pub mod bloom;
+/// Compact binary encoding of transactions.
+pub mod compact;
 /// Constants related to transactions.
 pub mod constants;
 /// Types related to transactions.
 pub mod types;
+/// Checks of the value returned by the account validation.
+pub mod validation;
 
+use alloc::collections::BTreeMap;
 use alloc::string::{String, ToString};
-use alloc::vec;
+use alloc::sync::Arc;
+use alloc::{format, vec};
 
 use blockifier::block_context::BlockContext;
 use blockifier::execution::contract_class::ContractClass;
@@ -13,14 +21,16 @@ use blockifier::execution::entry_point::{CallEntryPoint, CallInfo, CallType, Exe
 use blockifier::execution::errors::EntryPointExecutionError;
 use blockifier::state::state_api::State;
 use blockifier::transaction::errors::TransactionExecutionError;
-use blockifier::transaction::objects::AccountTransactionContext;
+use blockifier::transaction::objects::{AccountTransactionContext, TransactionExecutionInfo};
 use blockifier::transaction::transaction_utils::verify_no_calls_to_other_contracts;
 use blockifier::transaction::transactions::Executable;
+use frame_support::traits::Get;
 use frame_support::BoundedVec;
+use scale_codec::{Encode, Output};
 use sp_core::U256;
 use starknet_api::api_core::{ChainId, ContractAddress as StarknetContractAddress, EntryPointSelector, Nonce};
 use starknet_api::deprecated_contract_class::EntryPointType;
-use starknet_api::hash::{StarkFelt, StarkHash};
+use starknet_api::hash::StarkFelt;
 use starknet_api::transaction::{
     Calldata, ContractAddressSalt, DeclareTransaction, DeclareTransactionV0V1, DeployAccountTransaction, EventContent,
     Fee, InvokeTransactionV1, L1HandlerTransaction, TransactionHash, TransactionOutput, TransactionReceipt,
@@ -29,34 +39,75 @@ use starknet_api::transaction::{
 use starknet_api::{calldata, StarknetApiError};
 
 use self::types::{
-    EventError, EventWrapper, MaxArraySize, Transaction, TransactionExecutionErrorWrapper,
-    TransactionExecutionInfoWrapper, TransactionExecutionResultWrapper, TransactionReceiptWrapper,
-    TransactionValidationErrorWrapper, TransactionValidationResultWrapper, TxType,
+    AccountTxType, CallTraceWrapper, EventAccumulator, EventError, EventOverflow, EventWrapper, ExecutionStatus,
+    FeeUnit, FieldDiff, IndexedEvent, MaxArraySize, RPCTransactionConversionError, SimulationFlags, Transaction,
+    TransactionConversionError, TransactionExecutionErrorWrapper, TransactionExecutionInfoWrapper,
+    TransactionExecutionResultWrapper, TransactionReceiptWrapper, TransactionTraceWrapper,
+    TransactionValidationErrorWrapper, TransactionValidationResultWrapper, TxType, ValidationContext,
 };
 use crate::block::Block as StarknetBlock;
-use crate::execution::types::{CallEntryPointWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper};
-use crate::fees::{self, charge_fee};
-use crate::state::StateChanges;
+use crate::crypto::commitment::{
+    calculate_l1_handler_tx_hash, calculate_transaction_hash, calculate_transaction_hash_preimage,
+};
+use crate::execution::call_entrypoint_wrapper::MaxCalldataSize;
+use crate::execution::types::{
+    CallEntryPointWrapper, ChainId as ChainIdWrapper, ClassHashWrapper, ContractAddressWrapper, ContractClassWrapper,
+    EntryPointSelector as EntryPointSelectorWrapper, EntryPointTypeWrapper, Felt252Wrapper,
+};
+use crate::fees::{self, charge_fee, FeeTokenConfig};
+use crate::state::{StateChanges, StateRollback};
+use crate::traits::bounded::BoundedCapacity;
+use crate::traits::limits::{DefaultTxLimits, TxLimits};
 
 impl EventWrapper {
     /// Creates a new instance of an event.
     ///
+    /// Events received as input are expected to carry their selector as first key, so they're
+    /// rejected without keys. A contract can still emit an event without keys: events collected
+    /// from an execution are built with [`EventBuilder::build`], which keeps them as emitted.
+    ///
     /// # Arguments
     ///
     /// * `keys` - Event keys.
     /// * `data` - Event data.
     /// * `from_address` - Contract Address where the event was emitted from.
     /// * `transaction_hash` - Transaction hash where the event was emitted from.
+    ///
+    /// # Errors
+    ///
+    /// [`EventError::InvalidKeys`] if there are no keys or more than [`MaxArraySize`], and
+    /// [`EventError::InvalidData`] if there is more data than [`MaxArraySize`].
     pub fn new(
-        keys: BoundedVec<Felt252Wrapper, MaxArraySize>,
-        data: BoundedVec<Felt252Wrapper, MaxArraySize>,
+        keys: vec::Vec<Felt252Wrapper>,
+        data: vec::Vec<Felt252Wrapper>,
         from_address: ContractAddressWrapper,
         transaction_hash: Felt252Wrapper,
-    ) -> Self {
-        Self { keys, data, from_address, transaction_hash }
+    ) -> Result<Self, EventError> {
+        if keys.is_empty() {
+            return Err(EventError::InvalidKeys);
+        }
+        Self::bounded(keys, data, from_address, transaction_hash)
+    }
+
+    /// Creates an event, only checking that its keys and data fit in [`MaxArraySize`].
+    fn bounded(
+        keys: vec::Vec<Felt252Wrapper>,
+        data: vec::Vec<Felt252Wrapper>,
+        from_address: ContractAddressWrapper,
+        transaction_hash: Felt252Wrapper,
+    ) -> Result<Self, EventError> {
+        Ok(Self {
+            keys: BoundedVec::try_from(keys).map_err(|_| EventError::InvalidKeys)?,
+            data: BoundedVec::try_from(data).map_err(|_| EventError::InvalidData)?,
+            from_address,
+            transaction_hash,
+        })
     }
 
     /// Creates an empty event.
+    ///
+    /// The event has no keys so it isn't a valid Starknet event, it's meant as a base to build
+    /// events field by field.
     pub fn empty() -> Self {
         Self {
             keys: BoundedVec::try_from(vec![]).unwrap(),
@@ -70,6 +121,24 @@ impl EventWrapper {
     pub fn builder() -> EventBuilder {
         EventBuilder::default()
     }
+
+    /// Attaches the position of the event in the chain, see [`IndexedEvent`].
+    ///
+    /// # Arguments
+    ///
+    /// * `block_number` - The number of the block the event was emitted in.
+    /// * `block_hash` - The hash of the block the event was emitted in.
+    /// * `transaction_index` - The index of the emitting transaction within the block.
+    /// * `event_index` - The index of the event within the events of the transaction.
+    pub fn with_index(
+        self,
+        block_number: u64,
+        block_hash: Felt252Wrapper,
+        transaction_index: u64,
+        event_index: u64,
+    ) -> IndexedEvent {
+        IndexedEvent { event: self, block_number, block_hash, transaction_index, event_index }
+    }
 }
 
 /// Builder pattern for `EventWrapper`.
@@ -136,20 +205,25 @@ impl EventBuilder {
     }
 
     /// Builds the event.
+    ///
+    /// Unlike [`EventWrapper::new`], an event without keys is built: a contract can emit one and
+    /// the receipt records the events as they were emitted.
+    ///
+    /// # Errors
+    ///
+    /// [`EventError::InvalidKeys`] if there are more keys than [`MaxArraySize`],
+    /// [`EventError::InvalidData`] if there is more data than [`MaxArraySize`], and
+    /// [`EventError::InvalidFromAddress`] if the from address isn't a valid felt.
     pub fn build(self) -> Result<EventWrapper, EventError> {
-        Ok(EventWrapper {
-            keys: BoundedVec::try_from(self.keys).map_err(|_| EventError::InvalidKeys)?,
-            data: BoundedVec::try_from(self.data).map_err(|_| EventError::InvalidData)?,
-            from_address: self
-                .from_address
-                .unwrap_or_default()
-                .0
-                .key()
-                .bytes()
-                .try_into()
-                .map_err(|_| EventError::InvalidFromAddress)?,
-            transaction_hash: self.transaction_hash.unwrap_or_default().0.into(),
-        })
+        let from_address = self
+            .from_address
+            .unwrap_or_default()
+            .0
+            .key()
+            .bytes()
+            .try_into()
+            .map_err(|_| EventError::InvalidFromAddress)?;
+        EventWrapper::bounded(self.keys, self.data, from_address, self.transaction_hash.unwrap_or_default().0.into())
     }
 }
 
@@ -170,30 +244,285 @@ impl TryInto<TransactionReceiptWrapper> for &TransactionReceipt {
     type Error = EventError;
 
     fn try_into(self) -> Result<TransactionReceiptWrapper, Self::Error> {
-        let _events: Result<vec::Vec<EventWrapper>, EventError> = self
-            .output
-            .events()
-            .iter()
-            .map(|e| {
-                EventWrapper::builder().with_event_content(e.content.clone()).with_from_address(e.from_address).build()
-            })
-            .collect();
+        let mut events = EventAccumulator::new();
+        for event in self.output.events() {
+            events.push(
+                EventWrapper::builder()
+                    .with_event_content(event.content.clone())
+                    .with_from_address(event.from_address)
+                    .build()?,
+            )?;
+        }
 
         Ok(TransactionReceiptWrapper {
             transaction_hash: self.transaction_hash.0.into(),
-            actual_fee: U256::from(self.output.actual_fee().0).try_into().expect("Actual fee too large for felt252."),
+            actual_fee: self.output.actual_fee().into(),
+            fee_unit: FeeUnit::Wei,
             tx_type: match self.output {
                 TransactionOutput::Declare(_) => TxType::Declare,
+                TransactionOutput::Deploy(_) => TxType::Deploy,
                 TransactionOutput::DeployAccount(_) => TxType::DeployAccount,
                 TransactionOutput::Invoke(_) => TxType::Invoke,
                 TransactionOutput::L1Handler(_) => TxType::L1Handler,
-                _ => TxType::Invoke,
             },
-            block_hash: self.block_hash.0.into(),
-            block_number: self.block_number.0,
-            events: BoundedVec::try_from(_events?).map_err(|_| EventError::TooManyEvents)?,
+            block_hash: Some(self.block_hash.0.into()),
+            block_number: Some(self.block_number.0),
+            block_timestamp: 0,
+            sequencer_address: ContractAddressWrapper::default(),
+            class_hash: None,
+            contract_address: None,
+            events: events.into_events(),
+            execution_status: ExecutionStatus::Succeeded,
+            revert_reason: None,
+            events_truncated: false,
+        })
+    }
+}
+
+impl EventAccumulator {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes an event.
+    ///
+    /// # Errors
+    ///
+    /// An [EventOverflow] locating the event if the accumulator already holds [MaxArraySize]
+    /// events, it converts into [EventError::TooManyEvents].
+    pub fn push(&mut self, event: EventWrapper) -> Result<(), EventOverflow> {
+        let index = self.events.len();
+        let from_address = event.from_address;
+        self.events.try_push(event).map_err(|_| EventOverflow { index, from_address })
+    }
+
+    /// Returns the number of events pushed.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no event was pushed.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the events, in the order they were pushed.
+    pub fn into_events(self) -> BoundedVec<EventWrapper, MaxArraySize> {
+        self.events
+    }
+}
+
+impl BoundedCapacity for EventAccumulator {
+    fn remaining_capacity(&self) -> usize {
+        self.events.remaining_capacity()
+    }
+}
+
+/// The blockifier doesn't track the state diff, the revert reason nor the execution duration of
+/// a transaction: they are left empty.
+impl From<TransactionExecutionInfo> for TransactionExecutionInfoWrapper {
+    fn from(info: TransactionExecutionInfo) -> Self {
+        Self {
+            validate_call_info: info.validate_call_info,
+            execute_call_info: info.execute_call_info,
+            fee_transfer_call_info: info.fee_transfer_call_info,
+            actual_fee: info.actual_fee,
+            actual_resources: info.actual_resources.0.into_iter().collect(),
+            revert_error: None,
+            state_diff: None,
+            execution_duration_micros: None,
+        }
+    }
+}
+
+impl TransactionExecutionInfoWrapper {
+    /// Returns the time spent executing the transaction, in microseconds.
+    ///
+    /// It's measured with a monotonic clock by [`Transaction::simulate`], only when built with
+    /// `std`.
+    pub fn execution_duration_micros(&self) -> Option<u64> {
+        self.execution_duration_micros
+    }
+
+    /// Builds the receipt of the executed transaction.
+    ///
+    /// The events of the validate, execute and fee transfer calls are collected, in that order, by
+    /// walking each call tree in pre-order: the events of a call, sorted by their order, come
+    /// before the events of its inner calls.
+    ///
+    /// The receipt has no class hash nor contract address and its fee unit is the default one,
+    /// they are left to the caller as they depend on the transaction. It's a pending receipt, its
+    /// block number and hash are set when the block including it is finalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The execution info of the transaction.
+    /// * `tx_hash` - The hash of the transaction.
+    /// * `tx_type` - The type of the transaction.
+    ///
+    /// # Errors
+    ///
+    /// [EventError::TooManyEvents] if the calls emitted more than [MaxArraySize] events, see
+    /// [`validate_events`] for the other errors.
+    pub fn into_receipt(
+        self,
+        tx_hash: Felt252Wrapper,
+        tx_type: TxType,
+    ) -> Result<TransactionReceiptWrapper, EventError> {
+        self.build_receipt(tx_hash, tx_type, false)
+    }
+
+    /// Builds the receipt of the executed transaction, dropping the events that don't fit in it.
+    ///
+    /// Same as [`Self::into_receipt`] except that the events which can't be built, see
+    /// [`EventBuilder::build`], are skipped, as are the events emitted after the first
+    /// [MaxArraySize] ones. The receipt is then flagged with
+    /// [`TransactionReceiptWrapper::events_truncated`].
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The execution info of the transaction.
+    /// * `tx_hash` - The hash of the transaction.
+    /// * `tx_type` - The type of the transaction.
+    pub fn into_truncated_receipt(self, tx_hash: Felt252Wrapper, tx_type: TxType) -> TransactionReceiptWrapper {
+        self.build_receipt(tx_hash, tx_type, true).expect("events that don't fit are dropped")
+    }
+
+    /// Returns the events emitted by the calls of the transaction, in the order of its receipt, see
+    /// [`Self::into_receipt`].
+    pub fn events(&self) -> vec::Vec<EventContent> {
+        self.ordered_events().into_iter().map(|(_, content)| content.clone()).collect()
+    }
+
+    /// Returns the events emitted by the calls of the transaction along with the address of their
+    /// emitter, in the order of its receipt.
+    fn ordered_events(&self) -> vec::Vec<(StarknetContractAddress, &EventContent)> {
+        let mut events = vec::Vec::new();
+        for call_info in
+            [&self.validate_call_info, &self.execute_call_info, &self.fee_transfer_call_info].into_iter().flatten()
+        {
+            collect_call_events(call_info, &mut events);
+        }
+        events
+    }
+
+    /// Builds the receipt, see [`Self::into_receipt`] and, if `truncate` is set,
+    /// [`Self::into_truncated_receipt`].
+    fn build_receipt(
+        self,
+        tx_hash: Felt252Wrapper,
+        tx_type: TxType,
+        truncate: bool,
+    ) -> Result<TransactionReceiptWrapper, EventError> {
+        let mut events = EventAccumulator::new();
+        let mut events_truncated = false;
+        for (from_address, content) in self.ordered_events() {
+            let pushed = EventWrapper::builder()
+                .with_event_content(content.clone())
+                .with_from_address(from_address)
+                .with_transaction_hash(TransactionHash(tx_hash.into()))
+                .build()
+                .and_then(|event| events.push(event).map_err(EventError::from));
+            match pushed {
+                Ok(()) => {}
+                Err(_) if truncate => events_truncated = true,
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(TransactionReceiptWrapper {
+            transaction_hash: tx_hash,
+            actual_fee: self.actual_fee.into(),
+            fee_unit: FeeUnit::Wei,
+            tx_type,
+            // Set when the block is finalized.
+            block_hash: None,
+            block_number: None,
+            block_timestamp: 0,
+            sequencer_address: ContractAddressWrapper::default(),
+            class_hash: None,
+            contract_address: None,
+            events: events.into_events(),
+            execution_status: if self.revert_error.is_some() {
+                ExecutionStatus::Reverted
+            } else {
+                ExecutionStatus::Succeeded
+            },
+            revert_reason: self.revert_error.map(|reason| BoundedVec::truncate_from(reason.into_bytes())),
+            events_truncated,
         })
     }
+
+    /// Builds the trace of the calls made by the transaction, see [CallTraceWrapper].
+    pub fn trace(&self) -> TransactionTraceWrapper {
+        TransactionTraceWrapper {
+            validate_invocation: self.validate_call_info.as_ref().map(CallTraceWrapper::from),
+            execute_invocation: self.execute_call_info.as_ref().map(CallTraceWrapper::from),
+            fee_transfer_invocation: self.fee_transfer_call_info.as_ref().map(CallTraceWrapper::from),
+        }
+    }
+}
+
+/// Returns the total time spent executing the transactions, in microseconds.
+///
+/// The transactions whose execution wasn't measured are counted as zero.
+///
+/// # Arguments
+///
+/// * `infos` - The execution infos of the transactions.
+pub fn total_execution_time(infos: &[TransactionExecutionInfoWrapper]) -> u64 {
+    infos.iter().filter_map(|info| info.execution_duration_micros).fold(0, u64::saturating_add)
+}
+
+/// Checks that events fit in a receipt, before building it.
+///
+/// The limits are the ones of [`EventBuilder::build`] and of the receipt events, so a caller can
+/// choose between failing and [`TransactionExecutionInfoWrapper::into_truncated_receipt`] right
+/// after the execution.
+///
+/// # Arguments
+///
+/// * `events` - The events emitted by the transaction, see
+///   [`TransactionExecutionInfoWrapper::events`].
+///
+/// # Errors
+///
+/// [`EventError::TooManyEvents`] if there are more than [MaxArraySize] events,
+/// [`EventError::InvalidKeys`] if an event has more keys than [MaxArraySize], and
+/// [`EventError::InvalidData`] if an event has more data than [MaxArraySize].
+pub fn validate_events(events: &[EventContent]) -> Result<(), EventError> {
+    let max_size = MaxArraySize::get() as usize;
+    if events.len() > max_size {
+        return Err(EventError::TooManyEvents);
+    }
+    for event in events {
+        if event.keys.len() > max_size {
+            return Err(EventError::InvalidKeys);
+        }
+        if event.data.0.len() > max_size {
+            return Err(EventError::InvalidData);
+        }
+    }
+
+    Ok(())
+}
+
+/// Pushes the events of a call and of its inner calls along with their emitter, see
+/// [`TransactionExecutionInfoWrapper::into_receipt`].
+fn collect_call_events<'a>(
+    call_info: &'a CallInfo,
+    events: &mut vec::Vec<(StarknetContractAddress, &'a EventContent)>,
+) {
+    let mut ordered_events: vec::Vec<_> = call_info.execution.events.iter().collect();
+    ordered_events.sort_by_key(|ordered_event| ordered_event.order);
+    for ordered_event in ordered_events {
+        events.push((call_info.call.storage_address, &ordered_event.event));
+    }
+
+    for inner_call in &call_info.inner_calls {
+        collect_call_events(inner_call, events);
+    }
 }
 
 /// Try to convert a `&Transaction` into a `DeployAccountTransaction`.
@@ -207,11 +536,9 @@ impl TryInto<DeployAccountTransaction> for &Transaction {
             transaction_hash: TransactionHash(StarkFelt::new(self.hash.into())?),
             max_fee: Fee(2),
             version: TransactionVersion(StarkFelt::new(U256::from(self.version).into())?),
-            signature: TransactionSignature(
-                self.signature.clone().into_inner().iter().map(|x| StarkFelt::new((*x).into()).unwrap()).collect(),
-            ),
-            nonce: Nonce(StarkFelt::new(self.nonce.into())?),
-            contract_address: StarknetContractAddress::try_from(StarkFelt::new(self.sender_address.into())?)?,
+            signature: self.signature_api(),
+            nonce: self.nonce_api(),
+            contract_address: self.sender_address_api()?,
             class_hash: entrypoint.class_hash.unwrap_or_default(),
             constructor_calldata: entrypoint.calldata,
             contract_address_salt: ContractAddressSalt(StarkFelt::new(
@@ -231,12 +558,10 @@ impl TryInto<L1HandlerTransaction> for &Transaction {
         Ok(L1HandlerTransaction {
             transaction_hash: TransactionHash(StarkFelt::new(self.hash.into())?),
             version: TransactionVersion(StarkFelt::new(U256::from(self.version).into())?),
-            nonce: Nonce(StarkFelt::new(self.nonce.into())?),
-            contract_address: StarknetContractAddress::try_from(StarkFelt::new(self.sender_address.into())?)?,
+            nonce: self.nonce_api(),
+            contract_address: self.sender_address_api()?,
             calldata: entrypoint.calldata,
-            entry_point_selector: EntryPointSelector(StarkHash::new(<[u8; 32]>::from(
-                self.call_entrypoint.entrypoint_selector.unwrap_or_default(),
-            ))?),
+            entry_point_selector: self.call_entrypoint.entrypoint_selector.unwrap_or_default().into(),
         })
     }
 }
@@ -251,11 +576,9 @@ impl TryInto<InvokeTransactionV1> for &Transaction {
         Ok(InvokeTransactionV1 {
             transaction_hash: TransactionHash(StarkFelt::new(self.hash.into())?),
             max_fee: Fee(2),
-            signature: TransactionSignature(
-                self.signature.clone().into_inner().iter().map(|x| StarkFelt::new((*x).into()).unwrap()).collect(),
-            ),
-            nonce: Nonce(StarkFelt::new(self.nonce.into())?),
-            sender_address: StarknetContractAddress::try_from(StarkFelt::new(self.sender_address.into())?)?,
+            signature: self.signature_api(),
+            nonce: self.nonce_api(),
+            sender_address: self.sender_address_api()?,
             calldata: entrypoint.calldata,
         })
     }
@@ -271,11 +594,9 @@ impl TryInto<DeclareTransaction> for &Transaction {
         let tx = DeclareTransactionV0V1 {
             transaction_hash: TransactionHash(StarkFelt::new(self.hash.into())?),
             max_fee: Fee(2),
-            signature: TransactionSignature(
-                self.signature.clone().into_inner().iter().map(|x| StarkFelt::new((*x).into()).unwrap()).collect(),
-            ),
-            nonce: Nonce(StarkFelt::new(self.nonce.into())?),
-            sender_address: StarknetContractAddress::try_from(StarkFelt::new(self.sender_address.into())?)?,
+            signature: self.signature_api(),
+            nonce: self.nonce_api(),
+            sender_address: self.sender_address_api()?,
             class_hash: entrypoint.class_hash.unwrap_or_default(),
         };
 
@@ -301,7 +622,7 @@ impl Transaction {
         nonce: Felt252Wrapper,
         call_entrypoint: CallEntryPointWrapper,
         contract_class: Option<ContractClassWrapper>,
-        contract_address_salt: Option<U256>,
+        contract_address_salt: Option<Felt252Wrapper>,
         max_fee: Felt252Wrapper,
     ) -> Self {
         Self {
@@ -315,6 +636,7 @@ impl Transaction {
             contract_class,
             contract_address_salt,
             max_fee,
+            paid_fee_on_l1: None,
         }
     }
 
@@ -323,20 +645,632 @@ impl Transaction {
         Self { hash, ..Self::default() }
     }
 
+    /// Creates a new instance of a transaction builder.
+    pub fn builder() -> TransactionBuilder {
+        TransactionBuilder::default()
+    }
+
+    /// Builds the L1 handler transaction consuming an L1 to L2 message.
+    ///
+    /// As the spec requires, the L1 sender address is prepended to the payload to build the
+    /// calldata of the handler. The hash is computed with [`calculate_l1_handler_tx_hash`].
+    ///
+    /// # Arguments
+    ///
+    /// * `from_address` - The L1 address that sent the message.
+    /// * `to_address` - The L2 contract handling the message.
+    /// * `selector` - The selector of the L1 handler.
+    /// * `payload` - The payload of the message.
+    /// * `nonce` - The nonce of the message on L1.
+    /// * `paid_fee_on_l1` - The fee paid on L1 for the message.
+    /// * `chain_id` - The chain id of the network.
+    ///
+    /// # Errors
+    ///
+    /// [`RPCTransactionConversionError::CalldataTooLong`] if the payload doesn't fit in the
+    /// calldata once the sender address is prepended.
+    pub fn from_l1_message(
+        from_address: Felt252Wrapper,
+        to_address: ContractAddressWrapper,
+        selector: EntryPointSelectorWrapper,
+        payload: &[Felt252Wrapper],
+        nonce: Felt252Wrapper,
+        paid_fee_on_l1: Felt252Wrapper,
+        chain_id: ChainIdWrapper,
+    ) -> Result<Transaction, RPCTransactionConversionError> {
+        Self::from_l1_message_with_limits::<DefaultTxLimits>(
+            from_address,
+            to_address,
+            selector,
+            payload,
+            nonce,
+            paid_fee_on_l1,
+            chain_id,
+        )
+    }
+
+    /// Builds the L1 handler transaction consuming an L1 to L2 message, see
+    /// [`Transaction::from_l1_message`], checking the calldata against the `MaxCalldataSize` of
+    /// `L`.
+    pub fn from_l1_message_with_limits<L: TxLimits>(
+        from_address: Felt252Wrapper,
+        to_address: ContractAddressWrapper,
+        selector: EntryPointSelectorWrapper,
+        payload: &[Felt252Wrapper],
+        nonce: Felt252Wrapper,
+        paid_fee_on_l1: Felt252Wrapper,
+        chain_id: ChainIdWrapper,
+    ) -> Result<Transaction, RPCTransactionConversionError> {
+        // The sender address is prepended to the payload.
+        if payload.len() >= L::MaxCalldataSize::get() as usize {
+            return Err(RPCTransactionConversionError::CalldataTooLong);
+        }
+        let calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize> =
+            BoundedVec::try_from([&[from_address][..], payload].concat())
+                .map_err(|_| RPCTransactionConversionError::CalldataTooLong)?;
+        let hash = calculate_l1_handler_tx_hash(to_address, selector.into(), &calldata, nonce, chain_id);
+
+        Ok(Transaction {
+            tx_type: TxType::L1Handler,
+            version: 0,
+            hash,
+            signature: BoundedVec::default(),
+            sender_address: to_address,
+            nonce,
+            call_entrypoint: CallEntryPointWrapper::new(
+                None,
+                EntryPointTypeWrapper::L1Handler,
+                Some(selector),
+                calldata,
+                to_address,
+                ContractAddressWrapper::default(),
+            ),
+            contract_class: None,
+            contract_address_salt: None,
+            max_fee: Felt252Wrapper::ZERO,
+            paid_fee_on_l1: Some(paid_fee_on_l1),
+        })
+    }
+
+    /// Checks that the hash of the transaction matches its content.
+    ///
+    /// The hash is recomputed with [`calculate_transaction_hash`].
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The transaction to check.
+    /// * `chain_id` - The chain id of the network.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `false` if the transaction was modified after its hash was computed.
+    pub fn verify_hash(&self, chain_id: ChainIdWrapper) -> bool {
+        calculate_transaction_hash(self, chain_id) == self.hash
+    }
+
+    /// Returns the ordered felts the hash of the transaction is computed on, so that it can be
+    /// reproduced outside of Madara, see [`calculate_transaction_hash_preimage`].
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The transaction to get the hash preimage of.
+    /// * `chain_id` - The chain id of the network.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Felt252Wrapper>` - The preimage, empty for L1 handler transactions.
+    pub fn hash_preimage(&self, chain_id: ChainIdWrapper) -> vec::Vec<Felt252Wrapper> {
+        calculate_transaction_hash_preimage(self, chain_id)
+    }
+
+    /// Returns a copy of the transaction with the next nonce, to build the next transaction of
+    /// the same account.
+    ///
+    /// The hash of the copy is reset to zero as it no longer matches its content, it has to be
+    /// computed again, see [`calculate_transaction_hash`].
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Transaction>` - `None` if the nonce is [`Felt252Wrapper::MAX`] and can't be
+    ///   incremented.
+    pub fn with_next_nonce(&self) -> Option<Transaction> {
+        Some(Transaction { nonce: self.nonce.checked_increment()?, hash: Felt252Wrapper::ZERO, ..self.clone() })
+    }
+
+    /// Returns the identity of the transaction, its hash along with its version.
+    ///
+    /// This is the key to deduplicate transactions with, see [`dedup_by_hash`]. Transactions of the
+    /// same account at the same nonce are distinct transactions, and so are transactions whose
+    /// content only differs by their version.
+    pub fn identity(&self) -> (Felt252Wrapper, u8) {
+        (self.hash, self.version)
+    }
+
+    /// Returns the address of the ERC-20 the fee of the transaction is paid in, STRK for v3
+    /// transactions and ETH otherwise, see [`FeeUnit::from_tx_version`].
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The transaction paying the fee.
+    /// * `config` - The fee token addresses of the chain.
+    pub fn fee_token_address(&self, config: &FeeTokenConfig) -> ContractAddressWrapper {
+        match FeeUnit::from_tx_version(self.version) {
+            FeeUnit::Wei => config.eth,
+            FeeUnit::Fri => config.strk,
+        }
+    }
+
+    /// Returns the number of felts of the transaction, to weigh it by its size.
+    ///
+    /// The version, hash, sender address, nonce and max fee are counted along with the optional
+    /// class hash, entry point selector and contract address salt when they are set, the calldata,
+    /// the signature and the felts of the declared contract class, see
+    /// [`ContractClassWrapper::felt_count`].
+    pub fn felt_count(&self) -> usize {
+        const FIXED_FIELDS: usize = 5;
+        let optional_fields = [
+            self.call_entrypoint.class_hash.is_some(),
+            self.call_entrypoint.entrypoint_selector.is_some(),
+            self.contract_address_salt.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_set| *is_set)
+        .count();
+
+        FIXED_FIELDS
+            + optional_fields
+            + self.call_entrypoint.calldata.len()
+            + self.signature.len()
+            + self.contract_class.as_ref().map_or(0, ContractClassWrapper::felt_count)
+    }
+
+    /// Returns the weight of the transaction, used to fill blocks under a weight budget.
+    ///
+    /// It is the [`TxType::base_weight`] of the transaction, plus [`constants::WEIGHT_PER_FELT`]
+    /// for each felt of [`Self::felt_count`] and [`constants::WEIGHT_PER_CLASS_FELT`] for each
+    /// felt of the declared contract class. The sum saturates at [`u64::MAX`].
+    pub fn total_weight(&self) -> u64 {
+        let felts = u64::try_from(self.felt_count()).unwrap_or(u64::MAX);
+        let class_felts =
+            u64::try_from(self.contract_class.as_ref().map_or(0, ContractClassWrapper::felt_count)).unwrap_or(u64::MAX);
+
+        self.tx_type
+            .base_weight()
+            .saturating_add(felts.saturating_mul(constants::WEIGHT_PER_FELT))
+            .saturating_add(class_felts.saturating_mul(constants::WEIGHT_PER_CLASS_FELT))
+    }
+
+    /// Writes the SCALE encoding of the transaction into `writer`, field by field.
+    ///
+    /// The bytes are the same as [`Encode::encode`] but are streamed into the sink instead of
+    /// being collected in an intermediate `Vec<u8>`, which avoids holding a second copy of a
+    /// declared contract class in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The sink the encoding is written to.
+    pub fn encode_to_writer<W: Output>(&self, writer: &mut W) {
+        self.tx_type.encode_to(writer);
+        self.version.encode_to(writer);
+        self.hash.encode_to(writer);
+        self.signature.encode_to(writer);
+        self.sender_address.encode_to(writer);
+        self.nonce.encode_to(writer);
+        self.call_entrypoint.encode_to(writer);
+        self.contract_class.encode_to(writer);
+        self.contract_address_salt.encode_to(writer);
+        self.max_fee.encode_to(writer);
+        self.paid_fee_on_l1.encode_to(writer);
+    }
+
+    /// Checks whether the signature of the transaction is equal to `other`.
+    ///
+    /// Unlike the derived `PartialEq`, this doesn't short-circuit on the first differing felt:
+    /// every felt of the transaction signature is compared, so the time taken only depends on
+    /// the signature length.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The transaction holding the expected signature.
+    /// * `other` - The signature to compare with.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if both signatures are equal.
+    pub fn signature_matches(&self, other: &[Felt252Wrapper]) -> bool {
+        let mut diff = (self.signature.len() != other.len()) as u8;
+        for (i, expected) in self.signature.iter().enumerate() {
+            let expected = expected.0.to_bytes_be();
+            let provided = other.get(i).map(|felt| felt.0.to_bytes_be()).unwrap_or_default();
+            diff |= expected.iter().zip(provided.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+        }
+        diff == 0
+    }
+
+    /// Returns an iterator over the felts of the transaction.
+    ///
+    /// The felts are yielded in the following order: hash, version, nonce, sender address, each
+    /// calldata felt and each signature felt.
+    pub fn felt_iter(&self) -> impl Iterator<Item = Felt252Wrapper> + '_ {
+        [self.hash, Felt252Wrapper::from(u64::from(self.version)), self.nonce, self.sender_address]
+            .into_iter()
+            .chain(self.call_entrypoint.calldata.iter().copied())
+            .chain(self.signature.iter().copied())
+    }
+
+    /// Returns a one line summary of the transaction for logging.
+    ///
+    /// The format is `type=INVOKE v1 hash=0x.. sender=0x.. nonce=N calldata_len=M` and is kept
+    /// stable so it can be grepped in the node logs.
+    pub fn to_log_string(&self) -> String {
+        let tx_type = match self.tx_type {
+            TxType::Invoke => "INVOKE",
+            TxType::Declare => "DECLARE",
+            TxType::DeployAccount => "DEPLOY_ACCOUNT",
+            TxType::L1Handler => "L1_HANDLER",
+            TxType::Deploy => "DEPLOY",
+        };
+        format!(
+            "type={} v{} hash={:#x} sender={:#x} nonce={} calldata_len={}",
+            tx_type,
+            self.version,
+            self.hash.0,
+            self.sender_address.0,
+            self.nonce.0,
+            self.call_entrypoint.calldata.len()
+        )
+    }
+
+    /// Returns a copy of the transaction without its signature, to be shipped to telemetry.
+    ///
+    /// The hash is kept so the redacted copy can still be matched with the original transaction.
+    /// Chain [`Transaction::with_truncated_calldata`] to bound the calldata as well.
+    pub fn redacted(&self) -> Transaction {
+        Transaction { signature: BoundedVec::default(), ..self.clone() }
+    }
+
+    /// Truncates the calldata to its first `max_len` felts, leaving the hash untouched.
+    pub fn with_truncated_calldata(mut self, max_len: usize) -> Self {
+        let calldata = self.call_entrypoint.calldata.into_inner();
+        self.call_entrypoint.calldata = BoundedVec::truncate_from(calldata.into_iter().take(max_len).collect());
+        self
+    }
+
+    /// Returns the [log string](Transaction::to_log_string) of the
+    /// [redacted](Transaction::redacted) transaction.
+    ///
+    /// The log string has no signature today, the two only differ if it ever includes one.
+    pub fn redacted_log_string(&self) -> String {
+        self.redacted().to_log_string()
+    }
+
+    /// Returns the fields that differ between two transactions, in declaration order.
+    ///
+    /// Meant to debug re-execution mismatches without printing both transactions: felts are
+    /// rendered in hex and arrays by their length and first differing element.
+    pub fn diff(&self, other: &Transaction) -> vec::Vec<FieldDiff> {
+        let mut diffs = vec::Vec::new();
+        let (left, right) = (&self.call_entrypoint, &other.call_entrypoint);
+
+        push_diff(&mut diffs, "tx_type", &self.tx_type, &other.tx_type, |tx_type| format!("{tx_type:?}"));
+        push_diff(&mut diffs, "version", &self.version, &other.version, ToString::to_string);
+        push_diff(&mut diffs, "hash", &self.hash, &other.hash, render_felt);
+        push_felts_diff(&mut diffs, "signature", &self.signature, &other.signature);
+        push_diff(&mut diffs, "sender_address", &self.sender_address, &other.sender_address, render_felt);
+        push_diff(&mut diffs, "nonce", &self.nonce, &other.nonce, render_felt);
+        push_diff(&mut diffs, "call_entrypoint.class_hash", &left.class_hash, &right.class_hash, render_option_felt);
+        push_diff(&mut diffs, "call_entrypoint.entrypoint_type", &left.entrypoint_type, &right.entrypoint_type, |t| {
+            format!("{t:?}")
+        });
+        push_diff(
+            &mut diffs,
+            "call_entrypoint.entrypoint_selector",
+            &left.entrypoint_selector.map(Felt252Wrapper::from),
+            &right.entrypoint_selector.map(Felt252Wrapper::from),
+            render_option_felt,
+        );
+        push_felts_diff(&mut diffs, "call_entrypoint.calldata", &left.calldata, &right.calldata);
+        push_diff(
+            &mut diffs,
+            "call_entrypoint.storage_address",
+            &left.storage_address,
+            &right.storage_address,
+            render_felt,
+        );
+        push_diff(
+            &mut diffs,
+            "call_entrypoint.caller_address",
+            &left.caller_address,
+            &right.caller_address,
+            render_felt,
+        );
+        push_diff(&mut diffs, "contract_class", &self.contract_class, &other.contract_class, |class| {
+            String::from(if class.is_some() { "Some(..)" } else { "None" })
+        });
+        push_diff(
+            &mut diffs,
+            "contract_address_salt",
+            &self.contract_address_salt,
+            &other.contract_address_salt,
+            render_option_felt,
+        );
+        push_diff(&mut diffs, "max_fee", &self.max_fee, &other.max_fee, render_felt);
+        push_diff(&mut diffs, "paid_fee_on_l1", &self.paid_fee_on_l1, &other.paid_fee_on_l1, render_option_felt);
+
+        diffs
+    }
+
+    /// Returns the type of the transaction if it's sent by an account, `None` for L1 handler and
+    /// legacy deploy transactions.
+    pub fn account_tx_type(&self) -> Option<AccountTxType> {
+        match self.tx_type {
+            TxType::Invoke => Some(AccountTxType::Invoke),
+            TxType::Declare => Some(AccountTxType::Declare),
+            TxType::DeployAccount => Some(AccountTxType::DeployAccount),
+            TxType::L1Handler | TxType::Deploy => None,
+        }
+    }
+
+    /// Returns the sender address as a [`starknet_api`] contract address.
+    ///
+    /// # Errors
+    ///
+    /// [`StarknetApiError::OutOfRange`] if the address is over the contract address bound.
+    pub fn sender_address_api(&self) -> Result<StarknetContractAddress, StarknetApiError> {
+        StarknetContractAddress::try_from(StarkFelt::from(self.sender_address))
+    }
+
+    /// Returns the nonce as a [`starknet_api`] nonce.
+    pub fn nonce_api(&self) -> Nonce {
+        Nonce(self.nonce.into())
+    }
+
+    /// Returns the signature as a [`starknet_api`] transaction signature.
+    pub fn signature_api(&self) -> TransactionSignature {
+        TransactionSignature(self.signature.iter().map(|&felt| felt.into()).collect())
+    }
+
+    /// Returns the calldata of the call entry point as [`starknet_api`] calldata.
+    pub fn calldata_api(&self) -> Calldata {
+        Calldata(Arc::new(self.call_entrypoint.calldata.iter().map(|&felt| felt.into()).collect()))
+    }
+
+    /// Returns whether the transaction carries a signature.
+    pub fn is_signed(&self) -> bool {
+        !self.signature.is_empty()
+    }
+
+    /// Checks that the transaction is signed.
+    ///
+    /// L1 handler transactions have no signature by design and always pass. Query transactions
+    /// (simulation, fee estimation) may legitimately be unsigned and shouldn't go through this
+    /// check.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::MissingSignature`] if the transaction has no signature.
+    pub fn require_signature(&self) -> TransactionValidationResultWrapper<()> {
+        match self.account_tx_type() {
+            None => Ok(()),
+            Some(_) if self.is_signed() => Ok(()),
+            Some(_) => Err(TransactionValidationErrorWrapper::MissingSignature),
+        }
+    }
+
+    /// Returns whether the sender of the transaction must already be deployed.
+    ///
+    /// The sender of a deploy account transaction is the account being deployed, so it doesn't
+    /// exist yet and the "sender is deployed" precheck must be skipped for it, as for the contract
+    /// deployed by a legacy deploy transaction. Every other transaction type requires a deployed
+    /// sender.
+    pub fn requires_deployed_sender(&self) -> bool {
+        !matches!(self.tx_type, TxType::DeployAccount | TxType::Deploy)
+    }
+
+    /// Checks that the transaction nonce is the current nonce of the sender account.
+    ///
+    /// L1 handler transactions carry the nonce of the L1 message, not of an account, and always
+    /// pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_nonce` - The current nonce of the sender account.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::InvalidNonce`] if the nonces differ.
+    pub fn check_nonce(&self, account_nonce: Felt252Wrapper) -> TransactionValidationResultWrapper<()> {
+        match self.account_tx_type() {
+            None => Ok(()),
+            Some(_) if self.nonce == account_nonce => Ok(()),
+            Some(_) => {
+                Err(TransactionValidationErrorWrapper::InvalidNonce { expected: account_nonce, got: self.nonce })
+            }
+        }
+    }
+
+    /// Checks that the transaction nonce isn't too far ahead of the current nonce of the sender
+    /// account.
+    ///
+    /// A transaction can only run once every nonce before its own has been used, one far ahead
+    /// of the account nonce would sit in the pool for a long time, if not forever. L1 handler
+    /// transactions carry the nonce of the L1 message, not of an account, and always pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_nonce` - The current nonce of the sender account.
+    /// * `max_gap` - The max difference allowed between the transaction and account nonces.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::InvalidNonce`] if the transaction nonce is below the
+    /// account nonce, and [`TransactionValidationErrorWrapper::NonceGapTooLarge`] if it's more than
+    /// `max_gap` above it.
+    pub fn check_nonce_gap(
+        &self,
+        current_nonce: Felt252Wrapper,
+        max_gap: u64,
+    ) -> TransactionValidationResultWrapper<()> {
+        if self.account_tx_type().is_none() {
+            return Ok(());
+        }
+        let gap = self
+            .nonce
+            .checked_sub(current_nonce)
+            .ok_or(TransactionValidationErrorWrapper::InvalidNonce { expected: current_nonce, got: self.nonce })?;
+        match u64::try_from(gap) {
+            Ok(gap) if gap <= max_gap => Ok(()),
+            _ => Err(TransactionValidationErrorWrapper::NonceGapTooLarge {
+                current: current_nonce,
+                got: self.nonce,
+                max_gap,
+            }),
+        }
+    }
+
+    /// Checks that the transaction has a non-zero max fee.
+    ///
+    /// An account transaction with a zero max fee can't pay for its execution and would only fail
+    /// once executed. L1 handler transactions are paid on L1 and always pass. Query transactions
+    /// (simulation, fee estimation) don't pay a fee and shouldn't go through this check.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::ZeroMaxFee`] if the max fee is zero.
+    pub fn check_max_fee(&self) -> TransactionValidationResultWrapper<()> {
+        match self.account_tx_type() {
+            None => Ok(()),
+            Some(_) if self.max_fee != Felt252Wrapper::ZERO => Ok(()),
+            Some(_) => Err(TransactionValidationErrorWrapper::ZeroMaxFee),
+        }
+    }
+
+    /// Returns whether the validation of the transaction must return
+    /// [`validation::VALIDATE_RETURN_MAGIC`].
+    ///
+    /// L1 handler transactions aren't validated, and transactions older than
+    /// [`validation::VALIDATE_RETURN_MIN_VERSION`] predate the requirement.
+    pub fn requires_validate_magic(&self) -> bool {
+        self.account_tx_type().is_some() && self.version >= validation::VALIDATE_RETURN_MIN_VERSION
+    }
+
+    /// Checks the data returned by the validate entry point of the account.
+    ///
+    /// Transactions for which [`Transaction::requires_validate_magic`] is false always pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `retdata` - The data returned by the validate entry point.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::InvalidValidateReturn`] if `retdata` doesn't start with
+    /// [`validation::VALIDATE_RETURN_MAGIC`], including when it is empty.
+    pub fn check_validate_result(&self, retdata: &[Felt252Wrapper]) -> TransactionValidationResultWrapper<()> {
+        if !self.requires_validate_magic() {
+            return Ok(());
+        }
+        match retdata.first() {
+            Some(magic) if *magic == validation::VALIDATE_RETURN_MAGIC => Ok(()),
+            _ => Err(TransactionValidationErrorWrapper::InvalidValidateReturn),
+        }
+    }
+
+    /// Checks that an L1 handler transaction carries the fee paid on L1 for its message.
+    ///
+    /// An L1 handler is only valid if its message was paid for on L1, one without the fee could
+    /// be forged. Account transactions always pass.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::MissingL1Fee`] if an L1 handler transaction has no
+    /// `paid_fee_on_l1`.
+    pub fn check_l1_fee(&self) -> TransactionValidationResultWrapper<()> {
+        match (&self.tx_type, self.paid_fee_on_l1) {
+            (TxType::L1Handler, None) => Err(TransactionValidationErrorWrapper::MissingL1Fee),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs the admission checks of the transaction against the state of its sender.
+    ///
+    /// The checks run in this order, stopping at the first failure:
+    /// * the hash must match the content of the transaction, see [`Transaction::verify_hash`];
+    /// * L1 handler transactions must carry the fee paid on L1, see [`Transaction::check_l1_fee`],
+    ///   and skip the remaining checks, which only concern account transactions;
+    /// * the max fee must be non-zero, see [`Transaction::check_max_fee`];
+    /// * the nonce must be at most `max_nonce_gap` ahead of the account nonce, see
+    ///   [`Transaction::check_nonce_gap`];
+    /// * the account balance must cover the max fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The state the transaction is admitted against.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::InvalidTransactionHash`] if the hash doesn't match,
+    /// [`TransactionValidationErrorWrapper::InsufficientBalance`] if the balance is below the max
+    /// fee, [`TransactionValidationErrorWrapper::LegacyDeploy`] for legacy deploy transactions, and
+    /// the errors of the other checks.
+    pub fn validate_against(&self, ctx: &ValidationContext) -> TransactionValidationResultWrapper<()> {
+        if !self.verify_hash(ctx.chain_id) {
+            return Err(TransactionValidationErrorWrapper::InvalidTransactionHash);
+        }
+        match self.tx_type {
+            TxType::L1Handler => return self.check_l1_fee(),
+            TxType::Deploy => return Err(TransactionValidationErrorWrapper::LegacyDeploy),
+            TxType::Invoke | TxType::Declare | TxType::DeployAccount => {}
+        }
+        self.check_max_fee()?;
+        self.check_nonce_gap(ctx.current_nonce, ctx.max_nonce_gap)?;
+        if self.max_fee > ctx.account_balance {
+            return Err(TransactionValidationErrorWrapper::InsufficientBalance {
+                balance: ctx.account_balance,
+                max_fee: self.max_fee,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns whether the transaction is charged a fee to its sender.
+    ///
+    /// Every account transaction is, L1 handler transactions are paid for on L1.
+    pub fn charges_fee(&self) -> bool {
+        self.account_tx_type().is_some()
+    }
+
+    /// Returns whether running the transaction with the given flags charges its fee.
+    ///
+    /// Query transactions (simulation, fee estimation), run with
+    /// [`SimulationFlags::skip_fee_charge`], only compute the fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - The flags the transaction is run with.
+    pub(crate) fn charges_fee_with(&self, flags: SimulationFlags) -> bool {
+        self.charges_fee() && !flags.skip_fee_charge
+    }
+
     /// Returns the validate entry point selector.
     pub fn validate_entry_point_selector(
         &self,
         tx_type: &TxType,
     ) -> TransactionValidationResultWrapper<EntryPointSelector> {
         match tx_type {
-            TxType::Declare => Ok(*constants::VALIDATE_DECLARE_ENTRY_POINT_SELECTOR),
-            TxType::DeployAccount => Ok(*constants::VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR),
-            TxType::Invoke => Ok(*constants::VALIDATE_ENTRY_POINT_SELECTOR),
+            TxType::Declare => Ok(EntryPointSelectorWrapper::VALIDATE_DECLARE.into()),
+            TxType::DeployAccount => Ok(EntryPointSelectorWrapper::VALIDATE_DEPLOY.into()),
+            TxType::Invoke => Ok(EntryPointSelectorWrapper::VALIDATE.into()),
             TxType::L1Handler => Err(EntryPointExecutionError::InvalidExecutionInput {
                 input_descriptor: "tx_type".to_string(),
                 info: "l1 handler transaction should not be validated".to_string(),
             })
             .map_err(TransactionValidationErrorWrapper::from),
+            TxType::Deploy => Err(EntryPointExecutionError::InvalidExecutionInput {
+                input_descriptor: "tx_type".to_string(),
+                info: "legacy deploy transaction should not be validated".to_string(),
+            })
+            .map_err(TransactionValidationErrorWrapper::from),
         }
     }
 
@@ -370,9 +1304,40 @@ impl Transaction {
                 info: "l1 handler transaction should not be validated".to_string(),
             })
             .map_err(TransactionValidationErrorWrapper::from),
+            TxType::Deploy => Err(EntryPointExecutionError::InvalidExecutionInput {
+                input_descriptor: "tx_type".to_string(),
+                info: "legacy deploy transaction should not be validated".to_string(),
+            })
+            .map_err(TransactionValidationErrorWrapper::from),
         }
     }
 
+    /// Returns the call entrypoint of the account validation of the transaction.
+    ///
+    /// The entrypoint calls `__validate__`, `__validate_declare__` or `__validate_deploy__` on the
+    /// sender account depending on the transaction type, with the calldata of
+    /// [`Transaction::validate_entrypoint_calldata`].
+    ///
+    /// # Returns
+    ///
+    /// [None] for L1 handler transactions, which aren't validated, or if the validation calldata
+    /// can't be built from the transaction.
+    pub fn validate_entrypoint(&self) -> Option<CallEntryPointWrapper> {
+        let selector = self.validate_entry_point_selector(&self.tx_type).ok()?;
+        let calldata = self.validate_entrypoint_calldata(&self.tx_type).ok()?;
+        let calldata = calldata.0.iter().map(|felt| Felt252Wrapper::from(*felt)).collect::<vec::Vec<_>>();
+        let calldata = BoundedVec::try_from(calldata).ok()?;
+
+        Some(CallEntryPointWrapper::new(
+            None,
+            EntryPointTypeWrapper::External,
+            Some(selector.into()),
+            calldata,
+            self.sender_address,
+            ContractAddressWrapper::default(),
+        ))
+    }
+
     /// Validates a transaction
     ///
     /// # Arguments
@@ -461,8 +1426,43 @@ impl Transaction {
     /// # Returns
     ///
     /// * `TransactionExecutionResult<TransactionExecutionInfo>` - The result of the transaction
-    ///   execution
-    pub fn execute<S: State + StateChanges>(
+    ///   execution. If the execution of an `Invoke` transaction fails, the transaction is
+    ///   considered reverted: the writes of the failed call are discarded, the fee is still charged
+    ///   and the error is returned in `revert_error`.
+    pub fn execute<S: State + StateChanges + StateRollback>(
+        &self,
+        state: &mut S,
+        block: StarknetBlock,
+        tx_type: TxType,
+        contract_class: Option<ContractClass>,
+        fee_token_address: ContractAddressWrapper,
+        chain_id: ChainId,
+    ) -> TransactionExecutionResultWrapper<TransactionExecutionInfoWrapper> {
+        self.simulate(state, block, tx_type, contract_class, fee_token_address, chain_id, SimulationFlags::default())
+    }
+
+    /// Simulates a transaction, like [`Transaction::execute`] but with some steps skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The transaction to simulate.
+    /// * `state` - The state to simulate the transaction on.
+    /// * `block` - The block to simulate the transaction on.
+    /// * `tx_type` - The type of the transaction to simulate.
+    /// * `contract_class` - The contract class to simulate the transaction on.
+    /// * `fee_token_address` - The fee token address.
+    /// * `chain_id` - The chain id.
+    /// * `flags` - The steps to skip. With `skip_validate` the validate entrypoint isn't called and
+    ///   `validate_call_info` is `None`. With `skip_fee_charge` the fee is still computed and
+    ///   returned in `actual_fee`, but it isn't transferred and `fee_transfer_call_info` is `None`.
+    ///
+    /// # Returns
+    ///
+    /// * `TransactionExecutionResult<TransactionExecutionInfo>` - The result of the transaction
+    ///   simulation. Legacy deploy transactions can't be run and fail with
+    ///   [`TransactionExecutionErrorWrapper::LegacyDeploy`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn simulate<S: State + StateChanges + StateRollback>(
         &self,
         state: &mut S,
         block: StarknetBlock,
@@ -470,36 +1470,65 @@ impl Transaction {
         contract_class: Option<ContractClass>,
         fee_token_address: ContractAddressWrapper,
         chain_id: ChainId,
+        flags: SimulationFlags,
     ) -> TransactionExecutionResultWrapper<TransactionExecutionInfoWrapper> {
+        #[cfg(feature = "std")]
+        let started_at = std::time::Instant::now();
         let block_context = block.header().clone().into_block_context(fee_token_address, chain_id);
 
         // Initialize the execution resources.
         let execution_resources = &mut ExecutionResources::default();
 
+        // Legacy deploy transactions are only decoded from historic blocks.
+        if tx_type == TxType::Deploy {
+            return Err(TransactionExecutionErrorWrapper::LegacyDeploy);
+        }
+
         // Verify the transaction version.
         self.verify_tx_version(&tx_type)?;
 
         // Going one lower level gives us more flexibility like not validating the tx as we could do
         // it before the tx lands in the mempool.
         // However it also means we need to copy/paste internal code from the tx.execute() method.
+        let mut revert_error = None;
         let (execute_call_info, validate_call_info, account_context) = match tx_type {
             TxType::Invoke => {
+                // v0 invoke transactions call their entry point selector on the contract directly, they
+                // are rejected by the version check above and must never run as the `__execute__` call
+                // of a v1 transaction.
+                if self.call_entrypoint.entrypoint_selector.is_some() {
+                    return Err(TransactionExecutionErrorWrapper::EntrypointExecution(
+                        EntryPointExecutionError::InvalidExecutionInput {
+                            input_descriptor: "entry_point_selector".to_string(),
+                            info: "only v0 invoke transactions have an entry point selector".to_string(),
+                        },
+                    ));
+                }
                 let tx: InvokeTransactionV1 = self.try_into().map_err(TransactionExecutionErrorWrapper::StarknetApi)?;
                 let account_context = self.get_invoke_transaction_context(&tx);
                 // Update nonce
                 self.handle_nonce(state, &account_context)?;
 
                 // Validate.
-                let validate_call_info =
-                    self.validate_tx(state, execution_resources, &block_context, &account_context, &tx_type)?;
+                let validate_call_info = if flags.skip_validate {
+                    None
+                } else {
+                    self.validate_tx(state, execution_resources, &block_context, &account_context, &tx_type)?
+                };
 
                 // Execute.
-                (
+                // A failing execution doesn't invalidate the transaction: it is reverted, its writes are
+                // discarded, the reason is kept and the fee is still charged.
+                let execute_call_info = match state.with_rollback(|state| {
                     tx.run_execute(state, execution_resources, &block_context, &account_context, contract_class)
-                        .map_err(TransactionExecutionErrorWrapper::TransactionExecution)?,
-                    validate_call_info,
-                    account_context,
-                )
+                }) {
+                    Ok(execute_call_info) => execute_call_info,
+                    Err(e) => {
+                        revert_error = Some(e.to_string());
+                        None
+                    }
+                };
+                (execute_call_info, validate_call_info, account_context)
             }
             TxType::L1Handler => {
                 let tx = self.try_into().map_err(TransactionExecutionErrorWrapper::StarknetApi)?;
@@ -519,8 +1548,11 @@ impl Transaction {
                 self.handle_nonce(state, &account_context)?;
 
                 // Validate.
-                let validate_call_info =
-                    self.validate_tx(state, execution_resources, &block_context, &account_context, &tx_type)?;
+                let validate_call_info = if flags.skip_validate {
+                    None
+                } else {
+                    self.validate_tx(state, execution_resources, &block_context, &account_context, &tx_type)?
+                };
 
                 // Execute.
                 (
@@ -541,27 +1573,52 @@ impl Transaction {
                     .run_execute(state, execution_resources, &block_context, &account_context, contract_class)
                     .map_err(TransactionExecutionErrorWrapper::TransactionExecution)?;
 
-                (
-                    transaction_execution,
-                    self.validate_tx(state, execution_resources, &block_context, &account_context, &tx_type)?,
-                    account_context,
-                )
+                // Validate.
+                let validate_call_info = if flags.skip_validate {
+                    None
+                } else {
+                    self.validate_tx(state, execution_resources, &block_context, &account_context, &tx_type)?
+                };
+
+                (transaction_execution, validate_call_info, account_context)
             }
+            TxType::Deploy => return Err(TransactionExecutionErrorWrapper::LegacyDeploy),
         };
-        let tx_resources = fees::get_transaction_resources(
-            state,
-            &execute_call_info,
-            &validate_call_info,
-            execution_resources,
-            tx_type,
-        )?;
-        let (actual_fee, fee_transfer_call_info) = charge_fee(state, &block_context, &account_context, &tx_resources)?;
+        // L1 handler transactions are paid for on L1 when the message is sent, so the fee computation
+        // is skipped entirely for them.
+        let (tx_resources, actual_fee, fee_transfer_call_info) = if tx_type == TxType::L1Handler {
+            (BTreeMap::new(), Fee::default(), None)
+        } else {
+            let tx_resources = fees::get_transaction_resources(
+                state,
+                &execute_call_info,
+                &validate_call_info,
+                execution_resources,
+                tx_type,
+            )?;
+            let (actual_fee, fee_transfer_call_info) = if self.charges_fee_with(flags) {
+                charge_fee(state, &block_context, &account_context, &tx_resources)?
+            } else {
+                (fees::calculate_tx_fee(&tx_resources, &block_context)?, None)
+            };
+            (tx_resources, actual_fee, fee_transfer_call_info)
+        };
+        // The state diff is informative, a transaction making more changes than it can hold has
+        // already been executed and must not fail because of it.
+        let state_diff = state.state_diff().ok();
+        #[cfg(feature = "std")]
+        let execution_duration_micros = Some(u64::try_from(started_at.elapsed().as_micros()).unwrap_or(u64::MAX));
+        #[cfg(not(feature = "std"))]
+        let execution_duration_micros = None;
         Ok(TransactionExecutionInfoWrapper {
             validate_call_info,
             execute_call_info,
             fee_transfer_call_info,
             actual_fee,
             actual_resources: tx_resources,
+            revert_error,
+            state_diff,
+            execution_duration_micros,
         })
     }
 
@@ -695,6 +1752,267 @@ impl Transaction {
     }
 }
 
+/// Builder pattern for [`Transaction`].
+///
+/// Builds an invoke transaction unless [`TransactionBuilder::declare`] or
+/// [`TransactionBuilder::deploy_account`] is called. The hash is computed by
+/// [`TransactionBuilder::build`].
+pub struct TransactionBuilder {
+    tx_type: TxType,
+    version: u8,
+    sender_address: ContractAddressWrapper,
+    calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>,
+    nonce: Felt252Wrapper,
+    signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
+    max_fee: Felt252Wrapper,
+    class_hash: Option<ClassHashWrapper>,
+    contract_class: Option<ContractClassWrapper>,
+    contract_address_salt: Option<Felt252Wrapper>,
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        Self {
+            tx_type: TxType::Invoke,
+            version: 1_u8,
+            sender_address: ContractAddressWrapper::default(),
+            calldata: BoundedVec::default(),
+            nonce: Felt252Wrapper::default(),
+            signature: BoundedVec::default(),
+            max_fee: Felt252Wrapper::default(),
+            class_hash: None,
+            contract_class: None,
+            contract_address_salt: None,
+        }
+    }
+}
+
+impl TransactionBuilder {
+    /// Builds an invoke transaction.
+    pub fn invoke(mut self) -> Self {
+        self.tx_type = TxType::Invoke;
+        self
+    }
+
+    /// Builds a declare transaction, which requires a class and a class hash.
+    pub fn declare(mut self) -> Self {
+        self.tx_type = TxType::Declare;
+        self
+    }
+
+    /// Builds a deploy account transaction, which requires a class hash.
+    pub fn deploy_account(mut self) -> Self {
+        self.tx_type = TxType::DeployAccount;
+        self
+    }
+
+    /// Sets the version of the transaction, 1 by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Transaction version.
+    pub fn version(mut self, version: u8) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the sender of the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `sender_address` - Address of the account sending the transaction, or of the account
+    ///   deployed by a deploy account transaction.
+    pub fn sender(mut self, sender_address: ContractAddressWrapper) -> Self {
+        self.sender_address = sender_address;
+        self
+    }
+
+    /// Sets the calldata of the transaction, ignored by declare transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `calldata` - Calldata of the `__execute__` call, or of the constructor of the deployed
+    ///   account.
+    pub fn calldata(mut self, calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>) -> Self {
+        self.calldata = calldata;
+        self
+    }
+
+    /// Sets the nonce of the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `nonce` - Account nonce.
+    pub fn nonce(mut self, nonce: Felt252Wrapper) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sets the signature of the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Transaction signature.
+    pub fn signature(mut self, signature: BoundedVec<Felt252Wrapper, MaxArraySize>) -> Self {
+        self.signature = signature;
+        self
+    }
+
+    /// Sets the max fee of the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_fee` - Max fee.
+    pub fn max_fee(mut self, max_fee: Felt252Wrapper) -> Self {
+        self.max_fee = max_fee;
+        self
+    }
+
+    /// Sets the class hash, the compiled class hash of a declare transaction or the class hash of
+    /// the account deployed by a deploy account transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `class_hash` - Class hash.
+    pub fn class_hash(mut self, class_hash: ClassHashWrapper) -> Self {
+        self.class_hash = Some(class_hash);
+        self
+    }
+
+    /// Sets the class declared by a declare transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_class` - Declared class.
+    pub fn contract_class(mut self, contract_class: ContractClassWrapper) -> Self {
+        self.contract_class = Some(contract_class);
+        self
+    }
+
+    /// Sets the salt of the address of the account deployed by a deploy account transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_address_salt` - Contract address salt.
+    pub fn contract_address_salt(mut self, contract_address_salt: Felt252Wrapper) -> Self {
+        self.contract_address_salt = Some(contract_address_salt);
+        self
+    }
+
+    /// Builds the transaction and computes its hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id` - Chain id used to compute the hash.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionConversionError::MissingClass`] if a declare transaction has no class,
+    /// [`TransactionConversionError::MissingClassHash`] if a declare or deploy account
+    /// transaction has no class hash, and [`TransactionConversionError::InvalidEntryPointSelector`]
+    /// for a v0 invoke transaction, which the builder can't set the entry point selector of.
+    pub fn build(self, chain_id: ChainIdWrapper) -> Result<Transaction, TransactionConversionError> {
+        let transaction = match self.tx_type {
+            TxType::Invoke => types::InvokeTransaction {
+                version: self.version,
+                sender_address: self.sender_address,
+                calldata: self.calldata,
+                nonce: self.nonce,
+                signature: self.signature,
+                max_fee: self.max_fee,
+                entry_point_selector: None,
+            }
+            .from_invoke(chain_id)
+            .map_err(|_| TransactionConversionError::InvalidEntryPointSelector)?,
+            TxType::Declare => types::DeclareTransaction {
+                version: self.version,
+                sender_address: self.sender_address,
+                nonce: self.nonce,
+                signature: self.signature,
+                max_fee: self.max_fee,
+                compiled_class_hash: self.class_hash.ok_or(TransactionConversionError::MissingClassHash)?,
+                contract_class: self.contract_class.ok_or(TransactionConversionError::MissingClass)?,
+            }
+            .from_declare(chain_id),
+            TxType::DeployAccount => types::DeployAccountTransaction {
+                version: self.version,
+                sender_address: self.sender_address,
+                calldata: self.calldata,
+                nonce: self.nonce,
+                salt: self.contract_address_salt.map(U256::from).unwrap_or_default(),
+                signature: self.signature,
+                account_class_hash: self.class_hash.ok_or(TransactionConversionError::MissingClassHash)?,
+                max_fee: self.max_fee,
+            }
+            .from_deploy(chain_id)
+            .expect("the salt set on the builder is a felt"),
+            TxType::L1Handler | TxType::Deploy => {
+                unreachable!("the builder has no setter for L1 handler nor legacy deploy transactions")
+            }
+        };
+        Ok(transaction)
+    }
+}
+
+/// Removes the transactions whose [`Transaction::identity`] was already seen earlier in the batch.
+///
+/// The first occurrence of each identity is kept and the order of the kept transactions is
+/// preserved.
+///
+/// # Arguments
+///
+/// * `txs` - The transactions to deduplicate.
+///
+/// # Returns
+///
+/// The deduplicated transactions and the number of transactions removed.
+#[cfg(feature = "std")]
+pub fn dedup_by_hash(txs: vec::Vec<Transaction>) -> (vec::Vec<Transaction>, usize) {
+    let total = txs.len();
+    let mut seen = std::collections::HashSet::with_capacity(total);
+    let unique: vec::Vec<Transaction> = txs
+        .into_iter()
+        .filter(|tx| {
+            let (hash, version) = tx.identity();
+            seen.insert((<[u8; 32]>::from(hash), version))
+        })
+        .collect();
+    let removed = total - unique.len();
+    (unique, removed)
+}
+
+/// Checks that the transactions of a sender use every nonce from `current` onwards, in order.
+///
+/// The mempool orders the transactions of an account by nonce, a batch with a gap or a repeated
+/// nonce can't be executed as a whole.
+///
+/// # Arguments
+///
+/// * `current` - The current nonce of the sender account.
+/// * `txs` - The transactions of the sender, in execution order.
+///
+/// # Errors
+///
+/// [`TransactionValidationErrorWrapper::InvalidNonce`] for the first transaction whose nonce isn't
+/// `current` plus its index in the batch.
+pub fn validate_nonce_sequence(
+    current: Felt252Wrapper,
+    txs: &[&Transaction],
+) -> TransactionValidationResultWrapper<()> {
+    let mut expected = current;
+    for (index, tx) in txs.iter().enumerate() {
+        if index > 0 {
+            expected = expected
+                .checked_increment()
+                .ok_or(TransactionValidationErrorWrapper::InvalidNonce { expected, got: tx.nonce })?;
+        }
+        if tx.nonce != expected {
+            return Err(TransactionValidationErrorWrapper::InvalidNonce { expected, got: tx.nonce });
+        }
+    }
+    Ok(())
+}
+
 impl Default for Transaction {
     fn default() -> Self {
         let one = Felt252Wrapper::ONE;
@@ -709,6 +2027,7 @@ impl Default for Transaction {
             contract_class: None,
             contract_address_salt: None,
             max_fee: Felt252Wrapper::from(u128::MAX),
+            paid_fee_on_l1: None,
         }
     }
 }
@@ -718,10 +2037,60 @@ impl Default for TransactionReceiptWrapper {
         Self {
             transaction_hash: Felt252Wrapper::default(),
             actual_fee: Felt252Wrapper::default(),
+            fee_unit: FeeUnit::Wei,
             tx_type: TxType::Invoke,
-            block_hash: Felt252Wrapper::default(),
-            block_number: 0_u64,
+            block_hash: None,
+            block_number: None,
+            block_timestamp: 0_u64,
+            sequencer_address: ContractAddressWrapper::default(),
+            class_hash: None,
+            contract_address: None,
             events: BoundedVec::try_from(vec![EventWrapper::default(), EventWrapper::default()]).unwrap(),
+            execution_status: ExecutionStatus::Succeeded,
+            revert_reason: None,
+            events_truncated: false,
         }
     }
 }
+
+/// Pushes a [`FieldDiff`] for `field` if `left` and `right` differ.
+fn push_diff<T: PartialEq>(
+    diffs: &mut vec::Vec<FieldDiff>,
+    field: &'static str,
+    left: &T,
+    right: &T,
+    render: impl Fn(&T) -> String,
+) {
+    if left != right {
+        diffs.push(FieldDiff { field, left: render(left), right: render(right) });
+    }
+}
+
+/// Pushes a [`FieldDiff`] for `field` if the felt arrays differ, each side being rendered by its
+/// length and its element at the first index where the arrays differ, if any.
+fn push_felts_diff(
+    diffs: &mut vec::Vec<FieldDiff>,
+    field: &'static str,
+    left: &[Felt252Wrapper],
+    right: &[Felt252Wrapper],
+) {
+    if left == right {
+        return;
+    }
+    let index = left.iter().zip(right).position(|(l, r)| l != r).unwrap_or(left.len().min(right.len()));
+    let render = |felts: &[Felt252Wrapper]| match felts.get(index) {
+        Some(felt) => format!("len={} [{}]={}", felts.len(), index, render_felt(felt)),
+        None => format!("len={}", felts.len()),
+    };
+    diffs.push(FieldDiff { field, left: render(left), right: render(right) });
+}
+
+/// Renders a felt in hex.
+fn render_felt(felt: &Felt252Wrapper) -> String {
+    format!("{:#x}", felt.0)
+}
+
+/// Renders an optional felt in hex, or `None`.
+fn render_option_felt(felt: &Option<Felt252Wrapper>) -> String {
+    felt.as_ref().map_or_else(|| String::from("None"), render_felt)
+}
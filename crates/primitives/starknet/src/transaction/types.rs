@@ -1,41 +1,65 @@
 use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Read;
 
 use blockifier::execution::entry_point::CallInfo;
 use blockifier::execution::errors::EntryPointExecutionError;
 use blockifier::state::errors::StateError;
 use blockifier::transaction::errors::TransactionExecutionError;
 use blockifier::transaction::transaction_types::TransactionType;
+#[cfg(feature = "std")]
+use cairo_vm::types::program::Program;
+#[cfg(feature = "std")]
+use flate2::read::GzDecoder;
+use frame_support::traits::Get;
+#[cfg(feature = "std")]
+use frame_support::BoundedBTreeMap;
 use frame_support::BoundedVec;
-use sp_core::{ConstU32, U256};
+use sp_core::U256;
 use starknet_api::transaction::Fee;
 use starknet_api::StarknetApiError;
 #[cfg(feature = "std")]
 use starknet_core::types::{
-    DeclareTransaction as RPCDeclareTransaction, DeclareTransactionReceipt as RPCDeclareTransactionReceipt,
-    DeclareTransactionV1 as RPCDeclareTransactionV1, DeclareTransactionV2 as RPCDeclareTransactionV2,
-    DeployAccountTransaction as RPCDeployAccountTransaction,
-    DeployAccountTransactionReceipt as RPCDeployAccountTransactionReceipt, Event as RPCEvent, FieldElement,
+    BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV1, BroadcastedDeployAccountTransaction,
+    BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV0, BroadcastedInvokeTransactionV1,
+    BroadcastedTransaction, CompressedLegacyContractClass, DeclareTransaction as RPCDeclareTransaction,
+    DeclareTransactionReceipt as RPCDeclareTransactionReceipt, DeclareTransactionV1 as RPCDeclareTransactionV1,
+    DeclareTransactionV2 as RPCDeclareTransactionV2, DeployAccountTransaction as RPCDeployAccountTransaction,
+    DeployAccountTransactionReceipt as RPCDeployAccountTransactionReceipt, DeployTransaction as RPCDeployTransaction,
+    DeployTransactionReceipt as RPCDeployTransactionReceipt, EmittedEvent, Event as RPCEvent, FieldElement,
     InvokeTransaction as RPCInvokeTransaction, InvokeTransactionReceipt as RPCInvokeTransactionReceipt,
     InvokeTransactionV0 as RPCInvokeTransactionV0, InvokeTransactionV1 as RPCInvokeTransactionV1,
     L1HandlerTransaction as RPCL1HandlerTransaction, L1HandlerTransactionReceipt as RPCL1HandlerTransactionReceipt,
-    MaybePendingTransactionReceipt as RPCMaybePendingTransactionReceipt, Transaction as RPCTransaction,
+    MaybePendingTransactionReceipt as RPCMaybePendingTransactionReceipt,
+    PendingDeclareTransactionReceipt as RPCPendingDeclareTransactionReceipt,
+    PendingDeployAccountTransactionReceipt as RPCPendingDeployAccountTransactionReceipt,
+    PendingDeployTransactionReceipt as RPCPendingDeployTransactionReceipt,
+    PendingInvokeTransactionReceipt as RPCPendingInvokeTransactionReceipt,
+    PendingL1HandlerTransactionReceipt as RPCPendingL1HandlerTransactionReceipt,
+    PendingTransactionReceipt as RPCPendingTransactionReceipt, Transaction as RPCTransaction,
     TransactionReceipt as RPCTransactionReceipt, TransactionStatus as RPCTransactionStatus,
 };
 use thiserror_no_std::Error;
 
-use crate::crypto::commitment::{
-    calculate_declare_tx_hash, calculate_deploy_account_tx_hash, calculate_invoke_tx_hash,
-};
+use crate::crypto::commitment::{calculate_contract_address, calculate_event_commitment, StarknetV0};
+use crate::crypto::hash::pedersen::PedersenHasher;
 use crate::execution::call_entrypoint_wrapper::MaxCalldataSize;
 use crate::execution::entrypoint_wrapper::EntryPointTypeWrapper;
+#[cfg(feature = "std")]
+use crate::execution::entrypoint_wrapper::EntryPointWrapper;
 use crate::execution::types::{
-    CallEntryPointWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper, Felt252WrapperError,
+    CallEntryPointWrapper, ChainId, ClassHashWrapper, ContractAddressWrapper, ContractClassWrapper, EntryPointSelector,
+    Felt252Wrapper, Felt252WrapperError, StorageKeyWrapper,
 };
+use crate::traits::hash::TransactionHasher;
+use crate::traits::limits::{DefaultTxLimits, TxLimits};
+use crate::transaction::bloom::Bloom;
+use crate::transaction::constants;
 
 /// Max size of arrays.
-/// TODO: add real value (#250)
-pub type MaxArraySize = ConstU32<10000>;
+pub type MaxArraySize = <DefaultTxLimits as TxLimits>::MaxArraySize;
 
 /// Wrapper type for transaction execution result.
 pub type TransactionExecutionResultWrapper<T> = Result<T, TransactionExecutionErrorWrapper>;
@@ -70,6 +94,8 @@ pub enum TransactionExecutionErrorWrapper {
     #[error("Cairo resources are not contained in the fee costs")]
     CairoResourcesNotContainedInFeeCosts,
     /// Failed to compute the L1 gas usage.
+    ///
+    /// Never returned for L1 handler transactions, their fee isn't computed.
     #[error("Failed to compute the L1 gas usage")]
     FailedToComputeL1GasUsage,
     /// Entrypoint execution error
@@ -78,6 +104,77 @@ pub enum TransactionExecutionErrorWrapper {
     /// Unexpected holes.
     #[error("Unexpected holes: {0}")]
     UnexpectedHoles(String),
+    /// The transaction has no signature.
+    #[error("Missing transaction signature")]
+    MissingSignature,
+    /// The transaction nonce doesn't match the account nonce.
+    #[error("Invalid transaction nonce. Expected {}, got {}", expected.0, got.0)]
+    InvalidNonce {
+        /// Nonce of the account.
+        expected: Felt252Wrapper,
+        /// Nonce of the transaction.
+        got: Felt252Wrapper,
+    },
+    /// The transaction has a zero max fee and can't pay for its execution.
+    #[error("Max fee is zero")]
+    ZeroMaxFee,
+    /// The account validation didn't return the `VALID` magic value.
+    #[error("Validation didn't return VALID")]
+    InvalidValidateReturn,
+    /// The L1 handler transaction doesn't carry the fee paid on L1.
+    #[error("Missing fee paid on L1")]
+    MissingL1Fee,
+    /// The transaction nonce is too far ahead of the account nonce.
+    #[error("Nonce gap too large. Account nonce {}, got {}, max gap {max_gap}", current.0, got.0)]
+    NonceGapTooLarge {
+        /// Nonce of the account.
+        current: Felt252Wrapper,
+        /// Nonce of the transaction.
+        got: Felt252Wrapper,
+        /// Max allowed difference between the two.
+        max_gap: u64,
+    },
+    /// The declare transaction carries an empty contract class.
+    #[error("Empty contract class")]
+    EmptyContractClass,
+    /// The transaction hash doesn't match its content.
+    #[error("Invalid transaction hash")]
+    InvalidTransactionHash,
+    /// The account balance can't cover the max fee of the transaction.
+    #[error("Insufficient balance. Balance is {}, max fee is {}", balance.0, max_fee.0)]
+    InsufficientBalance {
+        /// Balance of the account.
+        balance: Felt252Wrapper,
+        /// Max fee of the transaction.
+        max_fee: Felt252Wrapper,
+    },
+    /// Legacy deploy transactions are only found in historic blocks and can't be executed.
+    #[error("Legacy deploy transactions can't be executed")]
+    LegacyDeploy,
+}
+
+impl TransactionExecutionErrorWrapper {
+    /// Creates a [`TransactionExecutionErrorWrapper::FeeTransferError`].
+    ///
+    /// The error is only meant for an actual fee exceeding the max fee, this is checked in debug
+    /// builds.
+    pub fn fee_transfer_error(max_fee: Fee, actual_fee: Fee) -> Self {
+        debug_assert!(actual_fee > max_fee, "actual fee {} doesn't exceed max fee {}", actual_fee.0, max_fee.0);
+        Self::FeeTransferError { max_fee, actual_fee }
+    }
+
+    /// Returns by how much the actual fee exceeds the max fee for a
+    /// [`TransactionExecutionErrorWrapper::FeeTransferError`].
+    ///
+    /// Returns [None] for other errors or if the actual fee doesn't exceed the max fee.
+    pub fn overage(&self) -> Option<Fee> {
+        match self {
+            Self::FeeTransferError { max_fee, actual_fee } => {
+                actual_fee.0.checked_sub(max_fee.0).filter(|overage| *overage > 0).map(Fee)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<TransactionValidationErrorWrapper> for TransactionExecutionErrorWrapper {
@@ -85,6 +182,20 @@ impl From<TransactionValidationErrorWrapper> for TransactionExecutionErrorWrappe
         match error {
             TransactionValidationErrorWrapper::TransactionValidationError(e) => Self::TransactionExecution(e),
             TransactionValidationErrorWrapper::CalldataError(e) => Self::StarknetApi(e),
+            TransactionValidationErrorWrapper::MissingSignature => Self::MissingSignature,
+            TransactionValidationErrorWrapper::InvalidNonce { expected, got } => Self::InvalidNonce { expected, got },
+            TransactionValidationErrorWrapper::ZeroMaxFee => Self::ZeroMaxFee,
+            TransactionValidationErrorWrapper::InvalidValidateReturn => Self::InvalidValidateReturn,
+            TransactionValidationErrorWrapper::MissingL1Fee => Self::MissingL1Fee,
+            TransactionValidationErrorWrapper::NonceGapTooLarge { current, got, max_gap } => {
+                Self::NonceGapTooLarge { current, got, max_gap }
+            }
+            TransactionValidationErrorWrapper::EmptyContractClass => Self::EmptyContractClass,
+            TransactionValidationErrorWrapper::InvalidTransactionHash => Self::InvalidTransactionHash,
+            TransactionValidationErrorWrapper::InsufficientBalance { balance, max_fee } => {
+                Self::InsufficientBalance { balance, max_fee }
+            }
+            TransactionValidationErrorWrapper::LegacyDeploy => Self::LegacyDeploy,
         }
     }
 }
@@ -101,6 +212,53 @@ pub enum TransactionValidationErrorWrapper {
     /// Calldata error
     #[error(transparent)]
     CalldataError(#[from] StarknetApiError),
+    /// The transaction has no signature
+    #[error("Missing transaction signature")]
+    MissingSignature,
+    /// The transaction nonce doesn't match the account nonce
+    #[error("Invalid transaction nonce. Expected {}, got {}", expected.0, got.0)]
+    InvalidNonce {
+        /// Nonce of the account.
+        expected: Felt252Wrapper,
+        /// Nonce of the transaction.
+        got: Felt252Wrapper,
+    },
+    /// The transaction has a zero max fee and can't pay for its execution.
+    #[error("Max fee is zero")]
+    ZeroMaxFee,
+    /// The account validation didn't return the `VALID` magic value.
+    #[error("Validation didn't return VALID")]
+    InvalidValidateReturn,
+    /// The L1 handler transaction doesn't carry the fee paid on L1.
+    #[error("Missing fee paid on L1")]
+    MissingL1Fee,
+    /// The transaction nonce is too far ahead of the account nonce.
+    #[error("Nonce gap too large. Account nonce {}, got {}, max gap {max_gap}", current.0, got.0)]
+    NonceGapTooLarge {
+        /// Nonce of the account.
+        current: Felt252Wrapper,
+        /// Nonce of the transaction.
+        got: Felt252Wrapper,
+        /// Max allowed difference between the two.
+        max_gap: u64,
+    },
+    /// The declare transaction carries an empty contract class.
+    #[error("Empty contract class")]
+    EmptyContractClass,
+    /// The transaction hash doesn't match its content.
+    #[error("Invalid transaction hash")]
+    InvalidTransactionHash,
+    /// The account balance can't cover the max fee of the transaction.
+    #[error("Insufficient balance. Balance is {}, max fee is {}", balance.0, max_fee.0)]
+    InsufficientBalance {
+        /// Balance of the account.
+        balance: Felt252Wrapper,
+        /// Max fee of the transaction.
+        max_fee: Felt252Wrapper,
+    },
+    /// Legacy deploy transactions are only found in historic blocks and can't be admitted.
+    #[error("Legacy deploy transactions can't be admitted")]
+    LegacyDeploy,
 }
 
 impl From<EntryPointExecutionError> for TransactionValidationErrorWrapper {
@@ -122,6 +280,8 @@ impl From<EntryPointExecutionError> for TransactionValidationErrorWrapper {
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TxType {
     /// Regular invoke transaction.
     Invoke,
@@ -131,7 +291,33 @@ pub enum TxType {
     DeployAccount,
     /// Message sent from ethereum.
     L1Handler,
+    /// Legacy deploy transaction, deprecated in favor of [TxType::DeployAccount].
+    ///
+    /// It's only found in historic blocks: it can't be submitted nor executed.
+    Deploy,
+}
+
+impl TxType {
+    /// Returns every transaction type that can be executed, i.e. all of them but the legacy
+    /// [TxType::Deploy]. [TxType::L1Handler] is included although it's only ever sent from L1.
+    pub fn all() -> [TxType; 4] {
+        [Self::Invoke, Self::Declare, Self::DeployAccount, Self::L1Handler]
+    }
+
+    /// Returns the type specific part of the weight of a transaction, see
+    /// [`Transaction::total_weight`].
+    pub fn base_weight(&self) -> u64 {
+        match self {
+            Self::Invoke => constants::INVOKE_BASE_WEIGHT,
+            Self::Declare => constants::DECLARE_BASE_WEIGHT,
+            Self::DeployAccount | Self::Deploy => constants::DEPLOY_ACCOUNT_BASE_WEIGHT,
+            Self::L1Handler => constants::L1_HANDLER_BASE_WEIGHT,
+        }
+    }
 }
+
+// The conversions from and to the blockifier `TransactionType` must not have wildcard arms, so
+// that a new variant on either side fails to compile instead of being silently misrouted.
 impl From<TransactionType> for TxType {
     fn from(value: TransactionType) -> Self {
         match value {
@@ -142,13 +328,50 @@ impl From<TransactionType> for TxType {
         }
     }
 }
-impl From<TxType> for TransactionType {
-    fn from(value: TxType) -> Self {
+impl TryFrom<TxType> for TransactionType {
+    type Error = TransactionConversionError;
+
+    fn try_from(value: TxType) -> Result<Self, Self::Error> {
+        match value {
+            TxType::Declare => Ok(Self::Declare),
+            TxType::DeployAccount => Ok(Self::DeployAccount),
+            TxType::Invoke => Ok(Self::InvokeFunction),
+            TxType::L1Handler => Ok(Self::L1Handler),
+            TxType::Deploy => Err(TransactionConversionError::LegacyDeploy),
+        }
+    }
+}
+
+/// Types of the transactions sent by an account, which go through the account validation.
+///
+/// This is [`TxType`] without [`TxType::L1Handler`], see [`Transaction::account_tx_type`].
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum AccountTxType {
+    /// Regular invoke transaction.
+    Invoke,
+    /// Declare transaction.
+    Declare,
+    /// Deploy account transaction.
+    DeployAccount,
+}
+
+impl From<AccountTxType> for TxType {
+    fn from(value: AccountTxType) -> Self {
         match value {
-            TxType::Declare => Self::Declare,
-            TxType::DeployAccount => Self::DeployAccount,
-            TxType::Invoke => Self::InvokeFunction,
-            TxType::L1Handler => Self::L1Handler,
+            AccountTxType::Invoke => Self::Invoke,
+            AccountTxType::Declare => Self::Declare,
+            AccountTxType::DeployAccount => Self::DeployAccount,
         }
     }
 }
@@ -166,6 +389,7 @@ impl From<TxType> for TransactionType {
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DeclareTransaction {
     /// Transaction version.
     pub version: u8,
@@ -174,23 +398,58 @@ pub struct DeclareTransaction {
     /// Class hash to declare.
     pub compiled_class_hash: Felt252Wrapper,
     /// Contract to declare.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub contract_class: ContractClassWrapper,
     /// Account contract nonce.
     pub nonce: Felt252Wrapper,
     /// Transaction signature.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
     /// Max fee.
     pub max_fee: Felt252Wrapper,
 }
 
 impl DeclareTransaction {
+    /// Checks that the transaction carries a contract class.
+    ///
+    /// A class without program bytecode nor entry points can't be declared, and would otherwise
+    /// only be rejected once its class hash is computed.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionValidationErrorWrapper::EmptyContractClass`] if the contract class is empty.
+    pub fn check_class_present(&self) -> TransactionValidationResultWrapper<()> {
+        if self.contract_class.felt_count() == 0 {
+            return Err(TransactionValidationErrorWrapper::EmptyContractClass);
+        }
+        Ok(())
+    }
+
     /// converts the transaction to a [Transaction] object
-    pub fn from_declare(self, chain_id: &str) -> Transaction {
+    pub fn from_declare(self, chain_id: ChainId) -> Transaction {
+        self.from_declare_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts the transaction to a [Transaction] object, hashed with `H`.
+    pub fn from_declare_with_hasher<H: TransactionHasher>(self, chain_id: ChainId) -> Transaction {
+        self.to_transaction_with_hasher::<H>(chain_id)
+    }
+
+    /// Converts a borrowed transaction to a [Transaction] object.
+    ///
+    /// The hash is computed from the reference, so the only copies made are the ones of the
+    /// fields moved into the [Transaction].
+    pub fn to_transaction(&self, chain_id: ChainId) -> Transaction {
+        self.to_transaction_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts a borrowed transaction to a [Transaction] object, hashed with `H`.
+    pub fn to_transaction_with_hasher<H: TransactionHasher>(&self, chain_id: ChainId) -> Transaction {
         Transaction {
             tx_type: TxType::Declare,
             version: self.version,
-            hash: calculate_declare_tx_hash(self.clone(), chain_id),
-            signature: self.signature,
+            hash: H::declare_tx_hash(self, chain_id),
+            signature: self.signature.clone(),
             sender_address: self.sender_address,
             nonce: self.nonce,
             call_entrypoint: CallEntryPointWrapper::new(
@@ -201,9 +460,10 @@ impl DeclareTransaction {
                 self.sender_address,
                 self.sender_address,
             ),
-            contract_class: Some(self.contract_class),
+            contract_class: Some(self.contract_class.clone()),
             contract_address_salt: None,
             max_fee: self.max_fee,
+            paid_fee_on_l1: None,
         }
     }
 }
@@ -221,18 +481,23 @@ impl DeclareTransaction {
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DeployAccountTransaction {
     /// Transaction version.
     pub version: u8,
     /// Transaction sender address.
     pub sender_address: ContractAddressWrapper,
     /// Transaction calldata.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>,
     /// Account contract nonce.
     pub nonce: Felt252Wrapper,
     /// Transaction salt.
+    #[cfg_attr(feature = "std", serde(with = "crate::starknet_serde::u256_hex"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::u256))]
     pub salt: U256,
     /// Transaction signature.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
     /// Account class hash.
     pub account_class_hash: Felt252Wrapper,
@@ -241,26 +506,159 @@ pub struct DeployAccountTransaction {
 }
 
 impl DeployAccountTransaction {
+    /// Computes the address the account is deployed at, derived from its class hash, salt and
+    /// constructor calldata with a zero deployer address.
+    ///
+    /// The address is known before the transaction is executed, so it can be returned on
+    /// submission and checked for funds before the deployment.
+    ///
+    /// # Errors
+    ///
+    /// [`RPCTransactionConversionError::OutOfRange`] if the salt doesn't fit in a felt.
+    pub fn contract_address(&self) -> Result<ContractAddressWrapper, RPCTransactionConversionError> {
+        let salt = Felt252Wrapper::try_from(self.salt).map_err(|_| RPCTransactionConversionError::OutOfRange)?;
+        Ok(calculate_contract_address(salt, self.account_class_hash, &self.calldata, ContractAddressWrapper::default()))
+    }
+
+    /// converts the transaction to a [Transaction] object, checking first that the constructor
+    /// calldata is within the `DeployConstructorMaxCalldata` of `L` and that the signature is
+    /// within its `MaxSignatureSize`, see [DeployAccountTransaction::from_deploy].
+    pub fn try_from_deploy<L: TxLimits>(self, chain_id: ChainId) -> Result<Transaction, RPCTransactionConversionError> {
+        if self.calldata.len() > L::DeployConstructorMaxCalldata::get() as usize {
+            return Err(RPCTransactionConversionError::CalldataTooLong);
+        }
+        if self.signature.len() > L::MaxSignatureSize::get() as usize {
+            return Err(RPCTransactionConversionError::SignatureTooLong);
+        }
+        self.from_deploy(chain_id)
+    }
+
     /// converts the transaction to a [Transaction] object
-    pub fn from_deploy(self, chain_id: &str) -> Transaction {
-        Transaction {
+    ///
+    /// # Errors
+    ///
+    /// [`RPCTransactionConversionError::OutOfRange`] if the salt doesn't fit in a felt: it's
+    /// hashed as a felt, a salt greater than the field modulus would overflow.
+    pub fn from_deploy(self, chain_id: ChainId) -> Result<Transaction, RPCTransactionConversionError> {
+        self.from_deploy_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts the transaction to a [Transaction] object, hashed with `H`, see
+    /// [DeployAccountTransaction::from_deploy].
+    pub fn from_deploy_with_hasher<H: TransactionHasher>(
+        self,
+        chain_id: ChainId,
+    ) -> Result<Transaction, RPCTransactionConversionError> {
+        self.to_transaction_with_hasher::<H>(chain_id)
+    }
+
+    /// Converts a borrowed transaction to a [Transaction] object, see
+    /// [DeployAccountTransaction::from_deploy].
+    ///
+    /// The hash is computed from the reference, so the only copies made are the ones of the
+    /// fields moved into the [Transaction].
+    pub fn to_transaction(&self, chain_id: ChainId) -> Result<Transaction, RPCTransactionConversionError> {
+        self.to_transaction_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts a borrowed transaction to a [Transaction] object, hashed with `H`, see
+    /// [DeployAccountTransaction::from_deploy].
+    pub fn to_transaction_with_hasher<H: TransactionHasher>(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<Transaction, RPCTransactionConversionError> {
+        let salt = Felt252Wrapper::try_from(self.salt).map_err(|_| RPCTransactionConversionError::OutOfRange)?;
+        Ok(Transaction {
             tx_type: TxType::DeployAccount,
             version: self.version,
-            hash: calculate_deploy_account_tx_hash(self.clone(), chain_id),
-            signature: self.signature,
+            hash: H::deploy_account_tx_hash(self, chain_id),
+            signature: self.signature.clone(),
             sender_address: self.sender_address,
             nonce: self.nonce,
             call_entrypoint: CallEntryPointWrapper::new(
                 Some(self.account_class_hash),
                 EntryPointTypeWrapper::External,
                 None,
-                self.calldata,
+                self.calldata.clone(),
                 self.sender_address,
                 self.sender_address,
             ),
             contract_class: None,
-            contract_address_salt: Some(self.salt),
+            contract_address_salt: Some(salt),
             max_fee: self.max_fee,
+            paid_fee_on_l1: None,
+        })
+    }
+}
+
+/// Legacy deploy transaction, see [TxType::Deploy].
+///
+/// It deploys a contract without going through an account, hence without signature, nonce nor
+/// fee. It's only kept to decode historic blocks and never enters the mempool.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeployTransaction {
+    /// Transaction version.
+    pub version: u8,
+    /// Class hash of the deployed contract.
+    pub class_hash: Felt252Wrapper,
+    /// Salt of the contract address.
+    pub contract_address_salt: Felt252Wrapper,
+    /// Calldata of the contract constructor.
+    pub constructor_calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>,
+}
+
+impl DeployTransaction {
+    /// Computes the address the contract is deployed at, derived from its class hash, salt and
+    /// constructor calldata with a zero deployer address.
+    pub fn contract_address(&self) -> ContractAddressWrapper {
+        calculate_contract_address(
+            self.contract_address_salt,
+            self.class_hash,
+            &self.constructor_calldata,
+            ContractAddressWrapper::default(),
+        )
+    }
+
+    /// converts the legacy transaction to a [Transaction] object
+    ///
+    /// The sender of the transaction is the deployed contract, its nonce and max fee are zero.
+    pub fn from_deploy_legacy(self, chain_id: ChainId) -> Transaction {
+        self.from_deploy_legacy_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts the transaction to a [Transaction] object, hashed with `H`.
+    pub fn from_deploy_legacy_with_hasher<H: TransactionHasher>(self, chain_id: ChainId) -> Transaction {
+        let contract_address = self.contract_address();
+        Transaction {
+            tx_type: TxType::Deploy,
+            version: self.version,
+            hash: H::deploy_tx_hash(&self, chain_id),
+            signature: BoundedVec::default(),
+            sender_address: contract_address,
+            nonce: Felt252Wrapper::ZERO,
+            call_entrypoint: CallEntryPointWrapper::new(
+                Some(self.class_hash),
+                EntryPointTypeWrapper::Constructor,
+                None,
+                self.constructor_calldata,
+                contract_address,
+                ContractAddressWrapper::default(),
+            ),
+            contract_class: None,
+            contract_address_salt: Some(self.contract_address_salt),
+            max_fee: Felt252Wrapper::ZERO,
+            paid_fee_on_l1: None,
         }
     }
 }
@@ -275,6 +673,12 @@ pub enum TransactionConversionError {
     /// Class is missing from the object of type [Transaction]
     #[error("Class is missing from the object of type [Transaction]")]
     MissingClass,
+    /// Legacy deploy transactions have no blockifier counterpart.
+    #[error("Legacy deploy transactions have no blockifier transaction type")]
+    LegacyDeploy,
+    /// A v0 invoke transaction has no entry point selector, or a later one has one.
+    #[error("Only v0 invoke transactions have an entry point selector")]
+    InvalidEntryPointSelector,
 }
 impl TryFrom<Transaction> for DeclareTransaction {
     type Error = TransactionConversionError;
@@ -295,6 +699,12 @@ impl TryFrom<Transaction> for DeclareTransaction {
 }
 
 /// Invoke transaction.
+///
+/// v1 transactions call the `__execute__` entry point of their `sender_address`. v0 transactions
+/// call the `entry_point_selector` entry point of the contract at `sender_address` directly.
+/// Each version is hashed with its own layout, see
+/// [calculate_invoke_tx_hash](crate::crypto::commitment::calculate_invoke_tx_hash), and there is
+/// no v2 invoke transaction in the RPC specification.
 #[derive(
     Clone,
     Debug,
@@ -307,19 +717,24 @@ impl TryFrom<Transaction> for DeclareTransaction {
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct InvokeTransaction {
     /// Transaction version.
     pub version: u8,
     /// Transaction sender address.
     pub sender_address: ContractAddressWrapper,
     /// Transaction calldata.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize>,
     /// Account contract nonce.
     pub nonce: Felt252Wrapper,
     /// Transaction signature.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
     /// Max fee.
     pub max_fee: Felt252Wrapper,
+    /// Entry point called by a v0 transaction, `None` for v1 transactions.
+    pub entry_point_selector: Option<EntryPointSelector>,
 }
 
 impl From<Transaction> for InvokeTransaction {
@@ -331,31 +746,88 @@ impl From<Transaction> for InvokeTransaction {
             nonce: value.nonce,
             calldata: value.call_entrypoint.calldata,
             max_fee: value.max_fee,
+            entry_point_selector: value.call_entrypoint.entrypoint_selector,
         }
     }
 }
 
 impl InvokeTransaction {
+    /// converts the transaction to a [Transaction] object, checking first that the calldata is
+    /// within the `InvokeMaxCalldata` of `L` and the signature within its `MaxSignatureSize`.
+    pub fn try_from_invoke<L: TxLimits>(self, chain_id: ChainId) -> Result<Transaction, RPCTransactionConversionError> {
+        if self.calldata.len() > L::InvokeMaxCalldata::get() as usize {
+            return Err(RPCTransactionConversionError::CalldataTooLong);
+        }
+        if self.signature.len() > L::MaxSignatureSize::get() as usize {
+            return Err(RPCTransactionConversionError::SignatureTooLong);
+        }
+        self.from_invoke(chain_id)
+    }
+
     /// converts the transaction to a [Transaction] object
-    pub fn from_invoke(self, chain_id: &str) -> Transaction {
-        Transaction {
+    ///
+    /// # Errors
+    ///
+    /// [`RPCTransactionConversionError::InvalidEntryPointSelector`] if a v0 transaction has no
+    /// entry point selector or a later one has one.
+    pub fn from_invoke(self, chain_id: ChainId) -> Result<Transaction, RPCTransactionConversionError> {
+        self.from_invoke_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts the transaction to a [Transaction] object, hashed with `H`, see
+    /// [InvokeTransaction::from_invoke].
+    pub fn from_invoke_with_hasher<H: TransactionHasher>(
+        self,
+        chain_id: ChainId,
+    ) -> Result<Transaction, RPCTransactionConversionError> {
+        self.to_transaction_with_hasher::<H>(chain_id)
+    }
+
+    /// Converts a borrowed transaction to a [Transaction] object, see
+    /// [InvokeTransaction::from_invoke].
+    ///
+    /// The hash is computed from the reference, so the only copies made are the ones of the
+    /// fields moved into the [Transaction].
+    pub fn to_transaction(&self, chain_id: ChainId) -> Result<Transaction, RPCTransactionConversionError> {
+        self.to_transaction_with_hasher::<StarknetV0>(chain_id)
+    }
+
+    /// Converts a borrowed transaction to a [Transaction] object, hashed with `H`, see
+    /// [InvokeTransaction::from_invoke].
+    pub fn to_transaction_with_hasher<H: TransactionHasher>(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<Transaction, RPCTransactionConversionError> {
+        self.check_entry_point_selector()?;
+        Ok(Transaction {
             tx_type: TxType::Invoke,
             version: self.version,
-            hash: calculate_invoke_tx_hash(self.clone(), chain_id),
-            signature: self.signature,
+            hash: H::invoke_tx_hash(self, chain_id),
+            signature: self.signature.clone(),
             sender_address: self.sender_address,
             nonce: self.nonce,
             call_entrypoint: CallEntryPointWrapper::new(
                 None,
                 EntryPointTypeWrapper::External,
-                None,
-                self.calldata,
+                self.entry_point_selector,
+                self.calldata.clone(),
                 self.sender_address,
                 self.sender_address,
             ),
             contract_class: None,
             contract_address_salt: None,
             max_fee: self.max_fee,
+            paid_fee_on_l1: None,
+        })
+    }
+
+    /// Checks that the transaction has an entry point selector if and only if it is a v0
+    /// transaction, the other versions call the `__execute__` entry point of the account.
+    fn check_entry_point_selector(&self) -> Result<(), RPCTransactionConversionError> {
+        match (self.version, self.entry_point_selector) {
+            (0, Some(_)) => Ok(()),
+            (0, None) | (_, Some(_)) => Err(RPCTransactionConversionError::InvalidEntryPointSelector),
+            (_, None) => Ok(()),
         }
     }
 }
@@ -372,29 +844,131 @@ impl InvokeTransaction {
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Transaction {
     /// The type of the transaction.
+    #[cfg_attr(feature = "std", serde(rename = "type"))]
     pub tx_type: TxType,
     /// The version of the transaction.
     pub version: u8,
     /// Transaction hash.
+    #[cfg_attr(feature = "std", serde(rename = "transaction_hash"))]
     pub hash: Felt252Wrapper,
     /// Signature.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub signature: BoundedVec<Felt252Wrapper, MaxArraySize>,
     /// Sender Address
     pub sender_address: ContractAddressWrapper,
     /// Nonce
     pub nonce: Felt252Wrapper,
-    /// Call entrypoint
+    /// Call entrypoint, its fields are serialized as fields of the transaction.
+    #[cfg_attr(feature = "std", serde(flatten))]
     pub call_entrypoint: CallEntryPointWrapper,
     /// Contract Class
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub contract_class: Option<ContractClassWrapper>,
     /// Contract Address Salt
-    pub contract_address_salt: Option<U256>,
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub contract_address_salt: Option<Felt252Wrapper>,
+    /// Max fee.
+    pub max_fee: Felt252Wrapper,
+    /// Fee paid on L1 for the message consumed by an L1 handler transaction, [None] for the
+    /// other transaction types.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub paid_fee_on_l1: Option<Felt252Wrapper>,
+}
+
+/// Scalar fields of a [Transaction], light enough to be emitted in runtime events.
+///
+/// Unlike the RPC types it doesn't depend on `starknet_core` and is available in `no_std`.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionSummary {
+    /// Transaction hash.
+    pub hash: Felt252Wrapper,
+    /// The type of the transaction.
+    pub tx_type: TxType,
+    /// The version of the transaction.
+    pub version: u8,
+    /// Sender Address
+    pub sender_address: ContractAddressWrapper,
+    /// Nonce
+    pub nonce: Felt252Wrapper,
     /// Max fee.
     pub max_fee: Felt252Wrapper,
 }
 
+impl From<&Transaction> for TransactionSummary {
+    fn from(tx: &Transaction) -> Self {
+        Self {
+            hash: tx.hash,
+            tx_type: tx.tx_type.clone(),
+            version: tx.version,
+            sender_address: tx.sender_address,
+            nonce: tx.nonce,
+            max_fee: tx.max_fee,
+        }
+    }
+}
+
+/// Flags changing how a transaction is run by [`Transaction::simulate`].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationFlags {
+    /// Don't call the validate entrypoint of the account.
+    pub skip_validate: bool,
+    /// Compute the fee without transferring it from the account to the sequencer.
+    pub skip_fee_charge: bool,
+}
+
+/// State a transaction is admitted against by [`Transaction::validate_against`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationContext {
+    /// Current nonce of the sender account.
+    pub current_nonce: Felt252Wrapper,
+    /// Fee token balance of the sender account.
+    pub account_balance: Felt252Wrapper,
+    /// Chain id the transaction hash is checked for.
+    pub chain_id: ChainId,
+    /// Max difference allowed between the transaction and account nonces.
+    pub max_nonce_gap: u64,
+    /// Timestamp of the block the transaction is admitted for, no check depends on it yet.
+    pub block_timestamp: u64,
+}
+
+/// A field that differs between two transactions, see [`Transaction::diff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Name of the field, nested fields are prefixed with their parent, e.g.
+    /// `call_entrypoint.calldata`.
+    pub field: &'static str,
+    /// Rendering of the field of the transaction `diff` is called on.
+    pub left: String,
+    /// Rendering of the field of the other transaction.
+    pub right: String,
+}
+
 impl TryFrom<Transaction> for DeployAccountTransaction {
     type Error = TransactionConversionError;
     fn try_from(value: Transaction) -> Result<Self, Self::Error> {
@@ -404,7 +978,7 @@ impl TryFrom<Transaction> for DeployAccountTransaction {
             sender_address: value.sender_address,
             nonce: value.nonce,
             calldata: value.call_entrypoint.calldata,
-            salt: value.contract_address_salt.unwrap_or_default(),
+            salt: value.contract_address_salt.map(U256::from).unwrap_or_default(),
             account_class_hash: value.call_entrypoint.class_hash.ok_or(TransactionConversionError::MissingClassHash)?,
             max_fee: value.max_fee,
         })
@@ -412,12 +986,14 @@ impl TryFrom<Transaction> for DeployAccountTransaction {
 }
 
 /// Error of conversion between the Madara Primitive Transaction and the RPC Transaction
-#[cfg(feature = "std")]
 #[derive(Debug, Error)]
 pub enum RPCTransactionConversionError {
     /// The u8 stored version doesn't match any of the existing version at the RPC level
     #[error("Unknown version")]
     UnknownVersion,
+    /// The version exists but the RPC types have no variant for the transaction type.
+    #[error("Unsupported version {0}")]
+    UnsupportedVersion(u8),
     /// Missing information
     #[error("Missing information")]
     MissingInformation,
@@ -436,9 +1012,75 @@ pub enum RPCTransactionConversionError {
     /// Value is too large to fit into target type.
     #[error("Value is too large to fit into target type")]
     ValueTooLarge,
+    /// The calldata is longer than the limit of the transaction type.
+    #[error("Calldata is too long for the transaction type")]
+    CalldataTooLong,
+    /// The signature is longer than `MaxSignatureSize`.
+    #[error("Signature is too long")]
+    SignatureTooLong,
+    /// An array is longer than its bound.
+    #[error("Array is too long")]
+    ArrayTooLong,
+    /// The contract class couldn't be decompressed or deserialized.
+    #[error("Invalid contract class")]
+    InvalidContractClass,
+    /// The transaction type has no broadcasted form, only account transactions are broadcasted.
+    #[error("Transaction type can't be broadcasted")]
+    NotBroadcastable,
+    /// A v0 invoke transaction has no entry point selector, or a later one has one.
+    #[error("Only v0 invoke transactions have an entry point selector")]
+    InvalidEntryPointSelector,
+}
+
+impl RPCTransactionConversionError {
+    /// Returns the code of the JSON-RPC error the conversion error is reported as.
+    ///
+    /// Versions without an RPC representation are `UNSUPPORTED_TX_VERSION` (61) and invalid
+    /// classes `INVALID_CONTRACT_CLASS` (50). The other errors are malformed transactions, arrays
+    /// over their limit or misplaced entry point selectors, or malformed stored data, which the
+    /// spec has no dedicated code for: they are `UNEXPECTED_ERROR` (63) and told apart by their
+    /// [message](RPCTransactionConversionError::rpc_error_message).
+    pub fn rpc_error_code(&self) -> i64 {
+        match self {
+            Self::UnknownVersion | Self::UnsupportedVersion(_) => 61,
+            Self::InvalidContractClass => 50,
+            Self::CalldataTooLong
+            | Self::SignatureTooLong
+            | Self::ArrayTooLong
+            | Self::InvalidEntryPointSelector
+            | Self::MissingInformation
+            | Self::NotBroadcastable
+            | Self::FromArrayError
+            | Self::InvalidLength
+            | Self::InvalidCharacter
+            | Self::OutOfRange
+            | Self::ValueTooLarge => 63,
+        }
+    }
+
+    /// Returns the message of the JSON-RPC error the conversion error is reported as, see
+    /// [RPCTransactionConversionError::rpc_error_code].
+    pub fn rpc_error_message(&self) -> &'static str {
+        match self {
+            Self::UnknownVersion | Self::UnsupportedVersion(_) => "The transaction version is not supported",
+            Self::InvalidContractClass => "Invalid contract class",
+            Self::CalldataTooLong => "Invalid transaction: calldata is too long",
+            Self::SignatureTooLong => "Invalid transaction: signature is too long",
+            Self::ArrayTooLong => "Invalid transaction: array is too long",
+            Self::InvalidEntryPointSelector => {
+                "Invalid transaction: only v0 invoke transactions have an entry point selector"
+            }
+            Self::MissingInformation
+            | Self::NotBroadcastable
+            | Self::FromArrayError
+            | Self::InvalidLength
+            | Self::InvalidCharacter
+            | Self::OutOfRange
+            | Self::ValueTooLarge => "An unexpected error occurred",
+        }
+    }
 }
 
-#[cfg(feature = "std")]
 impl From<Felt252WrapperError> for RPCTransactionConversionError {
     fn from(value: Felt252WrapperError) -> Self {
         match value {
@@ -451,25 +1093,39 @@ impl From<Felt252WrapperError> for RPCTransactionConversionError {
     }
 }
 
+/// Converts a transaction to its RPC representation.
+///
+/// Invoke transactions are converted for versions 0 and 1, version 2 has no RPC variant and fails
+/// with [RPCTransactionConversionError::UnsupportedVersion]. Declare transactions are converted for
+/// versions 1 and 2. Any other version fails with [RPCTransactionConversionError::UnknownVersion].
+/// Legacy deploy transactions are converted whatever their version.
 #[cfg(feature = "std")]
-impl TryFrom<Transaction> for RPCTransaction {
-    type Error = RPCTransactionConversionError;
-    fn try_from(value: Transaction) -> Result<Self, Self::Error> {
-        let transaction_hash = value.hash.0;
-        let max_fee = value.max_fee.0;
-        let signature = value.signature.iter().map(|&f| f.0).collect();
-        let nonce = value.nonce.0;
-        let sender_address = value.sender_address.0;
-        let class_hash = value.call_entrypoint.class_hash.ok_or(RPCTransactionConversionError::MissingInformation);
-        let contract_address = value.call_entrypoint.storage_address.0;
+impl Transaction {
+    /// Converts a borrowed transaction to an [`RPCTransaction`].
+    ///
+    /// Only the fields of the RPC transaction are copied, so read-only endpoints don't need to
+    /// clone the stored transaction first.
+    ///
+    /// # Errors
+    ///
+    /// [`RPCTransactionConversionError`] if the transaction is missing a field of its RPC
+    /// representation or has an unsupported version.
+    pub fn to_rpc(&self) -> Result<RPCTransaction, RPCTransactionConversionError> {
+        let transaction_hash = self.hash.0;
+        let max_fee = self.max_fee.0;
+        let signature = self.signature.iter().map(|&f| f.0).collect();
+        let nonce = self.nonce.0;
+        let sender_address = self.sender_address.0;
+        let class_hash = self.call_entrypoint.class_hash.ok_or(RPCTransactionConversionError::MissingInformation);
+        let contract_address = self.call_entrypoint.storage_address.0;
         let entry_point_selector =
-            value.call_entrypoint.entrypoint_selector.ok_or(RPCTransactionConversionError::MissingInformation);
-        let calldata = value.call_entrypoint.calldata.iter().map(|&f| f.0).collect();
+            self.call_entrypoint.entrypoint_selector.ok_or(RPCTransactionConversionError::MissingInformation);
+        let calldata = self.call_entrypoint.calldata.iter().map(|&f| f.0).collect();
 
-        match value.tx_type {
+        match self.tx_type {
             TxType::Declare => {
                 let class_hash = class_hash?.0;
-                match value.version {
+                match self.version {
                     1 => Ok(RPCTransaction::Declare(RPCDeclareTransaction::V1(RPCDeclareTransactionV1 {
                         transaction_hash,
                         max_fee,
@@ -490,14 +1146,14 @@ impl TryFrom<Transaction> for RPCTransaction {
                     _ => Err(RPCTransactionConversionError::UnknownVersion),
                 }
             }
-            TxType::Invoke => match value.version {
+            TxType::Invoke => match self.version {
                 0 => Ok(RPCTransaction::Invoke(RPCInvokeTransaction::V0(RPCInvokeTransactionV0 {
                     transaction_hash,
                     max_fee,
                     signature,
                     nonce,
                     contract_address,
-                    entry_point_selector: entry_point_selector?.0,
+                    entry_point_selector: entry_point_selector?.0.0,
                     calldata,
                 }))),
                 1 => Ok(RPCTransaction::Invoke(RPCInvokeTransaction::V1(RPCInvokeTransactionV1 {
@@ -508,6 +1164,7 @@ impl TryFrom<Transaction> for RPCTransaction {
                     sender_address,
                     calldata,
                 }))),
+                2 => Err(RPCTransactionConversionError::UnsupportedVersion(2)),
                 _ => Err(RPCTransactionConversionError::UnknownVersion),
             },
             TxType::DeployAccount => Ok(RPCTransaction::DeployAccount(RPCDeployAccountTransaction {
@@ -515,26 +1172,390 @@ impl TryFrom<Transaction> for RPCTransaction {
                 max_fee,
                 signature,
                 nonce,
-                contract_address_salt: Felt252Wrapper::try_from(
-                    value.contract_address_salt.ok_or(RPCTransactionConversionError::MissingInformation)?,
-                )?
-                .0,
+                contract_address_salt: self
+                    .contract_address_salt
+                    .ok_or(RPCTransactionConversionError::MissingInformation)?
+                    .0,
                 constructor_calldata: calldata,
                 class_hash: class_hash?.0,
             })),
+            TxType::Deploy => Ok(RPCTransaction::Deploy(RPCDeployTransaction {
+                transaction_hash,
+                class_hash: class_hash?.0,
+                version: self.version.into(),
+                contract_address_salt: self
+                    .contract_address_salt
+                    .ok_or(RPCTransactionConversionError::MissingInformation)?
+                    .0,
+                constructor_calldata: calldata,
+            })),
             TxType::L1Handler => {
-                let nonce = TryInto::try_into(value.nonce).unwrap(); // this panics in case of overflow
+                // The spec types the nonce of an L1 handler as a u64.
+                let nonce = u64::try_from(self.nonce)?;
                 Ok(RPCTransaction::L1Handler(RPCL1HandlerTransaction {
                     transaction_hash,
-                    version: value.version.into(),
+                    version: self.version.into(),
                     nonce,
                     contract_address,
-                    entry_point_selector: entry_point_selector?.0,
+                    entry_point_selector: entry_point_selector?.0.0,
                     calldata,
                 }))
             }
         }
     }
+
+    /// Converts a borrowed transaction to its broadcasted form, the one sent by clients before the
+    /// node computes the hash.
+    ///
+    /// Declare transactions carry their class with its cairo-lang program json compressed as in
+    /// [ContractClassWrapper::to_compressed_contract_class], so it can be declared again.
+    ///
+    /// # Errors
+    ///
+    /// [`RPCTransactionConversionError::NotBroadcastable`] for deploy and L1 handler transactions,
+    /// [`RPCTransactionConversionError::MissingInformation`] if the transaction is missing a field
+    /// of its broadcasted form, and the version errors of [Transaction::to_rpc]. Declare v2
+    /// transactions fail with [`RPCTransactionConversionError::UnsupportedVersion`] as their Sierra
+    /// class isn't stored.
+    pub fn to_broadcasted(&self) -> Result<BroadcastedTransaction, RPCTransactionConversionError> {
+        let max_fee = self.max_fee.0;
+        let signature = self.signature.iter().map(|&f| f.0).collect();
+        let nonce = self.nonce.0;
+        let entry_point_selector =
+            self.call_entrypoint.entrypoint_selector.ok_or(RPCTransactionConversionError::MissingInformation);
+        let calldata = self.call_entrypoint.calldata.iter().map(|&f| f.0).collect();
+
+        match self.tx_type {
+            TxType::Invoke => match self.version {
+                0 => Ok(BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V0(
+                    BroadcastedInvokeTransactionV0 {
+                        max_fee,
+                        signature,
+                        nonce,
+                        contract_address: self.call_entrypoint.storage_address.0,
+                        entry_point_selector: entry_point_selector?.0.0,
+                        calldata,
+                    },
+                ))),
+                1 => Ok(BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(
+                    BroadcastedInvokeTransactionV1 {
+                        max_fee,
+                        signature,
+                        nonce,
+                        sender_address: self.sender_address.0,
+                        calldata,
+                    },
+                ))),
+                2 => Err(RPCTransactionConversionError::UnsupportedVersion(2)),
+                _ => Err(RPCTransactionConversionError::UnknownVersion),
+            },
+            TxType::Declare => match self.version {
+                1 => Ok(BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(
+                    BroadcastedDeclareTransactionV1 {
+                        max_fee,
+                        signature,
+                        nonce,
+                        contract_class: self
+                            .contract_class
+                            .as_ref()
+                            .ok_or(RPCTransactionConversionError::MissingInformation)?
+                            .to_compressed_contract_class()?
+                            .into(),
+                        sender_address: self.sender_address.0,
+                    },
+                ))),
+                2 => Err(RPCTransactionConversionError::UnsupportedVersion(2)),
+                _ => Err(RPCTransactionConversionError::UnknownVersion),
+            },
+            TxType::DeployAccount => Ok(BroadcastedTransaction::DeployAccount(BroadcastedDeployAccountTransaction {
+                max_fee,
+                signature,
+                nonce,
+                contract_address_salt: self
+                    .contract_address_salt
+                    .ok_or(RPCTransactionConversionError::MissingInformation)?
+                    .0,
+                constructor_calldata: calldata,
+                class_hash: self.call_entrypoint.class_hash.ok_or(RPCTransactionConversionError::MissingInformation)?.0,
+            })),
+            TxType::Deploy | TxType::L1Handler => Err(RPCTransactionConversionError::NotBroadcastable),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<Transaction> for RPCTransaction {
+    type Error = RPCTransactionConversionError;
+    fn try_from(value: Transaction) -> Result<Self, Self::Error> {
+        value.to_rpc()
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<BroadcastedInvokeTransaction> for InvokeTransaction {
+    type Error = RPCTransactionConversionError;
+
+    fn try_from(value: BroadcastedInvokeTransaction) -> Result<Self, Self::Error> {
+        Self::try_from_broadcasted::<DefaultTxLimits>(value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl InvokeTransaction {
+    /// Converts a broadcasted invoke transaction, checking its calldata against the
+    /// `InvokeMaxCalldata` of `L` and its signature against its `MaxSignatureSize`.
+    ///
+    /// A v0 transaction calls its `entry_point_selector` on its `contract_address`, which becomes
+    /// the sender address as in [Transaction::to_broadcasted].
+    pub fn try_from_broadcasted<L: TxLimits>(
+        value: BroadcastedInvokeTransaction,
+    ) -> Result<Self, RPCTransactionConversionError> {
+        let (calldata, signature) = match &value {
+            BroadcastedInvokeTransaction::V0(tx) => (&tx.calldata, &tx.signature),
+            BroadcastedInvokeTransaction::V1(tx) => (&tx.calldata, &tx.signature),
+        };
+        if calldata.len() > L::InvokeMaxCalldata::get() as usize {
+            return Err(RPCTransactionConversionError::CalldataTooLong);
+        }
+        if signature.len() > L::MaxSignatureSize::get() as usize {
+            return Err(RPCTransactionConversionError::SignatureTooLong);
+        }
+
+        match value {
+            BroadcastedInvokeTransaction::V0(tx) => Ok(Self {
+                version: 0_u8,
+                sender_address: tx.contract_address.into(),
+                calldata: bounded_felts(&tx.calldata).map_err(|_| RPCTransactionConversionError::CalldataTooLong)?,
+                nonce: tx.nonce.into(),
+                signature: bounded_felts(&tx.signature)?,
+                max_fee: tx.max_fee.into(),
+                entry_point_selector: Some(EntryPointSelector(tx.entry_point_selector.into())),
+            }),
+            BroadcastedInvokeTransaction::V1(tx) => Ok(Self {
+                version: 1_u8,
+                sender_address: tx.sender_address.into(),
+                calldata: bounded_felts(&tx.calldata).map_err(|_| RPCTransactionConversionError::CalldataTooLong)?,
+                nonce: tx.nonce.into(),
+                signature: bounded_felts(&tx.signature)?,
+                max_fee: tx.max_fee.into(),
+                entry_point_selector: None,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<BroadcastedDeclareTransaction> for DeclareTransaction {
+    type Error = RPCTransactionConversionError;
+
+    fn try_from(value: BroadcastedDeclareTransaction) -> Result<Self, Self::Error> {
+        match value {
+            BroadcastedDeclareTransaction::V1(tx) => Ok(Self {
+                version: 1_u8,
+                sender_address: tx.sender_address.into(),
+                // TODO: compute the class hash of the declared class.
+                compiled_class_hash: Felt252Wrapper::ZERO,
+                contract_class: decompress_contract_class(&tx.contract_class)?,
+                nonce: tx.nonce.into(),
+                signature: bounded_felts(&tx.signature)?,
+                max_fee: tx.max_fee.into(),
+            }),
+            // Sierra classes can't be declared yet.
+            BroadcastedDeclareTransaction::V2(_) => Err(RPCTransactionConversionError::UnknownVersion),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<BroadcastedDeployAccountTransaction> for DeployAccountTransaction {
+    type Error = RPCTransactionConversionError;
+
+    fn try_from(value: BroadcastedDeployAccountTransaction) -> Result<Self, Self::Error> {
+        let calldata: BoundedVec<Felt252Wrapper, MaxCalldataSize> =
+            bounded_felts(&value.constructor_calldata).map_err(|_| RPCTransactionConversionError::CalldataTooLong)?;
+        let mut transaction = Self {
+            version: 1_u8,
+            sender_address: ContractAddressWrapper::default(),
+            calldata,
+            nonce: value.nonce.into(),
+            salt: Felt252Wrapper::from(value.contract_address_salt).into(),
+            signature: bounded_felts(&value.signature)?,
+            account_class_hash: value.class_hash.into(),
+            max_fee: value.max_fee.into(),
+        };
+        transaction.sender_address = transaction.contract_address()?;
+
+        Ok(transaction)
+    }
+}
+
+/// Converts RPC felts to a [BoundedVec], failing with
+/// [RPCTransactionConversionError::ArrayTooLong] if there are more than `S` of them.
+#[cfg(feature = "std")]
+fn bounded_felts<S: Get<u32>>(
+    felts: &[FieldElement],
+) -> Result<BoundedVec<Felt252Wrapper, S>, RPCTransactionConversionError> {
+    BoundedVec::try_from(felts.iter().map(|&felt| felt.into()).collect::<Vec<Felt252Wrapper>>())
+        .map_err(|_| RPCTransactionConversionError::ArrayTooLong)
+}
+
+/// Builds a [ContractClassWrapper] from a contract class whose program is gzip compressed, as sent
+/// over RPC.
+#[cfg(feature = "std")]
+fn decompress_contract_class(
+    contract_class: &CompressedLegacyContractClass,
+) -> Result<ContractClassWrapper, RPCTransactionConversionError> {
+    let mut program = Vec::new();
+    GzDecoder::new(contract_class.program.as_slice())
+        .read_to_end(&mut program)
+        .map_err(|_| RPCTransactionConversionError::InvalidContractClass)?;
+    let program =
+        Program::from_bytes(&program, None).map_err(|_| RPCTransactionConversionError::InvalidContractClass)?;
+
+    let entry_points = &contract_class.entry_points_by_type;
+    let mut entry_points_by_type = BTreeMap::new();
+    for (entry_point_type, entry_points) in [
+        (EntryPointTypeWrapper::Constructor, &entry_points.constructor),
+        (EntryPointTypeWrapper::External, &entry_points.external),
+        (EntryPointTypeWrapper::L1Handler, &entry_points.l1_handler),
+    ] {
+        let entry_points = entry_points.iter().cloned().map(EntryPointWrapper::from).collect::<Vec<_>>();
+        entry_points_by_type.insert(
+            entry_point_type,
+            BoundedVec::try_from(entry_points).map_err(|_| RPCTransactionConversionError::ArrayTooLong)?,
+        );
+    }
+
+    Ok(ContractClassWrapper::new(
+        program.try_into().map_err(|_| RPCTransactionConversionError::InvalidContractClass)?,
+        BoundedBTreeMap::try_from(entry_points_by_type).map_err(|_| RPCTransactionConversionError::ArrayTooLong)?,
+    ))
+}
+
+/// Converts transactions to [`RPCTransaction`] without stopping at the first failure.
+///
+/// # Arguments
+///
+/// * `txs` - The transactions to convert.
+///
+/// # Returns
+///
+/// The successfully converted transactions, in order, and the conversion errors along with the
+/// index of the transaction that failed.
+#[cfg(feature = "std")]
+pub fn try_into_rpc_transactions(
+    txs: Vec<Transaction>,
+) -> (Vec<RPCTransaction>, Vec<(usize, RPCTransactionConversionError)>) {
+    let mut converted = Vec::with_capacity(txs.len());
+    let mut errors = Vec::new();
+    for (index, tx) in txs.into_iter().enumerate() {
+        match RPCTransaction::try_from(tx) {
+            Ok(tx) => converted.push(tx),
+            Err(e) => errors.push((index, e)),
+        }
+    }
+    (converted, errors)
+}
+
+/// Result of converting a single [`Transaction`] to an [`RPCTransaction`].
+#[cfg(feature = "std")]
+pub type RPCTransactionConversionResult = Result<RPCTransaction, RPCTransactionConversionError>;
+
+/// Lazy iterator over transactions converted to [`RPCTransaction`].
+#[cfg(feature = "std")]
+pub type RPCTransactions<I> = core::iter::Map<I, fn(Transaction) -> RPCTransactionConversionResult>;
+
+/// Lazily converts a stream of [`Transaction`] to [`RPCTransaction`].
+///
+/// Each transaction is only converted when the returned iterator is advanced, so no intermediate
+/// collection of converted transactions is built. Use [`try_into_rpc_transactions`] to convert
+/// everything upfront.
+#[cfg(feature = "std")]
+pub trait IntoRpcTransactions: IntoIterator<Item = Transaction> + Sized {
+    /// Returns an iterator converting each transaction to an [`RPCTransaction`] as it is consumed.
+    fn into_rpc(self) -> RPCTransactions<Self::IntoIter> {
+        self.into_iter().map(RPCTransaction::try_from as fn(Transaction) -> RPCTransactionConversionResult)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: IntoIterator<Item = Transaction>> IntoRpcTransactions for I {}
+
+/// Unit of the fee charged for a transaction.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeeUnit {
+    /// Fee paid in ETH.
+    Wei,
+    /// Fee paid in STRK, for v3 transactions.
+    Fri,
+}
+
+impl FeeUnit {
+    /// Returns the unit of the fee charged for a transaction of the given version.
+    pub fn from_tx_version(version: u8) -> Self {
+        match version {
+            3 => Self::Fri,
+            _ => Self::Wei,
+        }
+    }
+}
+
+/// Execution status of a transaction included in a block.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum ExecutionStatus {
+    /// The transaction was executed successfully.
+    #[default]
+    Succeeded,
+    /// The transaction was reverted, its state changes are discarded but its fee is charged.
+    Reverted,
+}
+
+/// Data availability mode of the nonce or of the fee of a v3 transaction, the layer its state
+/// diff is published on.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DataAvailabilityMode {
+    /// State diff published on L1.
+    #[default]
+    L1 = 0,
+    /// State diff published on L2.
+    L2 = 1,
 }
 
 /// Representation of a Starknet transaction receipt.
@@ -554,16 +1575,65 @@ pub struct TransactionReceiptWrapper {
     pub transaction_hash: Felt252Wrapper,
     /// Fee paid for the transaction.
     pub actual_fee: Felt252Wrapper,
+    /// Unit of the fee paid for the transaction.
+    pub fee_unit: FeeUnit,
     /// Transaction type
+    #[cfg_attr(feature = "std", serde(rename = "type"))]
     pub tx_type: TxType,
-    /// Block Number
-    pub block_number: u64,
-    /// Block Hash
-    pub block_hash: Felt252Wrapper,
+    /// Number of the block of the transaction, [None] while the transaction is pending.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub block_number: Option<u64>,
+    /// Hash of the block of the transaction, [None] while the transaction is pending.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub block_hash: Option<Felt252Wrapper>,
+    /// Block timestamp, set when the block is finalized.
+    pub block_timestamp: u64,
+    /// Address of the sequencer of the block, set when the block is finalized.
+    pub sequencer_address: ContractAddressWrapper,
+    /// Hash of the class declared by the transaction, only set for declare transactions.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub class_hash: Option<ClassHashWrapper>,
+    /// Address of the contract deployed by the transaction, only set for deploy account and
+    /// legacy deploy transactions.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub contract_address: Option<ContractAddressWrapper>,
     /// Messages sent in the transaction.
     // pub messages_sent: BoundedVec<Message, MaxArraySize>, // TODO: add messages
     /// Events emitted in the transaction.
     pub events: BoundedVec<EventWrapper, MaxArraySize>,
+    /// Whether the transaction succeeded or was reverted.
+    pub execution_status: ExecutionStatus,
+    /// Reason of the revert, encoded as UTF-8 bytes; only set if the transaction was reverted.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub revert_reason: Option<BoundedVec<u8, MaxArraySize>>,
+    /// Whether events were dropped because they didn't fit in the receipt, see
+    /// [`TransactionExecutionInfoWrapper::into_truncated_receipt`].
+    #[cfg_attr(feature = "std", serde(default, skip_serializing_if = "core::ops::Not::not"))]
+    pub events_truncated: bool,
+}
+
+impl TransactionReceiptWrapper {
+    /// Returns the commitment to the events of the receipt, in emission order.
+    ///
+    /// Each event is hashed over its `from_address`, keys and data, see
+    /// [`crate::crypto::commitment::calculate_event_hash`], and the hashes are committed to
+    /// with [`calculate_event_commitment`] and the pedersen hasher.
+    pub fn event_commitment(&self) -> Felt252Wrapper {
+        let commitment = calculate_event_commitment::<PedersenHasher>(&self.events);
+        Felt252Wrapper::try_from(commitment).expect("the commitment tree root is a felt252")
+    }
+
+    /// Returns the [`Bloom`] filter of the keys and emitter addresses of the receipt events.
+    ///
+    /// It's computed on demand, events queries can check it to skip the receipt.
+    pub fn event_bloom(&self) -> Bloom {
+        let mut bloom = Bloom::default();
+        for event in self.events.iter() {
+            bloom.accrue(event.from_address);
+            event.keys.iter().for_each(|&key| bloom.accrue(key));
+        }
+        bloom
+    }
 }
 
 #[cfg(feature = "std")]
@@ -574,38 +1644,47 @@ impl TransactionReceiptWrapper {
     /// to pass some arguments like the [`RPCTransactionStatus`] which is unknown
     /// in the [`TransactionReceiptWrapper`].
     ///
-    /// Maybe extended later for other missing fields like messages sent to L1
-    /// and the contract class for the deploy.
+    /// Maybe extended later for other missing fields like messages sent to L1.
+    /// The RPC receipts of the spec we implement have no block timestamp, sequencer address
+    /// nor fee unit, and the declare receipt has no class hash, so those aren't part of the
+    /// output. The deploy account receipt carries the address of the deployed contract.
+    ///
+    /// Those receipts have no execution status nor revert reason either: a reverted transaction
+    /// is reported with the given `status`, like a successful one, since it was included in the
+    /// block and charged. Its `execution_status` and `revert_reason` are dropped, callers have to
+    /// read them from the [`TransactionReceiptWrapper`].
+    ///
+    /// A receipt that isn't part of a block yet, without block number nor block hash, is
+    /// converted with [`Self::into_pending_receipt`] and the `status` is ignored.
     pub fn into_maybe_pending_transaction_receipt(
         self,
         status: RPCTransactionStatus,
     ) -> RPCMaybePendingTransactionReceipt {
+        let (block_hash, block_number) = match (self.block_hash, self.block_number) {
+            (Some(block_hash), Some(block_number)) => (block_hash, block_number),
+            _ => return self.into_pending_receipt(),
+        };
         let transaction_hash = self.transaction_hash.into();
         let actual_fee = self.actual_fee.into();
-        let status = status;
-        let block_hash = self.block_hash.into();
-        let block_number = self.block_number;
+        let block_hash = block_hash.into();
         let events = self.events.iter().map(|e| (*e).clone().into()).collect();
 
         // TODO: from where those message must be taken?
         let messages_sent = vec![];
 
         match self.tx_type {
-            TxType::DeployAccount => {
-                RPCMaybePendingTransactionReceipt::Receipt(RPCTransactionReceipt::DeployAccount(
-                    RPCDeployAccountTransactionReceipt {
-                        transaction_hash,
-                        actual_fee,
-                        status,
-                        block_hash,
-                        block_number,
-                        messages_sent,
-                        events,
-                        // TODO: from where can I get this one?
-                        contract_address: FieldElement::ZERO,
-                    },
-                ))
-            }
+            TxType::DeployAccount => RPCMaybePendingTransactionReceipt::Receipt(RPCTransactionReceipt::DeployAccount(
+                RPCDeployAccountTransactionReceipt {
+                    transaction_hash,
+                    actual_fee,
+                    status,
+                    block_hash,
+                    block_number,
+                    messages_sent,
+                    events,
+                    contract_address: self.contract_address.unwrap_or_default().into(),
+                },
+            )),
             TxType::Declare => RPCMaybePendingTransactionReceipt::Receipt(RPCTransactionReceipt::Declare(
                 RPCDeclareTransactionReceipt {
                     transaction_hash,
@@ -639,11 +1718,81 @@ impl TransactionReceiptWrapper {
                     events,
                 },
             )),
+            TxType::Deploy => {
+                RPCMaybePendingTransactionReceipt::Receipt(RPCTransactionReceipt::Deploy(RPCDeployTransactionReceipt {
+                    transaction_hash,
+                    actual_fee,
+                    status,
+                    block_hash,
+                    block_number,
+                    messages_sent,
+                    events,
+                    contract_address: self.contract_address.unwrap_or_default().into(),
+                }))
+            }
         }
     }
+
+    /// Converts a [`TransactionReceiptWrapper`] to the pending variant of
+    /// [`RPCMaybePendingTransactionReceipt`].
+    ///
+    /// To be used for transactions that are executed but whose block isn't finalized yet,
+    /// the block hash, block number and status are therefore omitted.
+    /// [`Self::into_maybe_pending_transaction_receipt`] picks it for receipts without a block.
+    pub fn into_pending_receipt(self) -> RPCMaybePendingTransactionReceipt {
+        let transaction_hash = self.transaction_hash.into();
+        let actual_fee = self.actual_fee.into();
+        let events = self.events.iter().map(|e| (*e).clone().into()).collect();
+
+        // TODO: from where those message must be taken?
+        let messages_sent = vec![];
+
+        let receipt = match self.tx_type {
+            TxType::DeployAccount => {
+                RPCPendingTransactionReceipt::DeployAccount(RPCPendingDeployAccountTransactionReceipt {
+                    transaction_hash,
+                    actual_fee,
+                    messages_sent,
+                    events,
+                })
+            }
+            TxType::Declare => RPCPendingTransactionReceipt::Declare(RPCPendingDeclareTransactionReceipt {
+                transaction_hash,
+                actual_fee,
+                messages_sent,
+                events,
+            }),
+            TxType::Invoke => RPCPendingTransactionReceipt::Invoke(RPCPendingInvokeTransactionReceipt {
+                transaction_hash,
+                actual_fee,
+                messages_sent,
+                events,
+            }),
+            TxType::L1Handler => RPCPendingTransactionReceipt::L1Handler(RPCPendingL1HandlerTransactionReceipt {
+                transaction_hash,
+                actual_fee,
+                messages_sent,
+                events,
+            }),
+            TxType::Deploy => RPCPendingTransactionReceipt::Deploy(RPCPendingDeployTransactionReceipt {
+                transaction_hash,
+                actual_fee,
+                messages_sent,
+                events,
+                contract_address: self.contract_address.unwrap_or_default().into(),
+            }),
+        };
+
+        RPCMaybePendingTransactionReceipt::PendingReceipt(receipt)
+    }
 }
 
 /// Representation of a Starknet event.
+///
+/// The events of a transaction are ordered with a pre-order traversal of its call tree: the
+/// events of a call, sorted by their emission order, come before the events of its inner calls,
+/// which are visited in order. Clients rely on this to address an event by its index within the
+/// transaction.
 #[derive(
     Clone,
     Debug,
@@ -655,10 +1804,13 @@ impl TransactionReceiptWrapper {
     scale_codec::MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EventWrapper {
     /// The keys (topics) of the event.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub keys: BoundedVec<Felt252Wrapper, MaxArraySize>,
     /// The data of the event.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::bounded_vec))]
     pub data: BoundedVec<Felt252Wrapper, MaxArraySize>,
     /// The address that emitted the event
     pub from_address: ContractAddressWrapper,
@@ -677,6 +1829,47 @@ impl From<EventWrapper> for RPCEvent {
     }
 }
 
+/// A Starknet event along with its position in the chain.
+///
+/// Built with [`EventWrapper::with_index`].
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedEvent {
+    /// The event.
+    pub event: EventWrapper,
+    /// The number of the block the event was emitted in.
+    pub block_number: u64,
+    /// The hash of the block the event was emitted in.
+    pub block_hash: Felt252Wrapper,
+    /// The index of the transaction that emitted the event within its block.
+    pub transaction_index: u64,
+    /// The index of the event within the events of its transaction.
+    pub event_index: u64,
+}
+
+#[cfg(feature = "std")]
+impl From<IndexedEvent> for EmittedEvent {
+    fn from(value: IndexedEvent) -> Self {
+        Self {
+            from_address: value.event.from_address.into(),
+            keys: value.event.keys.iter().map(|k| (*k).into()).collect(),
+            data: value.event.data.iter().map(|d| (*d).into()).collect(),
+            block_hash: value.block_hash.into(),
+            block_number: value.block_number,
+            transaction_hash: value.event.transaction_hash.into(),
+        }
+    }
+}
+
 /// This struct wraps the \[TransactionExecutionInfo\] type from the blockifier.
 #[derive(Debug)]
 pub struct TransactionExecutionInfoWrapper {
@@ -691,6 +1884,81 @@ pub struct TransactionExecutionInfoWrapper {
     /// Actual execution resources the transaction is charged for,
     /// including L1 gas and additional OS resources estimation.
     pub actual_resources: BTreeMap<String, usize>,
+    /// Error string of the execution if the transaction reverted; [None] otherwise.
+    pub revert_error: Option<String>,
+    /// State changes made by the transaction; [None] when not tracked or when they don't fit in a
+    /// [StateDiffWrapper], the transaction is executed either way.
+    pub state_diff: Option<StateDiffWrapper>,
+    /// Time spent executing the transaction, in microseconds; [None] when not measured, as in
+    /// `no_std` where there is no clock.
+    pub execution_duration_micros: Option<u64>,
+}
+
+/// Event emitted by a call, see [CallTraceWrapper].
+#[derive(Clone, Debug, PartialEq, Eq, scale_codec::Encode, scale_codec::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallTraceEvent {
+    /// Order of the event among all the events of the transaction.
+    pub order: u64,
+    /// Event keys.
+    pub keys: Vec<Felt252Wrapper>,
+    /// Event data.
+    pub data: Vec<Felt252Wrapper>,
+}
+
+/// Trace of a call to a contract entry point and of the calls it made.
+#[derive(Clone, Debug, PartialEq, Eq, scale_codec::Encode, scale_codec::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallTraceWrapper {
+    /// Address of the called contract.
+    pub contract_address: ContractAddressWrapper,
+    /// Selector of the called entry point.
+    pub entry_point_selector: EntryPointSelector,
+    /// Calldata of the call.
+    pub calldata: Vec<Felt252Wrapper>,
+    /// Data returned by the call.
+    pub result: Vec<Felt252Wrapper>,
+    /// Events emitted by the call itself, sorted by order.
+    pub events: Vec<CallTraceEvent>,
+    /// Calls made by the call, in execution order.
+    pub calls: Vec<CallTraceWrapper>,
+}
+
+impl From<&CallInfo> for CallTraceWrapper {
+    fn from(call_info: &CallInfo) -> Self {
+        let mut events: Vec<CallTraceEvent> = call_info
+            .execution
+            .events
+            .iter()
+            .map(|ordered_event| CallTraceEvent {
+                order: ordered_event.order as u64,
+                keys: ordered_event.event.keys.iter().map(|key| key.0.into()).collect(),
+                data: ordered_event.event.data.0.iter().map(|&felt| felt.into()).collect(),
+            })
+            .collect();
+        events.sort_by_key(|event| event.order);
+
+        Self {
+            contract_address: call_info.call.storage_address.0.0.into(),
+            entry_point_selector: call_info.call.entry_point_selector.into(),
+            calldata: call_info.call.calldata.0.iter().map(|&felt| felt.into()).collect(),
+            result: call_info.execution.retdata.0.iter().map(|&felt| felt.into()).collect(),
+            events,
+            calls: call_info.inner_calls.iter().map(Self::from).collect(),
+        }
+    }
+}
+
+/// Trace of the calls made by a transaction, as returned by `starknet_traceTransaction`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, scale_codec::Encode, scale_codec::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionTraceWrapper {
+    /// Trace of the validate call; [None] for `L1Handler`.
+    pub validate_invocation: Option<CallTraceWrapper>,
+    /// Trace of the execute call; [None] for `Declare`.
+    pub execute_invocation: Option<CallTraceWrapper>,
+    /// Trace of the fee transfer call; [None] for `L1Handler`.
+    pub fee_transfer_invocation: Option<CallTraceWrapper>,
 }
 
 /// Error enum wrapper for events.
@@ -707,20 +1975,42 @@ pub struct TransactionExecutionInfoWrapper {
 )]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventError {
-    /// Provided keys are invalid.
+    /// Provided keys are invalid, there are none or too many.
     #[error("Provided keys are invalid")]
     InvalidKeys,
-    /// Provided data is invalid.
+    /// Provided data is invalid, there is too much.
     #[error("Provided data is invalid")]
     InvalidData,
     /// Provided from address is invalid.
     #[error("Provided from address is invalid")]
     InvalidFromAddress,
-    /// Too many events
+    /// Too many events for a transaction.
     #[error("Too many events")]
     TooManyEvents,
 }
 
+/// Collects the events of a transaction, up to [MaxArraySize] of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventAccumulator {
+    events: BoundedVec<EventWrapper, MaxArraySize>,
+}
+
+/// The event that didn't fit in an [EventAccumulator].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[error("Too many events, event {index} emitted by {from_address:?} is over the limit")]
+pub struct EventOverflow {
+    /// Index of the event in the transaction.
+    pub index: usize,
+    /// Address of the contract that emitted the event.
+    pub from_address: ContractAddressWrapper,
+}
+
+impl From<EventOverflow> for EventError {
+    fn from(_: EventOverflow) -> Self {
+        Self::TooManyEvents
+    }
+}
+
 /// Error enum wrapper for state diffs.
 #[derive(
     Clone,
@@ -741,4 +2031,88 @@ pub enum StateDiffError {
     /// Couldn't register newly declared contracts.
     #[error("Couldn't register newly declared contracts")]
     DeclaredClassError,
+    /// Couldn't register storage updates.
+    #[error("Couldn't register storage updates")]
+    StorageDiffError,
+    /// Couldn't register nonce updates.
+    #[error("Couldn't register nonce updates")]
+    NonceUpdateError,
+}
+
+/// Storage updates of a contract.
+pub type StorageDiffWrapper = BoundedVec<(StorageKeyWrapper, Felt252Wrapper), MaxArraySize>;
+
+/// State changes made by a transaction.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale_codec::Encode,
+    scale_codec::Decode,
+    scale_info::TypeInfo,
+    scale_codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiffWrapper {
+    /// Storage updates, grouped by contract.
+    pub storage_diffs: BoundedVec<(ContractAddressWrapper, StorageDiffWrapper), MaxArraySize>,
+    /// New nonce of the contracts whose nonce was updated.
+    pub nonces: BoundedVec<(ContractAddressWrapper, Felt252Wrapper), MaxArraySize>,
+    /// Newly deployed contracts and their class hash.
+    pub deployed_contracts: BoundedVec<(ContractAddressWrapper, ClassHashWrapper), MaxArraySize>,
+    /// Newly declared classes.
+    pub declared_classes: BoundedVec<ClassHashWrapper, MaxArraySize>,
+}
+
+impl StateDiffWrapper {
+    /// Registers a storage update.
+    ///
+    /// Updates of the same contract must be registered consecutively, they are grouped with the
+    /// last registered contract.
+    pub fn register_storage_update(
+        &mut self,
+        contract_address: ContractAddressWrapper,
+        key: StorageKeyWrapper,
+        value: Felt252Wrapper,
+    ) -> Result<(), StateDiffError> {
+        match self.storage_diffs.last_mut() {
+            Some((address, storage_diff)) if *address == contract_address => {
+                storage_diff.try_push((key, value)).map_err(|_| StateDiffError::StorageDiffError)
+            }
+            _ => {
+                let mut storage_diff = StorageDiffWrapper::default();
+                storage_diff.try_push((key, value)).map_err(|_| StateDiffError::StorageDiffError)?;
+                self.storage_diffs
+                    .try_push((contract_address, storage_diff))
+                    .map_err(|_| StateDiffError::StorageDiffError)
+            }
+        }
+    }
+
+    /// Registers the new nonce of a contract.
+    pub fn register_nonce_update(
+        &mut self,
+        contract_address: ContractAddressWrapper,
+        nonce: Felt252Wrapper,
+    ) -> Result<(), StateDiffError> {
+        self.nonces.try_push((contract_address, nonce)).map_err(|_| StateDiffError::NonceUpdateError)
+    }
+
+    /// Registers a newly deployed contract.
+    pub fn register_deployed_contract(
+        &mut self,
+        contract_address: ContractAddressWrapper,
+        class_hash: ClassHashWrapper,
+    ) -> Result<(), StateDiffError> {
+        self.deployed_contracts
+            .try_push((contract_address, class_hash))
+            .map_err(|_| StateDiffError::DeployedContractError)
+    }
+
+    /// Registers a newly declared class.
+    pub fn register_declared_class(&mut self, class_hash: ClassHashWrapper) -> Result<(), StateDiffError> {
+        self.declared_classes.try_push(class_hash).map_err(|_| StateDiffError::DeclaredClassError)
+    }
 }
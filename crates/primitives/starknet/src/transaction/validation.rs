@@ -0,0 +1,25 @@
+//! Checks of the value returned by the account validation.
+//!
+//! Since Starknet v0.12, `__validate__`, `__validate_declare__` and `__validate_deploy__` must
+//! return [`VALIDATE_RETURN_MAGIC`] for the transaction to be valid. Transactions of versions
+//! predating the requirement are exempted, see [`VALIDATE_RETURN_MIN_VERSION`].
+use starknet_ff::FieldElement;
+
+use crate::execution::types::Felt252Wrapper;
+
+/// Value the validate entry points must return: the short string `VALID`, 0x56414c4944.
+// Built from its Montgomery representation, the only way to build a `FieldElement` in a const
+// context. The value is checked against the encoded short string in the tests.
+pub const VALIDATE_RETURN_MAGIC: Felt252Wrapper = Felt252Wrapper(FieldElement::from_mont([
+    18446732218902960001,
+    18446744073709551615,
+    18446744073709551615,
+    576259220591366032,
+]));
+
+/// Lowest transaction version whose validation must return [`VALIDATE_RETURN_MAGIC`].
+///
+/// Version 0 transactions (legacy declares) are validated by Cairo 0 accounts which return
+/// nothing. Chains running accounts that predate the requirement for every version can skip
+/// [`crate::transaction::types::Transaction::check_validate_result`] altogether.
+pub const VALIDATE_RETURN_MIN_VERSION: u8 = 1;
@@ -28,7 +28,7 @@ pub use frame_system::Call as SystemCall;
 use frame_system::EventRecord;
 use mp_starknet::crypto::hash::Hasher;
 use mp_starknet::execution::types::{
-    ClassHashWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper, StorageKeyWrapper,
+    ChainId, ClassHashWrapper, ContractAddressWrapper, ContractClassWrapper, Felt252Wrapper, StorageKeyWrapper,
 };
 use mp_starknet::transaction::types::{
     DeclareTransaction, DeployAccountTransaction, EventWrapper, InvokeTransaction, Transaction, TxType,
@@ -339,12 +339,12 @@ impl_runtime_apis! {
         }
 
         fn extrinsic_filter(xts: Vec<<Block as BlockT>::Extrinsic>) -> Vec<Transaction> {
-            let chain_id  = &Starknet::chain_id_str();
+            let chain_id = ChainId::from(Starknet::chain_id());
 
             xts.into_iter().filter_map(|xt| match xt.function {
-                RuntimeCall::Starknet( invoke { transaction }) => Some(transaction.from_invoke(chain_id)),
+                RuntimeCall::Starknet( invoke { transaction }) => transaction.from_invoke(chain_id).ok(),
                 RuntimeCall::Starknet( declare { transaction }) => Some(transaction.from_declare(chain_id)),
-                RuntimeCall::Starknet( deploy_account { transaction }) => Some(transaction.from_deploy(chain_id)),
+                RuntimeCall::Starknet( deploy_account { transaction }) => transaction.try_from_deploy::<<Runtime as pallet_starknet::Config>::TxLimits>(chain_id).ok(),
                 _ => None
             }).collect::<Vec<Transaction>>()
         }
@@ -368,6 +368,7 @@ impl_runtime_apis! {
                 TxType::L1Handler => {
                     pallet_starknet::Call::consume_l1_message{transaction}
                 }
+                TxType::Deploy => return Err(DispatchError::Other("legacy deploy transactions can't be submitted")),
             };
             Ok(UncheckedExtrinsic::new_unsigned(call.into()))
         }
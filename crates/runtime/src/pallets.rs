@@ -39,6 +39,8 @@ impl pallet_starknet::Config for Runtime {
     type SystemHash = mp_starknet::crypto::hash::pedersen::PedersenHasher;
     type TimestampProvider = Timestamp;
     type UnsignedPriority = UnsignedPriority;
+    type TxLimits = mp_starknet::traits::limits::DefaultTxLimits;
+    type MaxNonceGap = MaxNonceGap;
 }
 
 /// --------------------------------------
@@ -184,6 +186,7 @@ impl pallet_sudo::Config for Runtime {
 
 parameter_types! {
     pub const UnsignedPriority: u64 = 1 << 20;
+    pub const MaxNonceGap: u64 = 16;
 }
 
 /// A stateless module with helpers for dispatch management which does no re-authentication.